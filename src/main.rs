@@ -18,11 +18,23 @@ mod runtime;
 
 
 
-use std::env::{ args, current_exe, var };
+use std::{ env::{ args, current_exe, var },
+           process::{ ExitCode, Termination } };
 use runtime::{ built_ins::{ base_words::register_base_words,
                             io_words::register_io_words,
+                            encoding_words::register_encoding_words,
+                            hash_words::register_hash_words,
+                            json_words::register_json_words,
+                            msgpack_words::register_msgpack_words,
+                            csv_words::register_csv_words,
+                            config_words::register_config_words,
+                            regex_words::register_regex_words,
+                            fs_words::register_fs_words,
+                            path_words::register_path_words,
                             terminal_words::register_terminal_words,
                             user_words::register_user_words,
+                            time_words::register_time_words,
+                            process_words::register_process_words,
                             ffi_words::register_ffi_words },
                data_structures::{ contextual_data::ContextualData,
                                   value::Value },
@@ -79,21 +91,31 @@ fn std_lib_directory() -> error::Result<String>
 }
 
 
-fn main() -> error::Result<()>
+/// Load the standard library into the interpreter and then run either the user's script or the
+/// REPL.
+fn run(interpreter: &mut SorthInterpreter) -> error::Result<()>
 {
-    // Create the core instance of the interpreter.  Then add the standard library's location to the
-    // search path.
-    let mut interpreter = SorthInterpreter::new();
-
+    // Add the standard library's location to the search path.
     interpreter.add_search_path(&std_lib_directory()?)?;
 
     // Register the core standard library words.  These are all the words that are implemented in
     // Rust.
-    register_base_words(&mut interpreter);
-    register_io_words(&mut interpreter);
-    register_terminal_words(&mut interpreter);
-    register_user_words(&mut interpreter);
-    register_ffi_words(&mut interpreter);
+    register_base_words(interpreter);
+    register_io_words(interpreter);
+    register_encoding_words(interpreter);
+    register_hash_words(interpreter);
+    register_json_words(interpreter);
+    register_msgpack_words(interpreter);
+    register_csv_words(interpreter);
+    register_config_words(interpreter);
+    register_regex_words(interpreter);
+    register_fs_words(interpreter);
+    register_path_words(interpreter);
+    register_terminal_words(interpreter);
+    register_user_words(interpreter);
+    register_time_words(interpreter);
+    register_process_words(interpreter);
+    register_ffi_words(interpreter);
 
     // Find and process the standard library's main file.
     interpreter.process_source_file(&"std.f".to_string())?;
@@ -117,7 +139,7 @@ fn main() -> error::Result<()>
                 Ok(())
             };
 
-        add_native_word!(&mut interpreter,
+        add_native_word!(interpreter,
                         "sorth.args",
                         handler,
                         "List of command line arguments passed to the script.",
@@ -137,3 +159,21 @@ fn main() -> error::Result<()>
     // Looks like everything went well.
     Ok(())
 }
+
+
+fn main() -> ExitCode
+{
+    // Create the core instance of the interpreter and run the script or REPL with it.
+    let mut interpreter = SorthInterpreter::new();
+    let result = run(&mut interpreter);
+
+    // Give the script a chance to clean up before exiting.  If the script itself failed or requested
+    // an exit that result takes priority over the result of the hooks.
+    let hook_result = interpreter.run_exit_hooks();
+
+    match result.and(hook_result)
+    {
+        Ok(())     => ExitCode::SUCCESS,
+        Err(error) => error.report()
+    }
+}
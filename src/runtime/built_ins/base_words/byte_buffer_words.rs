@@ -1,18 +1,23 @@
 
 use crate::{ add_native_word,
-             runtime::{ data_structures::{ byte_buffer::{ Buffer,
+             runtime::{ data_structures::{ byte_buffer::{ BufferPtr,
+                                                          ByteOrder,
                                                           ByteBuffer,
-                                                          ByteBufferPtr },
-                                           value::ToValue },
+                                                          SubBuffer },
+                                           data_object::{ BinaryField,
+                                                          DataObjectPtr },
+                                           value::{ ToValue,
+                                                    Value } },
                         error::{ self,
-                                 script_error },
+                                 script_error,
+                                 script_error_str },
                         interpreter::Interpreter } };
 
 
 
 /// Make sure the next read or write will not violate the bounds of the buffer.
 fn check_buffer_index(interpreter: &mut dyn Interpreter,
-                      buffer_ptr: &ByteBufferPtr,
+                      buffer_ptr: &BufferPtr,
                       byte_size: usize) -> error::Result<()>
 {
     if buffer_ptr.borrow().position() + byte_size > buffer_ptr.borrow().len()
@@ -27,6 +32,45 @@ fn check_buffer_index(interpreter: &mut dyn Interpreter,
     Ok(())
 }
 
+/// Make sure the next write will not violate the bounds of the buffer, and that the buffer can be
+/// written to at all.
+fn check_buffer_write(interpreter: &mut dyn Interpreter,
+                      buffer_ptr: &BufferPtr,
+                      byte_size: usize) -> error::Result<()>
+{
+    if !buffer_ptr.borrow().is_writable()
+    {
+        script_error_str(interpreter, "Can not write to a read-only buffer.")?;
+    }
+
+    check_buffer_index(interpreter, buffer_ptr, byte_size)
+}
+
+/// Get the binary layout of a structure, and the number of bytes it takes up.
+fn get_layout(interpreter: &mut dyn Interpreter,
+              data_ptr: &DataObjectPtr) -> error::Result<( Vec<BinaryField>, usize )>
+{
+    let layout = data_ptr.borrow().definition_ptr.borrow().layout().clone();
+
+    match layout
+    {
+        Some(layout) =>
+            {
+                match BinaryField::layout_size(&layout)
+                {
+                    Some(size) => Ok(( layout, size )),
+                    None       => script_error_str(interpreter, "Binary layout is too large.")
+                }
+            },
+
+        None =>
+            {
+                let name = data_ptr.borrow().definition_ptr.borrow().name().clone();
+                script_error(interpreter, format!("Structure {} has no binary layout.", name))
+            }
+    }
+}
+
 
 
 /// Create a new ByteBuffer of the given size.
@@ -35,7 +79,7 @@ fn check_buffer_index(interpreter: &mut dyn Interpreter,
 fn word_buffer_new(interpreter: &mut dyn Interpreter) -> error::Result<()>
 {
     let size = interpreter.pop_as_usize()?;
-    let buffer = ByteBuffer::new_ptr(size);
+    let buffer: BufferPtr = ByteBuffer::new_ptr(size);
 
     interpreter.push(buffer.to_value());
 
@@ -63,21 +107,54 @@ fn word_buffer_resize(interpreter: &mut dyn Interpreter) -> error::Result<()>
     let size = interpreter.pop_as_usize()?;
     let buffer = interpreter.pop_as_byte_buffer()?;
 
+    if !buffer.borrow().is_resizable()
+    {
+        script_error_str(interpreter, "This buffer's size can not be changed.")?;
+    }
+
     buffer.borrow_mut().resize(size);
 
     Ok(())
 }
 
-/// Write an integer of a given size to the buffer.  The only valid sizes are 1, 2, 4, and 8 bytes.
+/// Create a view of the range start..end of a buffer.  The slice shares the buffer's storage, so
+/// writes to either are visible in both.  The slice has it's own cursor and keeps the original
+/// buffer alive.
+///
+/// Signature: `buffer start end -- slice`
+fn word_buffer_slice(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let end = interpreter.pop_as_usize()?;
+    let start = interpreter.pop_as_usize()?;
+    let buffer = interpreter.pop_as_byte_buffer()?;
+
+    let size = buffer.borrow().len();
+
+    if    start > end
+       || end > size
+    {
+        script_error(interpreter, format!("Slice range {}..{} is outside of the buffer size {}.",
+                                          start,
+                                          end,
+                                          size))?;
+    }
+
+    let slice = SubBuffer::new_ptr(buffer, start, end);
+    interpreter.push(slice.to_value());
+
+    Ok(())
+}
+
+/// Write an integer of a given size to the buffer in the given byte order.
 ///
 /// Signature: `value buffer byte-size -- `
-fn word_buffer_write_int(interpreter: &mut dyn Interpreter) -> error::Result<()>
+fn buffer_write_int(interpreter: &mut dyn Interpreter, order: ByteOrder) -> error::Result<()>
 {
     let byte_size = interpreter.pop_as_usize()?;
     let buffer_ptr = interpreter.pop_as_byte_buffer()?;
     let value = interpreter.pop_as_int()?;
 
-    check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
+    check_buffer_write(interpreter, &buffer_ptr, byte_size)?;
 
     if    (byte_size != 1)
        && (byte_size != 2)
@@ -87,16 +164,16 @@ fn word_buffer_write_int(interpreter: &mut dyn Interpreter) -> error::Result<()>
         script_error(interpreter, format!("Invalid byte size {} for integer value.", byte_size))?;
     }
 
-    buffer_ptr.borrow_mut().write_int(byte_size, value);
+    buffer_ptr.borrow_mut().write_int_ordered(byte_size, value, order);
 
     Ok(())
 }
 
-/// Read an integer of a given size from the buffer.  The only valid sizes are 1, 2, 4, and 8 bytes.
-/// If the value is signed and negative the value will be sign extended.
+/// Read an integer of a given size from the buffer in the given byte order.  If the value is signed
+/// and negative the value will be sign extended.
 ///
 /// Signature: `buffer byte-size is-signed -- value`
-fn word_buffer_read_int(interpreter: &mut dyn Interpreter) -> error::Result<()>
+fn buffer_read_int(interpreter: &mut dyn Interpreter, order: ByteOrder) -> error::Result<()>
 {
     let is_signed = interpreter.pop_as_bool()?;
     let byte_size = interpreter.pop_as_usize()?;
@@ -112,22 +189,23 @@ fn word_buffer_read_int(interpreter: &mut dyn Interpreter) -> error::Result<()>
         script_error(interpreter, format!("Invalid byte size {} for integer value.", byte_size))?;
     }
 
-    let value = buffer_ptr.borrow_mut().read_int(byte_size, is_signed);
+    let value = buffer_ptr.borrow_mut().read_int_ordered(byte_size, is_signed, order);
     interpreter.push(value.to_value());
 
     Ok(())
 }
 
-/// Write a floating point value of a given size to the buffer.  The only valid sizes are 4 and 8
-/// bytes.
+/// Write a floating point value of a given size to the buffer in the given byte order.
 ///
 /// Signature: `value buffer byte-size -- `
-fn word_buffer_write_float(interpreter: &mut dyn Interpreter) -> error::Result<()>
+fn buffer_write_float(interpreter: &mut dyn Interpreter, order: ByteOrder) -> error::Result<()>
 {
     let byte_size = interpreter.pop_as_usize()?;
     let buffer_ptr = interpreter.pop_as_byte_buffer()?;
     let value = interpreter.pop_as_float()?;
 
+    check_buffer_write(interpreter, &buffer_ptr, byte_size)?;
+
     if    (byte_size != 4)
        && (byte_size != 8)
     {
@@ -135,16 +213,15 @@ fn word_buffer_write_float(interpreter: &mut dyn Interpreter) -> error::Result<(
                                           byte_size))?;
     }
 
-    buffer_ptr.borrow_mut().write_float(byte_size, value);
+    buffer_ptr.borrow_mut().write_float_ordered(byte_size, value, order);
 
     Ok(())
 }
 
-/// Read a floating point value of a given size from the buffer.  The only valid sizes are 4 and 8
-/// bytes.
+/// Read a floating point value of a given size from the buffer in the given byte order.
 ///
 /// Signature: `buffer byte-size -- value`
-fn word_buffer_read_float(interpreter: &mut dyn Interpreter) -> error::Result<()>
+fn buffer_read_float(interpreter: &mut dyn Interpreter, order: ByteOrder) -> error::Result<()>
 {
     let byte_size = interpreter.pop_as_usize()?;
     let buffer_ptr = interpreter.pop_as_byte_buffer()?;
@@ -158,12 +235,81 @@ fn word_buffer_read_float(interpreter: &mut dyn Interpreter) -> error::Result<()
                                           byte_size))?;
     }
 
-    let value = buffer_ptr.borrow_mut().read_float(byte_size);
+    let value = buffer_ptr.borrow_mut().read_float_ordered(byte_size, order);
     interpreter.push(value.to_value());
 
     Ok(())
 }
 
+
+
+/// Write an integer of a given size to the buffer.  The only valid sizes are 1, 2, 4, and 8 bytes.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_int(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_write_int(interpreter, ByteOrder::Little)
+}
+
+/// Read an integer of a given size from the buffer.  The only valid sizes are 1, 2, 4, and 8 bytes.
+/// If the value is signed and negative the value will be sign extended.
+///
+/// Signature: `buffer byte-size is-signed -- value`
+fn word_buffer_read_int(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_read_int(interpreter, ByteOrder::Little)
+}
+
+/// Write a big endian integer of a given size to the buffer.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_int_be(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_write_int(interpreter, ByteOrder::Big)
+}
+
+/// Read a big endian integer of a given size from the buffer.
+///
+/// Signature: `buffer byte-size is-signed -- value`
+fn word_buffer_read_int_be(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_read_int(interpreter, ByteOrder::Big)
+}
+
+/// Write a floating point value of a given size to the buffer.  The only valid sizes are 4 and 8
+/// bytes.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_float(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_write_float(interpreter, ByteOrder::Little)
+}
+
+/// Read a floating point value of a given size from the buffer.  The only valid sizes are 4 and 8
+/// bytes.
+///
+/// Signature: `buffer byte-size -- value`
+fn word_buffer_read_float(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_read_float(interpreter, ByteOrder::Little)
+}
+
+/// Write a big endian floating point value of a given size to the buffer.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_float_be(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_write_float(interpreter, ByteOrder::Big)
+}
+
+/// Read a big endian floating point value of a given size from the buffer.
+///
+/// Signature: `buffer byte-size -- value`
+fn word_buffer_read_float_be(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    buffer_read_float(interpreter, ByteOrder::Big)
+}
+
 /// Write a string of a given size to the buffer.  If the string is too short it will be padded with
 /// 0s in the buffer.  If it is larger than the size it will be truncated.
 ///
@@ -174,7 +320,7 @@ fn word_buffer_write_string(interpreter: &mut dyn Interpreter) -> error::Result<
     let buffer_ptr = interpreter.pop_as_byte_buffer()?;
     let value = interpreter.pop_as_string()?;
 
-    check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
+    check_buffer_write(interpreter, &buffer_ptr, byte_size)?;
 
     buffer_ptr.borrow_mut().write_string(byte_size, &value);
 
@@ -198,6 +344,119 @@ fn word_buffer_read_string(interpreter: &mut dyn Interpreter) -> error::Result<(
     Ok(())
 }
 
+/// Write the fields of a structure to the buffer using the structure's binary layout.
+///
+/// Signature: `structure buffer -- `
+fn word_buffer_pack(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let data_ptr = interpreter.pop_as_data_object()?;
+    let ( layout, size ) = get_layout(interpreter, &data_ptr)?;
+
+    check_buffer_write(interpreter, &buffer_ptr, size)?;
+
+    let mut index = 0;
+
+    for field in layout
+    {
+        if !field.is_field()
+        {
+            buffer_ptr.borrow_mut().write_string(field.size(), &String::new());
+            continue;
+        }
+
+        let value = data_ptr.borrow().fields[index].clone();
+        let is_valid = match field
+            {
+                BinaryField::String(_) => value.is_stringable(),
+                BinaryField::Bool      => true,
+                _                      => value.is_numeric()
+            };
+
+        if !is_valid
+        {
+            let name = data_ptr.borrow().definition_ptr.borrow().field_names()[index].clone();
+
+            script_error(interpreter, format!("Field {} value {} can not be packed.",
+                                              name,
+                                              value))?;
+        }
+
+        let mut buffer = buffer_ptr.borrow_mut();
+
+        match field
+        {
+            BinaryField::Int(size, _, order) =>
+                buffer.write_int_ordered(size, value.get_int_val(), order),
+
+            BinaryField::Float(size, order) =>
+                buffer.write_float_ordered(size, value.get_float_val(), order),
+
+            BinaryField::Bool =>
+                buffer.write_int(1, value.get_bool_val() as i64),
+
+            BinaryField::String(size) =>
+                buffer.write_string(size, &value.get_string_val()),
+
+            BinaryField::Padding(_) =>
+                unreachable!()
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Read the fields of a structure from the buffer using the structure's binary layout.
+///
+/// Signature: `structure buffer -- `
+fn word_buffer_unpack(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let data_ptr = interpreter.pop_as_data_object()?;
+    let ( layout, size ) = get_layout(interpreter, &data_ptr)?;
+
+    check_buffer_index(interpreter, &buffer_ptr, size)?;
+
+    let mut buffer = buffer_ptr.borrow_mut();
+    let mut fields = Vec::with_capacity(layout.len());
+
+    for field in layout
+    {
+        let value = match field
+            {
+                // The buffer reads single bytes as unsigned, so i8 fields are sign extended here.
+                BinaryField::Int(1, true, _) =>
+                    (buffer.read_int(1, false) as u8 as i8 as i64).to_value(),
+
+                BinaryField::Int(size, is_signed, order) =>
+                    buffer.read_int_ordered(size, is_signed, order).to_value(),
+
+                BinaryField::Float(size, order) =>
+                    buffer.read_float_ordered(size, order).to_value(),
+
+                BinaryField::Bool =>
+                    Value::Bool(buffer.read_int(1, false) != 0),
+
+                BinaryField::String(size) =>
+                    buffer.read_string(size).to_value(),
+
+                BinaryField::Padding(size) =>
+                    {
+                        buffer.increment_position(size);
+                        continue;
+                    }
+            };
+
+        fields.push(value);
+    }
+
+    data_ptr.borrow_mut().fields = fields;
+
+    Ok(())
+}
+
 /// Set the position of the cursor in the buffer.  This is the position that the next read or write
 /// will occur at.
 ///
@@ -249,20 +508,56 @@ pub fn register_byte_buffer_words(interpreter: &mut dyn Interpreter)
         "Resize an existing byte buffer.",
         "size buffer -- ");
 
+    add_native_word!(interpreter, "buffer.slice", word_buffer_slice,
+        "Get a view of part of a byte buffer that shares the buffer's storage.",
+        "buffer start end -- slice");
+
     add_native_word!(interpreter, "buffer.int!", word_buffer_write_int,
-        "Write an integer of a given size to the buffer.",
+        "Write a little endian integer of a given size to the buffer.",
         "value buffer byte_size -- ");
 
     add_native_word!(interpreter, "buffer.int@", word_buffer_read_int,
-        "Read an integer of a given size from the buffer.",
+        "Read a little endian integer of a given size from the buffer.",
+        "buffer byte_size is_signed -- value");
+
+    add_native_word!(interpreter, "buffer.int.le!", word_buffer_write_int,
+        "Write a little endian integer of a given size to the buffer.",
+        "value buffer byte_size -- ");
+
+    add_native_word!(interpreter, "buffer.int.le@", word_buffer_read_int,
+        "Read a little endian integer of a given size from the buffer.",
+        "buffer byte_size is_signed -- value");
+
+    add_native_word!(interpreter, "buffer.int.be!", word_buffer_write_int_be,
+        "Write a big endian integer of a given size to the buffer.",
+        "value buffer byte_size -- ");
+
+    add_native_word!(interpreter, "buffer.int.be@", word_buffer_read_int_be,
+        "Read a big endian integer of a given size from the buffer.",
         "buffer byte_size is_signed -- value");
 
     add_native_word!(interpreter, "buffer.float!", word_buffer_write_float,
-        "Write a float of a given size to the buffer.",
+        "Write a little endian float of a given size to the buffer.",
         "value buffer byte_size -- ");
 
     add_native_word!(interpreter, "buffer.float@", word_buffer_read_float,
-        "read a float of a given size from the buffer.",
+        "read a little endian float of a given size from the buffer.",
+        "buffer byte_size -- value");
+
+    add_native_word!(interpreter, "buffer.float.le!", word_buffer_write_float,
+        "Write a little endian float of a given size to the buffer.",
+        "value buffer byte_size -- ");
+
+    add_native_word!(interpreter, "buffer.float.le@", word_buffer_read_float,
+        "Read a little endian float of a given size from the buffer.",
+        "buffer byte_size -- value");
+
+    add_native_word!(interpreter, "buffer.float.be!", word_buffer_write_float_be,
+        "Write a big endian float of a given size to the buffer.",
+        "value buffer byte_size -- ");
+
+    add_native_word!(interpreter, "buffer.float.be@", word_buffer_read_float_be,
+        "Read a big endian float of a given size from the buffer.",
         "buffer byte_size -- value");
 
     add_native_word!(interpreter, "buffer.string!", word_buffer_write_string,
@@ -273,6 +568,14 @@ pub fn register_byte_buffer_words(interpreter: &mut dyn Interpreter)
         "Read a string of a given max size from the buffer.",
         "size buffer -- value");
 
+    add_native_word!(interpreter, "buffer.pack", word_buffer_pack,
+        "Write a structure to the buffer using the structure's binary layout.",
+        "structure buffer -- ");
+
+    add_native_word!(interpreter, "buffer.unpack", word_buffer_unpack,
+        "Read a structure's fields from the buffer using the structure's binary layout.",
+        "structure buffer -- ");
+
     add_native_word!(interpreter, "buffer.position!", word_buffer_set_position,
         "Set the position of the buffer pointer.",
         "position buffer -- ");
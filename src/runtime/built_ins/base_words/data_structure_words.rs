@@ -1,8 +1,10 @@
 
 use crate::{ add_native_word,
              location_here,
-             runtime::{ data_structures::{ data_object::{ DataObject,
+             runtime::{ data_structures::{ data_object::{ BinaryField,
+                                                          DataObject,
                                                           DataObjectDefinition,
+                                                          DataObjectDefinitionPtr,
                                                           DataObjectPtr },
                                            value::ToValue,
                                            value_vec::ValueVec },
@@ -29,6 +31,24 @@ fn check_index(interpreter: &mut dyn Interpreter,
 }
 
 
+/// Find the most recent structure definition with the given name.
+fn find_definition(interpreter: &mut dyn Interpreter,
+                   name: &String) -> error::Result<DataObjectDefinitionPtr>
+{
+    let found = interpreter.structure_definitions()
+                           .iter()
+                           .filter(|definition| definition.borrow().name() == name)
+                           .last()
+                           .cloned();
+
+    match found
+    {
+        Some(definition) => Ok(definition),
+        None             => script_error(interpreter, format!("Structure {} not found.", name))
+    }
+}
+
+
 
 /// Create a new structure definition and add it's helper words to the interpreter.  This word is
 /// not intended to be called directly user code.  Instead a Forth word is defined that handles
@@ -166,6 +186,87 @@ fn word_structure_compare(interpreter: &mut dyn Interpreter) -> error::Result<()
 }
 
 
+/// Attach a binary layout to a structure so that it can be packed into and unpacked from byte
+/// buffers.  The layout is an array of type names with one entry for each field in order, plus any
+/// padding entries.  See BinaryField::parse for the supported types.
+///
+/// Signature: `struct-name layout -- `
+fn word_structure_set_layout(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let layout_values = interpreter.pop_as_array()?;
+    let name = interpreter.pop_as_string()?;
+    let definition_ptr = find_definition(interpreter, &name)?;
+
+    let mut layout = Vec::with_capacity(layout_values.borrow().len());
+
+    for value in layout_values.borrow().iter()
+    {
+        let field = if value.is_stringable()
+            {
+                BinaryField::parse(&value.get_string_val())
+            }
+            else
+            {
+                None
+            };
+
+        match field
+        {
+            Some(field) => layout.push(field),
+            None        => script_error(interpreter, format!("Invalid layout type {} for {}.",
+                                                             value,
+                                                             name))?
+        }
+    }
+
+    let layout_fields = layout.iter().filter(|field| field.is_field()).count();
+    let field_count = definition_ptr.borrow().field_names().len();
+
+    if layout_fields != field_count
+    {
+        script_error(interpreter, format!("Layout for {} describes {} fields, expected {}.",
+                                          name,
+                                          layout_fields,
+                                          field_count))?;
+    }
+
+    if BinaryField::layout_size(&layout).is_none()
+    {
+        script_error(interpreter, format!("Layout for {} is too large.", name))?;
+    }
+
+    definition_ptr.borrow_mut().set_layout(layout);
+
+    Ok(())
+}
+
+/// Get the number of bytes a structure takes up when packed into a byte buffer.
+///
+/// Signature: `struct-name -- size`
+fn word_structure_layout_size(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let name = interpreter.pop_as_string()?;
+    let definition_ptr = find_definition(interpreter, &name)?;
+
+    let size = match definition_ptr.borrow().layout()
+        {
+            Some(layout) => BinaryField::layout_size(layout),
+            None         => return script_error(interpreter,
+                                                format!("Structure {} has no binary layout.",
+                                                        name))
+        };
+
+    let Some(size) = size
+        else
+        {
+            return script_error(interpreter, format!("Layout for {} is too large.", name));
+        };
+
+    interpreter.push(size.to_value());
+
+    Ok(())
+}
+
 
 /// Register the structs `sorth.word` and `sorth.location` with the interpreter.
 fn register_word_info_struct(interpreter: &mut dyn Interpreter)
@@ -242,5 +343,13 @@ pub fn register_data_structure_words(interpreter: &mut dyn Interpreter)
         "Check if two structures are the same.",
         "a b -- boolean");
 
+    add_native_word!(interpreter, "#.layout!", word_structure_set_layout,
+        "Give a structure a binary layout for use with buffer.pack and buffer.unpack.",
+        "struct_name layout -- ");
+
+    add_native_word!(interpreter, "#.layout.size@", word_structure_layout_size,
+        "Get the number of bytes a structure's binary layout takes up.",
+        "struct_name -- size");
+
     register_word_info_struct(interpreter);
 }
@@ -4,7 +4,7 @@ use crate::{ add_native_immediate_word,
              add_native_word,
              lang::compilation::process_token,
              runtime::{ data_structures::value::{ ToValue, Value},
-             error::{ self, script_error, script_error_str },
+             error::{ self, script_error, script_error_str, ScriptError },
              interpreter::Interpreter } };
 
 
@@ -248,6 +248,52 @@ fn word_throw(interpreter: &mut dyn Interpreter) -> error::Result<()>
     script_error(interpreter, message)
 }
 
+/// Push the exit code used to report a successful run.
+///
+/// Signature: ` -- exit-code`
+fn word_exit_success(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    interpreter.push(0i64.to_value());
+    Ok(())
+}
+
+/// Push the exit code used to report a failed run.
+///
+/// Signature: ` -- exit-code`
+fn word_exit_failure(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    interpreter.push(1i64.to_value());
+    Ok(())
+}
+
+/// Exit the interpreter with the given exit code.  The request unwinds the entire call stack, it
+/// can not be caught by a try/catch block.  Any registered exit hooks are run before the process
+/// exits.
+///
+/// Signature: `exit-code -- `
+fn word_sorth_exit(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let exit_code = interpreter.pop_as_int()?;
+    Err(ScriptError::new_exit_request(exit_code))
+}
+
+/// Register a word to be run before the interpreter exits.  Hooks are run in the reverse order
+/// they were registered.
+///
+/// Signature: `word-index -- `
+fn word_sorth_at_exit(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let index = interpreter.pop_as_usize()?;
+
+    if interpreter.word_handler_info(index).is_none()
+    {
+        script_error(interpreter, format!("Word index {} is not a valid word.", index))?;
+    }
+
+    interpreter.add_exit_hook(index);
+    Ok(())
+}
+
 /// Create a new thread and run the the specified word and return the new thread id.
 ///
 /// Signature: `word-index -- thread-id`
@@ -346,6 +392,22 @@ pub fn register_sorth_words(interpreter: &mut dyn Interpreter)
         "Throw an exception with the given message.",
         "message -- ");
 
+    add_native_word!(interpreter, "exit_success", word_exit_success,
+        "The exit code for a successful run.",
+        " -- exit_code");
+
+    add_native_word!(interpreter, "exit_failure", word_exit_failure,
+        "The exit code for a failed run.",
+        " -- exit_code");
+
+    add_native_word!(interpreter, "sorth.exit", word_sorth_exit,
+        "Run the exit hooks and exit the interpreter with the given code.",
+        "exit_code -- ");
+
+    add_native_word!(interpreter, "sorth.at-exit", word_sorth_at_exit,
+        "Register a word to be run before the interpreter exits.",
+        "word_index -- ");
+
     add_native_word!(interpreter, "thread.new", word_thread_new,
         "Create a new thread and run the specified word and return the new thread id.",
         "word-index -- thread-id");
@@ -2,7 +2,10 @@
 use std::sync::atomic::{ AtomicUsize,
                          Ordering };
 use crate::{ add_native_word,
-             runtime::{ data_structures::value::ToValue,
+             runtime::{ built_ins::encoding_words::push_bytes,
+                        data_structures::{ value::{ ToValue,
+                                                    Value },
+                                           value_vec::ValueVec },
                         error::{ self,
                                  script_error },
                         interpreter::Interpreter } };
@@ -48,6 +51,523 @@ fn char_index_to_byte_index(interpreter: &mut dyn Interpreter,
 
 
 
+/// Get the integer bits of a value for hex, octal, or binary output.  Floats give their raw bits
+/// and single character strings give their character code, while other strings have no bits.
+fn value_to_bits(value: &Value) -> Option<i64>
+{
+    match value
+    {
+        Value::Float(value)                                 => Some(value.to_bits() as i64),
+        Value::String(value) if value.len() == 1            => value.chars().next()
+                                                                    .map(|next| next as i64),
+        Value::String(_)                                    => None,
+        _ if value.is_numeric()                             => Some(value.get_int_val()),
+        _                                                   => None
+    }
+}
+
+
+
+/// How a formatted value is aligned within it's field width.
+#[derive(Clone, Copy, PartialEq)]
+enum FormatAlignment
+{
+    Left,
+    Center,
+    Right
+}
+
+
+/// Which argument a format placeholder takes it's value from.
+enum FormatArgument
+{
+    /// The next positional argument.
+    Next,
+
+    /// A positional argument by index, where 0 is the deepest argument on the stack.
+    Index(usize),
+
+    /// A named argument read from a hash table.
+    Name(String)
+}
+
+
+/// The formatting options of a single placeholder.
+struct FormatSpecifier
+{
+    fill: char,
+    alignment: Option<FormatAlignment>,
+    sign: bool,
+    alternate: bool,
+    zero_pad: bool,
+    width: usize,
+    thousands: bool,
+    precision: Option<usize>,
+    kind: Option<char>
+}
+
+
+impl Default for FormatSpecifier
+{
+    fn default() -> FormatSpecifier
+    {
+        FormatSpecifier
+            {
+                fill: ' ',
+                alignment: None,
+                sign: false,
+                alternate: false,
+                zero_pad: false,
+                width: 0,
+                thousands: false,
+                precision: None,
+                kind: None
+            }
+    }
+}
+
+
+/// A piece of a parsed format string, either literal text or a placeholder to be filled in.
+enum FormatPiece
+{
+    Text(String),
+    Placeholder(FormatArgument, FormatSpecifier)
+}
+
+
+/// Convert an alignment character into it's alignment.
+fn to_alignment(next: Option<char>) -> Option<FormatAlignment>
+{
+    match next
+    {
+        Some('<') => Some(FormatAlignment::Left),
+        Some('^') => Some(FormatAlignment::Center),
+        Some('>') => Some(FormatAlignment::Right),
+        _         => None
+    }
+}
+
+
+/// The widest a placeholder can be padded to, and the most digits of precision it can ask for.
+const MAX_FORMAT_WIDTH: usize = u16::MAX as usize;
+
+
+/// Read a run of decimal digits, if there are any.  Numbers too large to fit saturate, and are
+/// caught by the width and precision limits.
+fn read_format_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<usize>
+{
+    let mut number: Option<usize> = None;
+
+    while let Some(digit) = chars.peek().and_then(|next| next.to_digit(10))
+    {
+        number = Some(number.unwrap_or(0).saturating_mul(10).saturating_add(digit as usize));
+        chars.next();
+    }
+
+    number
+}
+
+
+/// Parse the original style of specifier, `[align][fill][width][x]`, as used by format strings
+/// that don't have a colon.
+fn parse_legacy_specifier(text: &str) -> FormatSpecifier
+{
+    let mut specifier = FormatSpecifier::default();
+    let mut chars = text.chars().peekable();
+
+    specifier.alignment = to_alignment(chars.peek().cloned());
+
+    if specifier.alignment.is_some()
+    {
+        chars.next();
+    }
+
+    // Any character but 1-9 is a fill character, unless it's the hex flag on it's own.
+    if let Some(&next) = chars.peek()
+    {
+        let is_hex_flag = (next == 'x' || next == 'X') && chars.clone().nth(1).is_none();
+
+        if !('1'..='9').contains(&next) && !is_hex_flag
+        {
+            specifier.fill = next;
+            chars.next();
+        }
+    }
+
+    specifier.width = read_format_number(&mut chars).unwrap_or(0);
+
+    if let Some(next @ ('x' | 'X')) = chars.peek().cloned()
+    {
+        specifier.kind = Some(next);
+    }
+
+    specifier
+}
+
+
+/// Parse a specifier following a colon, `[[fill]align][+][#][0][width][,][.precision][type]`.
+fn parse_specifier(text: &str) -> Result<FormatSpecifier, String>
+{
+    let mut specifier = FormatSpecifier::default();
+    let mut chars = text.chars().peekable();
+    let mut lookahead = chars.clone();
+    let first = lookahead.next();
+
+    if let Some(alignment) = to_alignment(lookahead.next())
+    {
+        specifier.fill = first.unwrap_or(' ');
+        specifier.alignment = Some(alignment);
+        chars.next();
+        chars.next();
+    }
+    else if let Some(alignment) = to_alignment(first)
+    {
+        specifier.alignment = Some(alignment);
+        chars.next();
+    }
+
+    if chars.next_if_eq(&'+').is_some()
+    {
+        specifier.sign = true;
+    }
+
+    if chars.next_if_eq(&'#').is_some()
+    {
+        specifier.alternate = true;
+    }
+
+    if chars.next_if_eq(&'0').is_some()
+    {
+        specifier.zero_pad = true;
+    }
+
+    specifier.width = read_format_number(&mut chars).unwrap_or(0);
+
+    if chars.next_if_eq(&',').is_some()
+    {
+        specifier.thousands = true;
+    }
+
+    if chars.next_if_eq(&'.').is_some()
+    {
+        match read_format_number(&mut chars)
+        {
+            Some(precision) => specifier.precision = Some(precision),
+            None            => return Err(format!("Missing precision in format specifier {}.",
+                                                  text))
+        }
+    }
+
+    if let Some(kind) = chars.next_if(|next| "?xXbo".contains(*next) || *next == 'e')
+    {
+        specifier.kind = Some(kind);
+    }
+
+    if chars.next().is_some()
+    {
+        return Err(format!("Invalid format specifier {}.", text));
+    }
+
+    Ok(specifier)
+}
+
+
+/// Does the text of a placeholder without a colon follow the original specifier syntax,
+/// `[align][fill][width][x]`?  Such text is never taken as a named argument, so a name that looks
+/// like a specifier, such as {a10}, has to be written with a colon, {a10:}.
+fn is_legacy_specifier(text: &str) -> bool
+{
+    let mut chars = text.chars().peekable();
+
+    let _ = chars.next_if(|next| "<^>".contains(*next));
+    let _ = chars.next_if(|next| !('1'..='9').contains(next));
+
+    while chars.next_if(|next| next.is_ascii_digit()).is_some()
+    {
+    }
+
+    let _ = chars.next_if(|next| *next == 'x' || *next == 'X');
+
+    chars.next().is_none()
+}
+
+
+/// Is the text a name of a named argument?
+fn is_format_name(text: &str) -> bool
+{
+       text.starts_with(|next: char| next.is_alphabetic())
+    && text.chars().all(|next| next.is_alphanumeric() || "_-.".contains(next))
+}
+
+
+/// Parse the contents of a {} placeholder.
+fn parse_placeholder(text: &str) -> Result<( FormatArgument, FormatSpecifier ), String>
+{
+    let ( argument, specifier ) = parse_placeholder_parts(text)?;
+
+    if    specifier.width > MAX_FORMAT_WIDTH
+       || specifier.precision.is_some_and(|precision| precision > MAX_FORMAT_WIDTH)
+    {
+        return Err(format!("The width or precision of format specifier {{{}}} is too large.",
+                           text));
+    }
+
+    Ok(( argument, specifier ))
+}
+
+
+/// Split a placeholder into the argument it refers to and it's formatting options.
+fn parse_placeholder_parts(text: &str) -> Result<( FormatArgument, FormatSpecifier ), String>
+{
+    let Some(( argument, specifier )) = text.split_once(':')
+        else
+        {
+            // Without a colon this is either an original style specifier or a named argument.
+            if !is_legacy_specifier(text) && is_format_name(text)
+            {
+                return Ok(( FormatArgument::Name(text.to_string()), FormatSpecifier::default() ));
+            }
+
+            return Ok(( FormatArgument::Next, parse_legacy_specifier(text) ));
+        };
+
+    let argument = if argument.is_empty()
+        {
+            FormatArgument::Next
+        }
+        else if argument.chars().all(|next| next.is_ascii_digit())
+        {
+            // The index has to leave room to count the arguments up to and including it.
+            match argument.parse::<usize>()
+            {
+                Ok(index) if index < usize::MAX => FormatArgument::Index(index),
+                _ => return Err(format!("Invalid format argument index {}.", argument))
+            }
+        }
+        else if is_format_name(argument)
+        {
+            FormatArgument::Name(argument.to_string())
+        }
+        else
+        {
+            return Err(format!("Invalid format argument {}.", argument));
+        };
+
+    Ok(( argument, parse_specifier(specifier)? ))
+}
+
+
+/// Break a format string up into it's literal text and placeholders.  A literal { is written as {{,
+/// while a } outside of a placeholder is always literal text, so }} is written as is.
+fn parse_format(format: &str) -> Result<Vec<FormatPiece>, String>
+{
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(next) = chars.next()
+    {
+        match next
+        {
+            '{' if chars.next_if_eq(&'{').is_some() => text.push('{'),
+
+            '{' =>
+                {
+                    let mut placeholder = String::new();
+                    let mut closed = false;
+
+                    for next in chars.by_ref()
+                    {
+                        if next == '}'
+                        {
+                            closed = true;
+                            break;
+                        }
+
+                        placeholder.push(next);
+                    }
+
+                    if !closed
+                    {
+                        return Err("Missing closing } in format specifier.".to_string());
+                    }
+
+                    let ( argument, specifier ) = parse_placeholder(&placeholder)?;
+
+                    pieces.push(FormatPiece::Text(std::mem::take(&mut text)));
+                    pieces.push(FormatPiece::Placeholder(argument, specifier));
+                },
+
+            _ => text.push(next)
+        }
+    }
+
+    pieces.push(FormatPiece::Text(text));
+
+    Ok(pieces)
+}
+
+
+/// Insert thousands separators into the integer part of a string of decimal digits.
+fn add_thousands_separators(digits: &str) -> String
+{
+    let ( integer, rest ) = match digits.find(|next: char| !next.is_ascii_digit())
+        {
+            Some(index) => digits.split_at(index),
+            None        => ( digits, "" )
+        };
+
+    let mut result = String::new();
+
+    for ( index, digit ) in integer.chars().enumerate()
+    {
+        if index > 0 && (integer.len() - index) % 3 == 0
+        {
+            result.push(',');
+        }
+
+        result.push(digit);
+    }
+
+    result + rest
+}
+
+
+/// Format a single value according to it's specifier.
+fn format_value(value: &Value, specifier: &FormatSpecifier) -> Result<String, String>
+{
+    let is_number = matches!(value, Value::Int(_) | Value::Float(_));
+
+    // Numbers are built up as a sign, a radix prefix, and then the digits so that zero padding can
+    // be placed between the prefix and the digits.
+    let ( mut sign, prefix, digits ) = match specifier.kind
+        {
+            Some('?') =>
+                {
+                    let text = match value
+                        {
+                            Value::String(text) => Value::stringify(text),
+                            _                   => value.to_string()
+                        };
+
+                    ( String::new(), "", text )
+                },
+
+            Some(kind @ ( 'x' | 'X' | 'b' | 'o' )) =>
+                {
+                    let Some(bits) = value_to_bits(value)
+                        else
+                        {
+                            return Err(format!("Value {} can not be formatted as a number.",
+                                               value));
+                        };
+
+                    match kind
+                    {
+                        'x' => ( String::new(), "0x", format!("{:x}", bits) ),
+                        'X' => ( String::new(), "0x", format!("{:X}", bits) ),
+                        'b' => ( String::new(), "0b", format!("{:b}", bits) ),
+                        _   => ( String::new(), "0o", format!("{:o}", bits) )
+                    }
+                },
+
+            Some(_) =>
+                {
+                    if !is_number
+                    {
+                        return Err(format!("Value {} can not be formatted as a number.", value));
+                    }
+
+                    let number = value.get_float_val();
+                    let text = match specifier.precision
+                        {
+                            Some(precision) => format!("{:.*e}", precision, number.abs()),
+                            None            => format!("{:e}", number.abs())
+                        };
+
+                    ( if number < 0.0 { "-".to_string() } else { String::new() }, "", text )
+                },
+
+            None if is_number =>
+                {
+                    let ( negative, mut text ) = match ( value, specifier.precision )
+                        {
+                            ( Value::Int(number), None ) =>
+                                ( *number < 0, number.unsigned_abs().to_string() ),
+
+                            ( _, Some(precision) ) =>
+                                {
+                                    let number = value.get_float_val();
+                                    ( number < 0.0, format!("{:.*}", precision, number.abs()) )
+                                },
+
+                            _ =>
+                                {
+                                    let number = value.get_float_val();
+                                    ( number < 0.0, Value::Float(number.abs()).to_string() )
+                                }
+                        };
+
+                    if specifier.thousands
+                    {
+                        text = add_thousands_separators(&text);
+                    }
+
+                    ( if negative { "-".to_string() } else { String::new() }, "", text )
+                },
+
+            None =>
+                {
+                    let text = match value
+                        {
+                            Value::String(text) => text.clone(),
+                            _                   => value.to_string()
+                        };
+
+                    let text = match specifier.precision
+                        {
+                            Some(precision) => text.chars().take(precision).collect(),
+                            None            => text
+                        };
+
+                    ( String::new(), "", text )
+                }
+        };
+
+    if specifier.sign && is_number && sign.is_empty() && specifier.kind != Some('?')
+    {
+        sign = "+".to_string();
+    }
+
+    let prefix = if specifier.alternate { prefix } else { "" };
+    let length = sign.chars().count() + prefix.len() + digits.chars().count();
+    let padding = specifier.width.saturating_sub(length);
+
+    if specifier.zero_pad && is_number
+    {
+        return Ok(format!("{}{}{}{}", sign, prefix, "0".repeat(padding), digits));
+    }
+
+    let alignment = match specifier.alignment
+        {
+            Some(alignment)                => alignment,
+            None if value.is_numeric()     => FormatAlignment::Right,
+            None                           => FormatAlignment::Left
+        };
+
+    let ( left, right ) = match alignment
+        {
+            FormatAlignment::Left   => ( 0, padding ),
+            FormatAlignment::Center => ( padding / 2, padding - (padding / 2) ),
+            FormatAlignment::Right  => ( padding, 0 )
+        };
+
+    let fill = |count: usize| std::iter::repeat_n(specifier.fill, count).collect::<String>();
+
+    Ok(format!("{}{}{}{}{}", fill(left), sign, prefix, digits, fill(right)))
+}
+
+
 /// Get the length of a string in logical characters.
 ///
 /// Signature: `string -- size`
@@ -203,6 +723,388 @@ fn word_string_to_number(interpreter: &mut dyn Interpreter) -> error::Result<()>
     Ok(())
 }
 
+/// Convert a string to upper case.
+///
+/// Signature: `string -- upper-string`
+fn word_string_upper(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    interpreter.push(string.to_uppercase().to_value());
+    Ok(())
+}
+
+/// Convert a string to lower case.
+///
+/// Signature: `string -- lower-string`
+fn word_string_lower(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    interpreter.push(string.to_lowercase().to_value());
+    Ok(())
+}
+
+/// Remove the whitespace from both ends of a string.
+///
+/// Signature: `string -- trimmed-string`
+fn word_string_trim(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    interpreter.push(string.trim().to_string().to_value());
+    Ok(())
+}
+
+/// Remove the whitespace from the start of a string.
+///
+/// Signature: `string -- trimmed-string`
+fn word_string_trim_left(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    interpreter.push(string.trim_start().to_string().to_value());
+    Ok(())
+}
+
+/// Remove the whitespace from the end of a string.
+///
+/// Signature: `string -- trimmed-string`
+fn word_string_trim_right(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    interpreter.push(string.trim_end().to_string().to_value());
+    Ok(())
+}
+
+/// Check if a string starts with the given prefix.
+///
+/// Signature: `prefix string -- bool`
+fn word_string_starts_with(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+    let prefix = interpreter.pop_as_string()?;
+
+    interpreter.push(string.starts_with(&prefix).to_value());
+    Ok(())
+}
+
+/// Check if a string ends with the given suffix.
+///
+/// Signature: `suffix string -- bool`
+fn word_string_ends_with(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+    let suffix = interpreter.pop_as_string()?;
+
+    interpreter.push(string.ends_with(&suffix).to_value());
+    Ok(())
+}
+
+/// Replace every instance of a sub-string within a string.
+///
+/// Signature: `search-string replacement string -- updated-string`
+fn word_string_replace(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+    let replacement = interpreter.pop_as_string()?;
+    let search_string = interpreter.pop_as_string()?;
+
+    if search_string.is_empty()
+    {
+        script_error(interpreter, "Can not replace an empty search string.".to_string())?;
+    }
+
+    interpreter.push(string.replace(&search_string, &replacement).to_value());
+    Ok(())
+}
+
+/// Join an array of values into a single string, placing the separator between each one.
+///
+/// Signature: `separator array -- string`
+fn word_string_join(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let array = interpreter.pop_as_array()?;
+    let separator = interpreter.pop_as_string()?;
+
+    let parts: Vec<String> = array.borrow()
+                                  .iter()
+                                  .map(|value|
+                                      {
+                                          match value
+                                          {
+                                              Value::String(string) => string.clone(),
+                                              _                     => value.to_string()
+                                          }
+                                      })
+                                  .collect();
+
+    interpreter.push(parts.join(&separator).to_value());
+    Ok(())
+}
+
+/// Create an empty string with room for a result of the given size in bytes.  Sizes that overflow
+/// or that can't be allocated are reported as script errors instead of taking down the interpreter.
+fn sized_string(interpreter: &mut dyn Interpreter, size: Option<usize>) -> error::Result<String>
+{
+    let mut string = String::new();
+
+    match size
+    {
+        Some(size) if string.try_reserve_exact(size).is_ok() => Ok(string),
+        Some(size) => script_error(interpreter, format!("Can not create a string of {} bytes.",
+                                                        size)),
+        None       => script_error(interpreter, "The resulting string is too large.".to_string())
+    }
+}
+
+/// Repeat a string the given number of times.
+///
+/// Signature: `count string -- repeated-string`
+fn word_string_repeat(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+    let count = interpreter.pop_as_int()?;
+
+    if count < 0
+    {
+        script_error(interpreter, format!("Can not repeat a string {} times.", count))?;
+    }
+
+    if string.is_empty()
+    {
+        interpreter.push(string.to_value());
+        return Ok(());
+    }
+
+    let mut repeated = sized_string(interpreter, string.len().checked_mul(count as usize))?;
+
+    for _ in 0..count
+    {
+        repeated.push_str(&string);
+    }
+
+    interpreter.push(repeated.to_value());
+    Ok(())
+}
+
+/// Pop the padding arguments and pad a string out to a width in logical characters.  Strings that
+/// are already at least as wide are left as is.
+fn pad_string(interpreter: &mut dyn Interpreter, pad_left: bool) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+    let pad = interpreter.pop_as_string()?;
+    let width = interpreter.pop_as_int()?;
+
+    let mut pad_chars = pad.chars();
+
+    let pad_char = match ( pad_chars.next(), pad_chars.next() )
+        {
+            ( Some(pad_char), None ) => pad_char,
+            _ => return script_error(interpreter,
+                                     format!("Padding {} must be a single character.", pad))
+        };
+
+    let char_count = string.chars().count() as i64;
+    let pad_count = width.saturating_sub(char_count).max(0) as usize;
+    let size = pad_char.len_utf8()
+                       .checked_mul(pad_count)
+                       .and_then(|size| size.checked_add(string.len()));
+
+    let mut padded = sized_string(interpreter, size)?;
+
+    if !pad_left
+    {
+        padded.push_str(&string);
+    }
+
+    padded.extend(std::iter::repeat_n(pad_char, pad_count));
+
+    if pad_left
+    {
+        padded.push_str(&string);
+    }
+
+    interpreter.push(padded.to_value());
+    Ok(())
+}
+
+/// Pad the start of a string with a character until it's the given width in characters.
+///
+/// Signature: `width pad-char string -- padded-string`
+fn word_string_pad_left(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    pad_string(interpreter, true)
+}
+
+/// Pad the end of a string with a character until it's the given width in characters.
+///
+/// Signature: `width pad-char string -- padded-string`
+fn word_string_pad_right(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    pad_string(interpreter, false)
+}
+
+/// Reverse the characters of a string.
+///
+/// Signature: `string -- reversed-string`
+fn word_string_reverse(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    interpreter.push(string.chars().rev().collect::<String>().to_value());
+    Ok(())
+}
+
+/// Split a string into an array of it's lines.  Both \n and \r\n line endings are recognized, and
+/// a final line ending doesn't create an extra empty line.
+///
+/// Signature: `string -- lines`
+fn word_string_lines(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+    let lines = string.lines().map(|line| line.to_string().to_value()).collect();
+
+    interpreter.push(ValueVec::from_vec(lines).to_value());
+    Ok(())
+}
+
+/// Pop a single character string from the stack.
+fn pop_char(interpreter: &mut dyn Interpreter) -> error::Result<char>
+{
+    let string = interpreter.pop_as_string()?;
+    let mut chars = string.chars();
+
+    match ( chars.next(), chars.next() )
+    {
+        ( Some(character), None ) => Ok(character),
+        _ => script_error(interpreter, format!("Expected a single character, found {}.",
+                                               Value::stringify(&string)))
+    }
+}
+
+/// Get the Unicode scalar value of a character.
+///
+/// Signature: `character -- code`
+fn word_char_code(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let character = pop_char(interpreter)?;
+
+    interpreter.push((character as i64).to_value());
+    Ok(())
+}
+
+/// Create a character from a Unicode scalar value.
+///
+/// Signature: `code -- character`
+fn word_char_from_code(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let code = interpreter.pop_as_int()?;
+
+    match u32::try_from(code).ok().and_then(char::from_u32)
+    {
+        Some(character) => interpreter.push(character.to_string().to_value()),
+        None            => script_error(interpreter,
+                                        format!("Code {} is not a valid Unicode scalar value.",
+                                                code))?
+    }
+
+    Ok(())
+}
+
+/// Check if a character is alphabetic.
+///
+/// Signature: `character -- bool`
+fn word_char_is_alpha(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let character = pop_char(interpreter)?;
+
+    interpreter.push(character.is_alphabetic().to_value());
+    Ok(())
+}
+
+/// Check if a character is one of the decimal digits 0 through 9.  Other numeric characters, such
+/// as ½ or Ⅻ, aren't digits.
+///
+/// Signature: `character -- bool`
+fn word_char_is_digit(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let character = pop_char(interpreter)?;
+
+    interpreter.push(character.is_ascii_digit().to_value());
+    Ok(())
+}
+
+/// Check if a character is whitespace.
+///
+/// Signature: `character -- bool`
+fn word_char_is_whitespace(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let character = pop_char(interpreter)?;
+
+    interpreter.push(character.is_whitespace().to_value());
+    Ok(())
+}
+
+/// Convert a character to upper case.  Some characters become more than one character when
+/// converted, so the result is a string.
+///
+/// Signature: `character -- string`
+fn word_char_upper(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let character = pop_char(interpreter)?;
+
+    interpreter.push(character.to_uppercase().collect::<String>().to_value());
+    Ok(())
+}
+
+/// Convert a character to lower case.  Some characters become more than one character when
+/// converted, so the result is a string.
+///
+/// Signature: `character -- string`
+fn word_char_lower(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let character = pop_char(interpreter)?;
+
+    interpreter.push(character.to_lowercase().collect::<String>().to_value());
+    Ok(())
+}
+
+/// Split a string into an array of it's characters, indexed the same way as `string.[]@`.
+///
+/// Signature: `string -- characters`
+fn word_string_chars(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+    let characters = string.chars().map(|character| character.to_string().to_value()).collect();
+
+    interpreter.push(ValueVec::from_vec(characters).to_value());
+    Ok(())
+}
+
+/// Convert a string into a byte buffer of it's UTF-8 bytes.
+///
+/// Signature: `string -- buffer`
+fn word_string_bytes(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    push_bytes(interpreter, string.into_bytes());
+    Ok(())
+}
+
+/// Get the size of a string in UTF-8 bytes, rather than characters.
+///
+/// Signature: `string -- size`
+fn word_string_byte_size(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    interpreter.push((string.len() as i64).to_value());
+    Ok(())
+}
+
 /// Convert a value to a string.
 ///
 /// Signature: `value -- string`
@@ -220,35 +1122,130 @@ fn word_to_string(interpreter: &mut dyn Interpreter) -> error::Result<()>
 fn word_hex(interpreter: &mut dyn Interpreter) -> error::Result<()>
 {
     let value = interpreter.pop()?;
-    let number =
-        if value.is_float()
+
+    // The hex word has always converted longer strings to zero.
+    let number = value_to_bits(&value).or_else(|| value.is_string().then_some(0));
+
+    let Some(number) = number
+        else
         {
-            let f_value = value.as_float(interpreter)?;
-            f_value.to_bits() as i64
-        }
-        else if value.is_numeric()
+            return script_error(interpreter, format!("Value {} is not a number.", value));
+        };
+
+    interpreter.push(format!("{:x}", number).to_value());
+    Ok(())
+}
+
+/// Format a string where occurrences of {} are replaced with values from the stack.  Placeholders
+/// can select positional arguments, {1:}, or named arguments, {name}, from a hash table that's
+/// pushed just before the format string.  The original specifiers, such as {>*10} or {a10x}, keep
+/// their meaning, so a name shaped like one has to be written with a colon, {a10:}.  A literal { is
+/// written as {{, and a } outside of a placeholder is always literal.
+///
+/// Signature: `[values] [named-values] format-string -- formatted-string`
+fn word_string_format(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let format = interpreter.pop_as_string()?;
+
+    let pieces = match parse_format(&format)
         {
-            value.get_int_val()
-        }
-        else if value.is_string()
+            Ok(pieces)   => pieces,
+            Err(message) => return script_error(interpreter, message)
+        };
+
+    let mut next_count = 0;
+    let mut argument_count = 0;
+    let mut has_names = false;
+
+    for piece in pieces.iter()
+    {
+        match piece
         {
-            let value = value.get_string_val();
+            FormatPiece::Placeholder(FormatArgument::Next, _) =>
+                {
+                    next_count += 1;
+                    argument_count = argument_count.max(next_count);
+                },
+
+            FormatPiece::Placeholder(FormatArgument::Index(index), _) =>
+                argument_count = argument_count.max(index.saturating_add(1)),
+
+            FormatPiece::Placeholder(FormatArgument::Name(_), _) => has_names = true,
+
+            FormatPiece::Text(_) => ()
+        }
+    }
+
+    let names = if has_names { Some(interpreter.pop_as_hash_map()?) } else { None };
+
+    // Make sure the arguments are actually there before making room for them.
+    if argument_count > interpreter.stack().len()
+    {
+        return script_error(interpreter, format!("The format string needs {} arguments, but \
+                                                  there are only {} values on the stack.",
+                                                 argument_count,
+                                                 interpreter.stack().len()));
+    }
 
-            if value.len() == 1
+    let mut arguments = vec![ Value::None; argument_count ];
+
+    for index in ( 0..argument_count ).rev()
+    {
+        arguments[index] = interpreter.pop()?;
+    }
+
+    let mut output = String::new();
+    let mut next_index = 0;
+
+    for piece in pieces.iter()
+    {
+        let ( argument, specifier ) = match piece
             {
-                value.chars().next().unwrap() as i64
-            }
-            else
+                FormatPiece::Text(text) =>
+                    {
+                        output.push_str(text);
+                        continue;
+                    },
+
+                FormatPiece::Placeholder(argument, specifier) => ( argument, specifier )
+            };
+
+        let value = match argument
             {
-                0
-            }
-        }
-        else
+                FormatArgument::Next =>
+                    {
+                        next_index += 1;
+                        arguments[next_index - 1].clone()
+                    },
+
+                FormatArgument::Index(index) => arguments[*index].clone(),
+
+                FormatArgument::Name(name) =>
+                    {
+                        let found = names.as_ref()
+                                         .and_then(|names| names.borrow()
+                                                                .get(&name.to_value())
+                                                                .cloned());
+
+                        match found
+                        {
+                            Some(value) => value,
+                            None        => return script_error(interpreter,
+                                                               format!("Format argument {} was \
+                                                                        not found.",
+                                                                       name))
+                        }
+                    }
+            };
+
+        match format_value(&value, specifier)
         {
-            return script_error(interpreter, format!("Value {} is not a number.", value));
-        };
+            Ok(text)     => output.push_str(&text),
+            Err(message) => return script_error(interpreter, message)
+        }
+    }
 
-    interpreter.push(format!("{:x}", number).to_value());
+    interpreter.push(output.to_value());
     Ok(())
 }
 
@@ -275,6 +1272,46 @@ pub fn register_string_words(interpreter: &mut dyn Interpreter)
         "Get the length of a given string.",
         "string -- size");
 
+    add_native_word!(interpreter, "string.byte-size@", word_string_byte_size,
+        "Get the size of a string in UTF-8 bytes.",
+        "string -- size");
+
+    add_native_word!(interpreter, "string.chars", word_string_chars,
+        "Split a string into an array of it's characters.",
+        "string -- characters");
+
+    add_native_word!(interpreter, "string.bytes", word_string_bytes,
+        "Convert a string into a byte buffer of it's UTF-8 bytes.",
+        "string -- buffer");
+
+    add_native_word!(interpreter, "char.code", word_char_code,
+        "Get the Unicode scalar value of a character.",
+        "character -- code");
+
+    add_native_word!(interpreter, "char.from-code", word_char_from_code,
+        "Create a character from a Unicode scalar value.",
+        "code -- character");
+
+    add_native_word!(interpreter, "char.alpha?", word_char_is_alpha,
+        "Check if a character is alphabetic.",
+        "character -- bool");
+
+    add_native_word!(interpreter, "char.digit?", word_char_is_digit,
+        "Check if a character is a decimal digit, 0 through 9.",
+        "character -- bool");
+
+    add_native_word!(interpreter, "char.whitespace?", word_char_is_whitespace,
+        "Check if a character is whitespace.",
+        "character -- bool");
+
+    add_native_word!(interpreter, "char.upper", word_char_upper,
+        "Convert a character to upper case.",
+        "character -- string");
+
+    add_native_word!(interpreter, "char.lower", word_char_lower,
+        "Convert a character to lower case.",
+        "character -- string");
+
     add_native_word!(interpreter, "string.[]!", word_string_insert,
         "Insert a string into another string.",
         "string -- updated_string");
@@ -295,10 +1332,70 @@ pub fn register_string_words(interpreter: &mut dyn Interpreter)
         "Convert a string into a number.",
         "string -- number");
 
+    add_native_word!(interpreter, "string.upper", word_string_upper,
+        "Convert a string to upper case.",
+        "string -- upper_string");
+
+    add_native_word!(interpreter, "string.lower", word_string_lower,
+        "Convert a string to lower case.",
+        "string -- lower_string");
+
+    add_native_word!(interpreter, "string.trim", word_string_trim,
+        "Remove the whitespace from both ends of a string.",
+        "string -- trimmed_string");
+
+    add_native_word!(interpreter, "string.trim-left", word_string_trim_left,
+        "Remove the whitespace from the start of a string.",
+        "string -- trimmed_string");
+
+    add_native_word!(interpreter, "string.trim-right", word_string_trim_right,
+        "Remove the whitespace from the end of a string.",
+        "string -- trimmed_string");
+
+    add_native_word!(interpreter, "string.starts-with?", word_string_starts_with,
+        "Check if a string starts with the given prefix.",
+        "prefix string -- bool");
+
+    add_native_word!(interpreter, "string.ends-with?", word_string_ends_with,
+        "Check if a string ends with the given suffix.",
+        "suffix string -- bool");
+
+    add_native_word!(interpreter, "string.replace", word_string_replace,
+        "Replace every instance of a sub-string within a string.",
+        "search_string replacement string -- updated_string");
+
+    add_native_word!(interpreter, "string.join", word_string_join,
+        "Join an array of values into a string with a separator between them.",
+        "separator array -- string");
+
+    add_native_word!(interpreter, "string.repeat", word_string_repeat,
+        "Repeat a string the given number of times.",
+        "count string -- repeated_string");
+
+    add_native_word!(interpreter, "string.pad-left", word_string_pad_left,
+        "Pad the start of a string with a character to the given width.",
+        "width pad_char string -- padded_string");
+
+    add_native_word!(interpreter, "string.pad-right", word_string_pad_right,
+        "Pad the end of a string with a character to the given width.",
+        "width pad_char string -- padded_string");
+
+    add_native_word!(interpreter, "string.reverse", word_string_reverse,
+        "Reverse the characters of a string.",
+        "string -- reversed_string");
+
+    add_native_word!(interpreter, "string.lines", word_string_lines,
+        "Split a string into an array of it's lines.",
+        "string -- lines");
+
     add_native_word!(interpreter, "to_string", word_to_string,
         "Convert a value to a string.",
         "value -- string");
 
+    add_native_word!(interpreter, "string.format", word_string_format,
+        "Format a string where occurrences of {} are replaced with stack values.",
+        "[variables] format_string -- formatted_string");
+
     add_native_word!(interpreter, "hex", word_hex,
         "Convert a number into a hex string.",
         "number -- hex_string");
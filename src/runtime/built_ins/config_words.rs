@@ -0,0 +1,1116 @@
+
+use std::{ collections::HashSet,
+           fs::{ read_to_string, write },
+           rc::Rc };
+use crate::{ add_native_word,
+             lang::source_buffer::{ SourceBuffer,
+                                    SourceLocation },
+             runtime::{ built_ins::time_words::{ find_time_parts_definition,
+                                                 format_iso_8601,
+                                                 parse_iso_8601,
+                                                 TimeParts },
+                        data_structures::{ data_object::DataObjectDefinitionPtr,
+                                           value::{ ToValue, Value },
+                                           value_hash::{ ValueHash,
+                                                         ValueHashPtr },
+                                           value_vec::ValueVec },
+                        error::{ self,
+                                 script_error,
+                                 ScriptError },
+                        interpreter::Interpreter } };
+
+
+
+/// The deepest nesting of arrays and tables that a configuration file can hold, when it's either
+/// loaded or saved.
+const MAX_CONFIG_DEPTH: usize = 512;
+
+
+
+/// Parser for the subset of TOML used by configuration files.  Tables and arrays of tables become
+/// nested hash tables, and dates become `time.parts` structures.  Errors are reported at their
+/// location within the configuration file.
+struct ConfigParser<'a>
+{
+    buffer: SourceBuffer<'a>,
+    time_parts: DataObjectDefinitionPtr,
+    root: ValueHashPtr,
+    defined_tables: HashSet<Vec<String>>,
+    depth: usize
+}
+
+
+impl<'a> ConfigParser<'a>
+{
+    /// Create a new parser for the configuration text read from the given path.
+    fn new(path: &String, text: &'a String, time_parts: DataObjectDefinitionPtr) -> ConfigParser<'a>
+    {
+        ConfigParser
+            {
+                buffer: SourceBuffer::new(path, text),
+                time_parts,
+                root: ValueHash::new(),
+                defined_tables: HashSet::new(),
+                depth: 0
+            }
+    }
+
+    /// Report an error at the given location in the configuration file.
+    fn error_at<T>(location: &SourceLocation, message: String) -> error::Result<T>
+    {
+        ScriptError::new_as_result(Some(location.clone()), message, None)
+    }
+
+    /// Report an error at the current location in the configuration file.
+    fn error<T>(&self, message: &str) -> error::Result<T>
+    {
+        ConfigParser::error_at(self.buffer.location(), message.to_string())
+    }
+
+    /// Track the nesting depth of inline arrays and tables so that deeply nested values can't
+    /// overflow the stack.
+    fn enter_nested(&mut self) -> error::Result<()>
+    {
+        self.depth += 1;
+
+        if self.depth > MAX_CONFIG_DEPTH
+        {
+            return self.error(&format!("Nesting is deeper than {} levels.", MAX_CONFIG_DEPTH));
+        }
+
+        Ok(())
+    }
+
+    /// Consume the expected character, or report an error.
+    fn expect(&mut self, expected: char) -> error::Result<()>
+    {
+        if self.buffer.peek_next() != Some(expected)
+        {
+            return self.error(&format!("Expected '{}'.", expected));
+        }
+
+        self.buffer.next();
+        Ok(())
+    }
+
+    /// Move past any spaces and tabs.
+    fn skip_whitespace(&mut self)
+    {
+        while let Some(' ' | '\t') = self.buffer.peek_next()
+        {
+            self.buffer.next();
+        }
+    }
+
+    /// Move past the rest of a comment, stopping at the end of the line.
+    fn skip_comment(&mut self)
+    {
+        while let Some(next) = self.buffer.peek_next()
+        {
+            if next == '\n'
+            {
+                break;
+            }
+
+            self.buffer.next();
+        }
+    }
+
+    /// Move past whitespace, new lines, and comments.  Used within arrays, which can span lines.
+    fn skip_blank_lines(&mut self)
+    {
+        loop
+        {
+            match self.buffer.peek_next()
+            {
+                Some(' ' | '\t' | '\r' | '\n') => { self.buffer.next(); },
+                Some('#')                      => self.skip_comment(),
+                _                              => break
+            }
+        }
+    }
+
+    /// Make sure that nothing but whitespace or a comment follows on the current line, then move to
+    /// the next line.
+    fn expect_line_end(&mut self) -> error::Result<()>
+    {
+        self.skip_whitespace();
+
+        if self.buffer.peek_next() == Some('#')
+        {
+            self.skip_comment();
+        }
+
+        if self.buffer.peek_next() == Some('\r')
+        {
+            self.buffer.next();
+        }
+
+        match self.buffer.peek_next()
+        {
+            Some('\n') => { self.buffer.next(); },
+            None       => (),
+            Some(_)    => return self.error("Expected the end of the line.")
+        }
+
+        Ok(())
+    }
+
+    /// Parse the whole configuration file into it's root table.
+    fn parse(mut self) -> error::Result<ValueHashPtr>
+    {
+        let mut current = self.root.clone();
+
+        loop
+        {
+            self.skip_whitespace();
+
+            match self.buffer.peek_next()
+            {
+                None                    => break,
+                Some('#' | '\r' | '\n') => self.expect_line_end()?,
+
+                Some('[') =>
+                    {
+                        current = self.parse_table_header()?;
+                        self.expect_line_end()?;
+                    },
+
+                Some(_) =>
+                    {
+                        self.parse_key_value(&current)?;
+                        self.expect_line_end()?;
+                    }
+            }
+        }
+
+        Ok(self.root)
+    }
+
+    /// Is the character allowed in a bare key?
+    fn is_bare_key_char(next: char) -> bool
+    {
+        next.is_ascii_alphanumeric() || next == '_' || next == '-'
+    }
+
+    /// Parse a key, which may be dotted.  Each part of the key can be bare or quoted.
+    fn parse_key(&mut self) -> error::Result<Vec<String>>
+    {
+        let mut keys = Vec::new();
+
+        loop
+        {
+            self.skip_whitespace();
+
+            let key = match self.buffer.peek_next()
+                {
+                    Some('"')  => self.parse_basic_string(false)?,
+                    Some('\'') => self.parse_literal_string(false)?,
+
+                    Some(next) if ConfigParser::is_bare_key_char(next) =>
+                        {
+                            let mut key = String::new();
+
+                            while let Some(next) = self.buffer.peek_next()
+                            {
+                                if !ConfigParser::is_bare_key_char(next)
+                                {
+                                    break;
+                                }
+
+                                key.push(next);
+                                self.buffer.next();
+                            }
+
+                            key
+                        },
+
+                    _ => return self.error("Expected a key.")
+                };
+
+            keys.push(key);
+            self.skip_whitespace();
+
+            if self.buffer.peek_next() != Some('.')
+            {
+                break;
+            }
+
+            self.buffer.next();
+        }
+
+        Ok(keys)
+    }
+
+    /// Find the sub-table with the given key, creating it if it doesn't exist yet.  If the key
+    /// refers to an array of tables, the last table in the array is used.
+    fn sub_table(table: &ValueHashPtr,
+                 key: &str,
+                 location: &SourceLocation) -> error::Result<ValueHashPtr>
+    {
+        let existing = table.borrow().get(&key.to_string().to_value()).cloned();
+
+        match existing
+        {
+            Some(Value::HashMap(sub_table)) => Ok(sub_table),
+
+            Some(Value::Vec(array)) =>
+                {
+                    match array.borrow().iter().last()
+                    {
+                        Some(Value::HashMap(sub_table)) => Ok(sub_table.clone()),
+                        _ => ConfigParser::error_at(location,
+                                                    format!("Key {} is not a table.", key))
+                    }
+                },
+
+            Some(_) => ConfigParser::error_at(location, format!("Key {} is not a table.", key)),
+
+            None =>
+                {
+                    let sub_table = ValueHash::new();
+
+                    table.borrow_mut().insert(key.to_string().to_value(), sub_table.to_value());
+                    Ok(sub_table)
+                }
+        }
+    }
+
+    /// Parse a [table] or [[array.of.tables]] header, returning the table that following keys
+    /// should be added to.
+    fn parse_table_header(&mut self) -> error::Result<ValueHashPtr>
+    {
+        let location = self.buffer.location().clone();
+
+        self.expect('[')?;
+
+        let is_array = self.buffer.peek_next() == Some('[');
+
+        if is_array
+        {
+            self.buffer.next();
+        }
+
+        let keys = self.parse_key()?;
+
+        self.expect(']')?;
+
+        if is_array
+        {
+            self.expect(']')?;
+        }
+
+        let ( last, parents ) = keys.split_last().expect("Keys can not be empty.");
+        let mut table = self.root.clone();
+
+        for key in parents
+        {
+            table = ConfigParser::sub_table(&table, key, &location)?;
+        }
+
+        if !is_array
+        {
+            if !self.defined_tables.insert(keys.clone())
+            {
+                return ConfigParser::error_at(&location,
+                                              format!("Table [{}] is defined more than once.",
+                                                      keys.join(".")));
+            }
+
+            return ConfigParser::sub_table(&table, last, &location);
+        }
+
+        // Each new table in the array gets to define it's own sub-tables.
+        self.defined_tables.retain(|defined| !defined.starts_with(&keys));
+
+        let new_table = ValueHash::new();
+        let key = last.to_string().to_value();
+        let existing = table.borrow().get(&key).cloned();
+
+        match existing
+        {
+            Some(Value::Vec(array)) => array.borrow_mut().push_back(new_table.to_value()),
+            None                    =>
+                {
+                    let array = ValueVec::from_vec(vec![ new_table.to_value() ]);
+                    table.borrow_mut().insert(key, array.to_value());
+                },
+            Some(_)                 =>
+                return ConfigParser::error_at(&location,
+                                              format!("Key {} is not an array of tables.", last))
+        }
+
+        Ok(new_table)
+    }
+
+    /// Parse a key = value pair and add it to the table.
+    fn parse_key_value(&mut self, table: &ValueHashPtr) -> error::Result<()>
+    {
+        let location = self.buffer.location().clone();
+        let keys = self.parse_key()?;
+
+        self.expect('=')?;
+        self.skip_whitespace();
+
+        let value = self.parse_value()?;
+        let ( last, parents ) = keys.split_last().expect("Keys can not be empty.");
+        let mut table = table.clone();
+
+        for key in parents
+        {
+            table = ConfigParser::sub_table(&table, key, &location)?;
+        }
+
+        let key = last.to_string().to_value();
+
+        if table.borrow().get(&key).is_some()
+        {
+            return ConfigParser::error_at(&location,
+                                          format!("Key {} is defined more than once.",
+                                                  keys.join(".")));
+        }
+
+        table.borrow_mut().insert(key, value);
+
+        Ok(())
+    }
+
+    /// Parse any value.
+    fn parse_value(&mut self) -> error::Result<Value>
+    {
+        match self.buffer.peek_next()
+        {
+            Some('"')  => Ok(self.parse_basic_string(true)?.to_value()),
+            Some('\'') => Ok(self.parse_literal_string(true)?.to_value()),
+            Some('[')  => self.parse_array(),
+            Some('{')  => self.parse_inline_table(),
+
+            Some(next) if next.is_ascii_alphanumeric() || "+-._:".contains(next) =>
+                self.parse_bare_value(),
+
+            _ => self.error("Expected a value.")
+        }
+    }
+
+    /// Read the given number of hex digits of a unicode escape sequence.
+    fn parse_unicode_escape(&mut self, digits: usize) -> error::Result<char>
+    {
+        let mut code_point = 0;
+
+        for _ in 0..digits
+        {
+            let Some(digit) = self.buffer.peek_next().and_then(|next| next.to_digit(16))
+                else
+                {
+                    return self.error("Expected a hex digit.");
+                };
+
+            code_point = (code_point * 16) + digit;
+            self.buffer.next();
+        }
+
+        match char::from_u32(code_point)
+        {
+            Some(character) => Ok(character),
+            None            => self.error(&format!("Invalid unicode code point {:x}.", code_point))
+        }
+    }
+
+    /// Parse the escape sequence after a backslash in a basic string.
+    fn parse_escape(&mut self) -> error::Result<char>
+    {
+        let escape = self.buffer.peek_next();
+
+        if escape.is_some()
+        {
+            self.buffer.next();
+        }
+
+        match escape
+        {
+            Some('b')  => Ok('\u{8}'),
+            Some('t')  => Ok('\t'),
+            Some('n')  => Ok('\n'),
+            Some('f')  => Ok('\u{c}'),
+            Some('r')  => Ok('\r'),
+            Some('"')  => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('u')  => self.parse_unicode_escape(4),
+            Some('U')  => self.parse_unicode_escape(8),
+            _          => self.error("Invalid escape sequence.")
+        }
+    }
+
+    /// Move past the new line that may immediately follow the opening quotes of a multi-line
+    /// string.
+    fn skip_opening_new_line(&mut self)
+    {
+        if self.buffer.peek_next() == Some('\r')
+        {
+            self.buffer.next();
+        }
+
+        if self.buffer.peek_next() == Some('\n')
+        {
+            self.buffer.next();
+        }
+    }
+
+    /// Handle a quote found within a multi-line string.  Returns true if this was the closing run
+    /// of quotes.  Quotes that don't close the string are added to it.
+    fn multi_line_quotes(&mut self, quote: char, result: &mut String) -> bool
+    {
+        let mut count = 1;
+
+        while count < 5 && self.buffer.peek_next() == Some(quote)
+        {
+            self.buffer.next();
+            count += 1;
+        }
+
+        if count >= 3
+        {
+            result.extend(std::iter::repeat_n(quote, count - 3));
+            return true;
+        }
+
+        result.extend(std::iter::repeat_n(quote, count));
+        false
+    }
+
+    /// Parse a double quoted string, processing escape sequences.  Triple quoted multi-line strings
+    /// are allowed for values, but not for keys.
+    fn parse_basic_string(&mut self, allow_multi_line: bool) -> error::Result<String>
+    {
+        let mut result = String::new();
+
+        self.expect('"')?;
+
+        if self.buffer.peek_next() == Some('"')
+        {
+            self.buffer.next();
+
+            if !allow_multi_line || self.buffer.peek_next() != Some('"')
+            {
+                return Ok(result);
+            }
+
+            self.buffer.next();
+            self.skip_opening_new_line();
+
+            loop
+            {
+                match self.buffer.next()
+                {
+                    None => return self.error("Unterminated multi-line string."),
+
+                    Some('"') =>
+                        {
+                            if self.multi_line_quotes('"', &mut result)
+                            {
+                                return Ok(result);
+                            }
+                        },
+
+                    // A backslash at the end of a line trims the line break and any whitespace
+                    // that follows it.
+                    Some('\\') if matches!(self.buffer.peek_next(),
+                                           Some(' ' | '\t' | '\r' | '\n')) =>
+                        self.skip_blank_lines(),
+
+                    Some('\\') => result.push(self.parse_escape()?),
+                    Some(next) => result.push(next)
+                }
+            }
+        }
+
+        loop
+        {
+            match self.buffer.peek_next()
+            {
+                None | Some('\n') => return self.error("Unterminated string."),
+
+                Some('"') =>
+                    {
+                        self.buffer.next();
+                        return Ok(result);
+                    },
+
+                Some('\\') =>
+                    {
+                        self.buffer.next();
+                        result.push(self.parse_escape()?);
+                    },
+
+                Some(next) =>
+                    {
+                        self.buffer.next();
+                        result.push(next);
+                    }
+            }
+        }
+    }
+
+    /// Parse a single quoted literal string, where no escape sequences are processed.  Triple
+    /// quoted multi-line strings are allowed for values, but not for keys.
+    fn parse_literal_string(&mut self, allow_multi_line: bool) -> error::Result<String>
+    {
+        let mut result = String::new();
+
+        self.expect('\'')?;
+
+        if self.buffer.peek_next() == Some('\'')
+        {
+            self.buffer.next();
+
+            if !allow_multi_line || self.buffer.peek_next() != Some('\'')
+            {
+                return Ok(result);
+            }
+
+            self.buffer.next();
+            self.skip_opening_new_line();
+
+            loop
+            {
+                match self.buffer.next()
+                {
+                    None       => return self.error("Unterminated multi-line string."),
+                    Some('\'') =>
+                        {
+                            if self.multi_line_quotes('\'', &mut result)
+                            {
+                                return Ok(result);
+                            }
+                        },
+                    Some(next) => result.push(next)
+                }
+            }
+        }
+
+        loop
+        {
+            match self.buffer.peek_next()
+            {
+                None | Some('\n') => return self.error("Unterminated string."),
+
+                Some('\'') =>
+                    {
+                        self.buffer.next();
+                        return Ok(result);
+                    },
+
+                Some(next) =>
+                    {
+                        self.buffer.next();
+                        result.push(next);
+                    }
+            }
+        }
+    }
+
+    /// Parse an array of values, which may span multiple lines.
+    fn parse_array(&mut self) -> error::Result<Value>
+    {
+        let mut values = Vec::new();
+
+        self.expect('[')?;
+        self.enter_nested()?;
+
+        loop
+        {
+            self.skip_blank_lines();
+
+            if self.buffer.peek_next() == Some(']')
+            {
+                self.buffer.next();
+                break;
+            }
+
+            values.push(self.parse_value()?);
+            self.skip_blank_lines();
+
+            match self.buffer.peek_next()
+            {
+                Some(',') => { self.buffer.next(); },
+                Some(']') =>
+                    {
+                        self.buffer.next();
+                        break;
+                    },
+                _         => return self.error("Expected ',' or ']'.")
+            }
+        }
+
+        self.depth -= 1;
+
+        Ok(ValueVec::from_vec(values).to_value())
+    }
+
+    /// Parse an inline { key = value, ... } table.
+    fn parse_inline_table(&mut self) -> error::Result<Value>
+    {
+        let table = ValueHash::new();
+
+        self.expect('{')?;
+        self.enter_nested()?;
+        self.skip_whitespace();
+
+        if self.buffer.peek_next() == Some('}')
+        {
+            self.buffer.next();
+            self.depth -= 1;
+
+            return Ok(table.to_value());
+        }
+
+        loop
+        {
+            self.parse_key_value(&table)?;
+            self.skip_whitespace();
+
+            match self.buffer.peek_next()
+            {
+                Some(',') => { self.buffer.next(); },
+                Some('}') =>
+                    {
+                        self.buffer.next();
+                        break;
+                    },
+                _         => return self.error("Expected ',' or '}'.")
+            }
+        }
+
+        self.depth -= 1;
+
+        Ok(table.to_value())
+    }
+
+    /// Parse a bare value, a boolean, number, or date.
+    fn parse_bare_value(&mut self) -> error::Result<Value>
+    {
+        let location = self.buffer.location().clone();
+        let mut token = String::new();
+
+        while let Some(next) = self.buffer.peek_next()
+        {
+            if !next.is_ascii_alphanumeric() && !"+-._:".contains(next)
+            {
+                break;
+            }
+
+            token.push(next);
+            self.buffer.next();
+        }
+
+        let invalid = || ConfigParser::error_at(&location, format!("Invalid value {}.", token));
+        let bytes = token.as_bytes();
+
+        // Dates start with a four digit year.
+        if    bytes.len() >= 10
+           && bytes[..4].iter().all(|byte| byte.is_ascii_digit())
+           && bytes[4] == b'-'
+        {
+            return match parse_iso_8601(&token)
+                {
+                    Ok(parts)  => Ok(parts.to_value(&self.time_parts)),
+                    Err(error) => ConfigParser::error_at(&location,
+                                                         format!("Invalid date {}: {}",
+                                                                 token,
+                                                                 error))
+                };
+        }
+
+        let value = match token.as_str()
+            {
+                "true"          => true.to_value(),
+                "false"         => false.to_value(),
+                "inf" | "+inf"  => f64::INFINITY.to_value(),
+                "-inf"          => f64::NEG_INFINITY.to_value(),
+                "nan" | "+nan" | "-nan" => f64::NAN.to_value(),
+
+                _ =>
+                    {
+                        let digits = token.trim_start_matches([ '+', '-' ]);
+
+                        if    digits.is_empty()
+                           || digits.starts_with('_')
+                           || digits.ends_with('_')
+                           || digits.contains("__")
+                           || digits.contains(':')
+                        {
+                            return invalid();
+                        }
+
+                        let clean = token.replace('_', "");
+                        let radix = match clean.get(..2)
+                            {
+                                Some("0x") => Some(16),
+                                Some("0o") => Some(8),
+                                Some("0b") => Some(2),
+                                _          => None
+                            };
+
+                        if let Some(radix) = radix
+                        {
+                            match i64::from_str_radix(&clean[2..], radix)
+                            {
+                                Ok(value) => value.to_value(),
+                                Err(_)    => return invalid()
+                            }
+                        }
+                        else if clean.contains([ '.', 'e', 'E' ])
+                        {
+                            match clean.parse::<f64>()
+                            {
+                                Ok(value) => value.to_value(),
+                                Err(_)    => return invalid()
+                            }
+                        }
+                        else
+                        {
+                            match clean.parse::<i64>()
+                            {
+                                Ok(value) => value.to_value(),
+                                Err(_)    => return invalid()
+                            }
+                        }
+                    }
+            };
+
+        Ok(value)
+    }
+}
+
+
+
+/// Format a key, quoting it if it can't be written as a bare key.
+fn format_key(key: &Value) -> Result<String, String>
+{
+    let key = match key
+        {
+            Value::String(key) => key.clone(),
+            Value::Int(_)      => key.to_string(),
+            _                  => return Err(format!("Can not use {} as a config key.", key))
+        };
+
+    if !key.is_empty() && key.chars().all(ConfigParser::is_bare_key_char)
+    {
+        Ok(key)
+    }
+    else
+    {
+        Ok(format_string(&key))
+    }
+}
+
+
+/// Format a string as a quoted basic string.
+fn format_string(text: &str) -> String
+{
+    let mut result = String::from("\"");
+
+    for character in text.chars()
+    {
+        match character
+        {
+            '"'                => result.push_str("\\\""),
+            '\\'               => result.push_str("\\\\"),
+            '\n'               => result.push_str("\\n"),
+            '\r'               => result.push_str("\\r"),
+            '\t'               => result.push_str("\\t"),
+            '\u{0}'..='\u{1f}' |
+            '\u{7f}'           => result.push_str(&format!("\\u{:04x}", character as u32)),
+            _                  => result.push(character)
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+
+/// Keep track of a table or array while it's being written.  If the container is already being
+/// written further up, it contains itself and would be written forever.  Containers can't be nested
+/// deeper than the parser would accept either.
+fn enter_container(interpreter: &mut dyn Interpreter,
+                   containers: &mut Vec<usize>,
+                   address: usize) -> error::Result<()>
+{
+    if containers.contains(&address)
+    {
+        return script_error(interpreter,
+                            "Can not save a value that contains itself to a config file."
+                            .to_string());
+    }
+
+    if containers.len() >= MAX_CONFIG_DEPTH
+    {
+        return script_error(interpreter, format!("Nesting is deeper than {} levels.",
+                                                 MAX_CONFIG_DEPTH));
+    }
+
+    containers.push(address);
+    Ok(())
+}
+
+
+/// Format a value for the right hand side of a key = value pair.  Tables and arrays are written
+/// inline.
+fn format_value(interpreter: &mut dyn Interpreter,
+                containers: &mut Vec<usize>,
+                value: &Value) -> error::Result<String>
+{
+    let text = match value
+        {
+            Value::String(text) => format_string(text),
+            Value::Int(value)   => value.to_string(),
+            Value::Bool(value)  => value.to_string(),
+
+            Value::Float(value) =>
+                {
+                    if value.is_nan()
+                    {
+                        "nan".to_string()
+                    }
+                    else if value.is_infinite()
+                    {
+                        if *value > 0.0 { "inf".to_string() } else { "-inf".to_string() }
+                    }
+                    else
+                    {
+                        format!("{:?}", value)
+                    }
+                },
+
+            Value::DataObject(data_ptr)
+                if data_ptr.borrow().definition_ptr.borrow().name() == "time.parts" =>
+                {
+                    format_iso_8601(&TimeParts::from_value(interpreter, value)?)
+                },
+
+            Value::Vec(array) =>
+                {
+                    let address = Rc::as_ptr(array) as *const () as usize;
+                    enter_container(interpreter, containers, address)?;
+
+                    let values: Vec<Value> = array.borrow().iter().cloned().collect();
+                    let mut items = Vec::with_capacity(values.len());
+
+                    for value in values.iter()
+                    {
+                        items.push(format_value(interpreter, containers, value)?);
+                    }
+
+                    containers.pop();
+
+                    if items.is_empty()
+                    {
+                        "[]".to_string()
+                    }
+                    else
+                    {
+                        format!("[ {} ]", items.join(", "))
+                    }
+                },
+
+            Value::HashMap(table) =>
+                {
+                    let address = Rc::as_ptr(table) as *const () as usize;
+                    enter_container(interpreter, containers, address)?;
+
+                    let mut items = Vec::new();
+
+                    for ( key, value ) in sorted_entries(interpreter, table)?
+                    {
+                        items.push(format!("{} = {}",
+                                           key,
+                                           format_value(interpreter, containers, &value)?));
+                    }
+
+                    containers.pop();
+
+                    if items.is_empty()
+                    {
+                        "{}".to_string()
+                    }
+                    else
+                    {
+                        format!("{{ {} }}", items.join(", "))
+                    }
+                },
+
+            _ => return script_error(interpreter,
+                                     format!("Can not save {} to a config file.", value))
+        };
+
+    Ok(text)
+}
+
+
+/// Get the entries of a table with their keys formatted, sorted by key to keep the output stable.
+fn sorted_entries(interpreter: &mut dyn Interpreter,
+                  table: &ValueHashPtr) -> error::Result<Vec<( String, Value )>>
+{
+    let mut entries = Vec::with_capacity(table.borrow().len());
+
+    for ( key, value ) in table.borrow().iter()
+    {
+        match format_key(key)
+        {
+            Ok(key)      => entries.push(( key, value.clone() )),
+            Err(message) => return script_error(interpreter, message)
+        }
+    }
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(entries)
+}
+
+
+/// Is the value a non-empty array that only holds tables?  These are written as [[arrays]].
+fn is_table_array(value: &Value) -> bool
+{
+    match value
+    {
+        Value::Vec(array) =>    array.borrow().len() > 0
+                             && array.borrow().iter().all(|item| item.is_hash_map()),
+        _                 => false
+    }
+}
+
+
+/// Write the contents of a table.  Plain values come first, followed by the sub-tables and arrays
+/// of tables which get their own [headers].
+fn write_table(interpreter: &mut dyn Interpreter,
+               containers: &mut Vec<usize>,
+               output: &mut String,
+               table: &ValueHashPtr,
+               path: &[String]) -> error::Result<()>
+{
+    enter_container(interpreter, containers, Rc::as_ptr(table) as *const () as usize)?;
+
+    let entries = sorted_entries(interpreter, table)?;
+
+    for ( key, value ) in entries.iter()
+    {
+        if !value.is_hash_map() && !is_table_array(value)
+        {
+            if value.is_none()
+            {
+                return script_error(interpreter,
+                                    format!("Can not save none for the config key {}.", key));
+            }
+
+            output.push_str(&format!("{} = {}\n",
+                                     key,
+                                     format_value(interpreter, containers, value)?));
+        }
+    }
+
+    for ( key, value ) in entries.iter()
+    {
+        let mut sub_path = path.to_vec();
+        sub_path.push(key.clone());
+
+        match value
+        {
+            Value::HashMap(sub_table) =>
+                {
+                    output.push_str(&format!("\n[{}]\n", sub_path.join(".")));
+                    write_table(interpreter, containers, output, sub_table, &sub_path)?;
+                },
+
+            Value::Vec(array) if is_table_array(value) =>
+                {
+                    let items: Vec<Value> = array.borrow().iter().cloned().collect();
+
+                    for item in items.iter()
+                    {
+                        output.push_str(&format!("\n[[{}]]\n", sub_path.join(".")));
+                        let sub_table = item.as_hash_map(interpreter)?.clone();
+
+                        write_table(interpreter, containers, output, &sub_table, &sub_path)?;
+                    }
+                },
+
+            _ => ()
+        }
+    }
+
+    containers.pop();
+    Ok(())
+}
+
+
+
+/// Load a TOML style configuration file into a hash table.  Tables become nested hash tables and
+/// dates become `time.parts` structures.
+///
+/// Signature: `path -- hash-table`
+fn word_config_load(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    let text = match read_to_string(&path)
+        {
+            Ok(text)   => text,
+            Err(error) => return script_error(interpreter,
+                                              format!("Could not read config file {}: {}.",
+                                                      path,
+                                                      error))
+        };
+
+    let Some(time_parts) = find_time_parts_definition(interpreter)
+        else
+        {
+            return script_error(interpreter,
+                                "The time.parts structure is not defined.".to_string());
+        };
+
+    match ConfigParser::new(&path, &text, time_parts).parse()
+    {
+        Ok(table) => interpreter.push(table.to_value()),
+
+        // Report the error at it's location in the config file, but with the script's call stack.
+        Err(error) => return ScriptError::new_as_result(error.location().clone(),
+                                                        error.error().clone(),
+                                                        Some(interpreter.call_stack().clone()))
+    }
+
+    Ok(())
+}
+
+/// Save a hash table as a TOML style configuration file.
+///
+/// Signature: `hash-table path -- `
+fn word_config_save(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    let table = interpreter.pop_as_hash_map()?;
+    let mut output = String::new();
+
+    write_table(interpreter, &mut Vec::new(), &mut output, &table, &[])?;
+
+    if let Err(error) = write(&path, output.trim_start())
+    {
+        script_error(interpreter, format!("Could not write config file {}: {}.", path, error))?;
+    }
+
+    Ok(())
+}
+
+
+
+/// Register the configuration file words with the given interpreter.
+pub fn register_config_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "config.load", word_config_load,
+        "Load a TOML style configuration file into a hash table.",
+        "path -- hash_table");
+
+    add_native_word!(interpreter, "config.save", word_config_save,
+        "Save a hash table as a TOML style configuration file.",
+        "hash_table path -- ");
+}
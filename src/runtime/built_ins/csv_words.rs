@@ -0,0 +1,435 @@
+
+use std::io::{ ErrorKind, Read };
+use crate::{ add_native_word,
+             runtime::{ built_ins::io_words::{ get_file,
+                                               read_file },
+                        data_structures::{ value::{ ToValue, Value },
+                                           value_hash::ValueHash,
+                                           value_vec::ValueVec },
+                        error::{ self,
+                                 script_error },
+                        interpreter::Interpreter } };
+
+
+
+/// Where the reader is within the current CSV record.
+#[derive(Clone, Copy, PartialEq)]
+enum CsvState
+{
+    FieldStart,
+    Unquoted,
+    Quoted,
+    QuoteInQuoted
+}
+
+
+
+/// Reader that pulls CSV records from a byte source one at a time.  Quoting follows RFC 4180,
+/// quoted fields can hold delimiters, quotes, and new lines.  Blank lines are skipped.
+struct CsvReader<'a>
+{
+    source: &'a mut dyn Read,
+    delimiter: u8,
+    line: usize
+}
+
+
+impl<'a> CsvReader<'a>
+{
+    /// Create a new reader for the given source and field delimiter.
+    fn new(source: &'a mut dyn Read, delimiter: u8) -> CsvReader<'a>
+    {
+        CsvReader
+            {
+                source,
+                delimiter,
+                line: 1
+            }
+    }
+
+    /// Generate an error message that includes the current line.
+    fn error<T>(&self, message: &str) -> Result<T, String>
+    {
+        Err(format!("CSV error on line {}: {}", self.line, message))
+    }
+
+    /// Read a single byte from the source.  Only one byte is read at a time so that a record can be
+    /// read from a file without consuming any of the data after it.
+    fn next_byte(&mut self) -> Result<Option<u8>, String>
+    {
+        let mut byte = [ 0; 1 ];
+
+        loop
+        {
+            match self.source.read(&mut byte)
+            {
+                Ok(0)                                                => return Ok(None),
+                Ok(_)                                                => return Ok(Some(byte[0])),
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(format!("Could not read CSV data: {}.", error))
+            }
+        }
+    }
+
+    /// Read the next record, or none if the end of the source has been reached.
+    fn read_record(&mut self) -> Result<Option<Vec<String>>, String>
+    {
+        let mut fields = Vec::new();
+        let mut field = Vec::new();
+        let mut state = CsvState::FieldStart;
+
+        loop
+        {
+            let Some(byte) = self.next_byte()?
+                else
+                {
+                    if state == CsvState::Quoted
+                    {
+                        return self.error("Unterminated quoted field.");
+                    }
+
+                    if fields.is_empty() && field.is_empty() && state == CsvState::FieldStart
+                    {
+                        return Ok(None);
+                    }
+
+                    fields.push(String::from_utf8_lossy(&field).to_string());
+                    return Ok(Some(fields));
+                };
+
+            if byte == b'\n'
+            {
+                self.line += 1;
+            }
+
+            match ( state, byte )
+            {
+                ( CsvState::Quoted, b'"' ) => state = CsvState::QuoteInQuoted,
+                ( CsvState::Quoted, _ )    => field.push(byte),
+
+                ( CsvState::QuoteInQuoted, b'"' ) =>
+                    {
+                        field.push(b'"');
+                        state = CsvState::Quoted;
+                    },
+
+                ( CsvState::FieldStart, b'"' ) => state = CsvState::Quoted,
+
+                ( _, b'\r' ) => (),
+
+                ( _, b'\n' ) =>
+                    {
+                        // Skip blank lines entirely.
+                        if fields.is_empty() && field.is_empty() && state == CsvState::FieldStart
+                        {
+                            continue;
+                        }
+
+                        fields.push(String::from_utf8_lossy(&field).to_string());
+                        return Ok(Some(fields));
+                    },
+
+                ( _, _ ) if byte == self.delimiter =>
+                    {
+                        fields.push(String::from_utf8_lossy(&field).to_string());
+                        field.clear();
+                        state = CsvState::FieldStart;
+                    },
+
+                ( CsvState::QuoteInQuoted, _ ) =>
+                    return self.error("Unexpected character after a closing quote."),
+
+                ( _, _ ) =>
+                    {
+                        field.push(byte);
+                        state = CsvState::Unquoted;
+                    }
+            }
+        }
+    }
+}
+
+
+
+/// Pop a field delimiter from the stack.  It must be a single ASCII character.
+fn pop_delimiter(interpreter: &mut dyn Interpreter) -> error::Result<u8>
+{
+    let delimiter = interpreter.pop_as_string()?;
+
+    match delimiter.as_bytes()
+    {
+        [ byte ] if delimiter.is_ascii() && *byte != b'"' && *byte != b'\n' && *byte != b'\r' =>
+            Ok(*byte),
+        _ => script_error(interpreter, format!("Invalid CSV delimiter {}.",
+                                               Value::stringify(&delimiter)))
+    }
+}
+
+
+/// Read every record from either a string or an open fd.
+fn read_all_records(interpreter: &mut dyn Interpreter,
+                    delimiter: u8) -> error::Result<Vec<Vec<String>>>
+{
+    fn read_all(reader: &mut CsvReader) -> Result<Vec<Vec<String>>, String>
+    {
+        let mut records = Vec::new();
+
+        while let Some(record) = reader.read_record()?
+        {
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    let source = interpreter.pop()?;
+
+    let result = if source.is_int()
+        {
+            let fd = source.get_int_val();
+
+            read_file(interpreter, fd, |reader| read_all(&mut CsvReader::new(reader, delimiter)))?
+        }
+        else if source.is_string()
+        {
+            let text = source.get_string_val();
+            let mut bytes = text.as_bytes();
+
+            read_all(&mut CsvReader::new(&mut bytes, delimiter))
+        }
+        else
+        {
+            Err(format!("Expected a CSV string or fd, found {}.", source))
+        };
+
+    match result
+    {
+        Ok(records)  => Ok(records),
+        Err(message) => script_error(interpreter, message)
+    }
+}
+
+
+/// Convert a record's fields into an array of strings.
+fn record_to_value(record: Vec<String>) -> Value
+{
+    ValueVec::from_vec(record.into_iter().map(|field| field.to_value()).collect()).to_value()
+}
+
+
+/// Format a single row as a line of CSV text, without the line ending.  Fields are only quoted
+/// when they need to be.
+fn format_row(row: &Value, delimiter: u8) -> Result<String, String>
+{
+    let Value::Vec(fields) = row
+        else
+        {
+            return Err(format!("Expected a CSV row to be an array, found {}.", row));
+        };
+
+    let delimiter = delimiter as char;
+    let field_count = fields.borrow().len();
+    let mut line = String::new();
+
+    for ( index, field ) in fields.borrow().iter().enumerate()
+    {
+        if index > 0
+        {
+            line.push(delimiter);
+        }
+
+        let text = match field
+            {
+                Value::None                                      => String::new(),
+                Value::String(text)                              => text.clone(),
+                Value::Int(_) | Value::Float(_) | Value::Bool(_) => field.to_string(),
+                _ => return Err(format!("Can not write {} as a CSV field.", field))
+            };
+
+        // A row of a single empty field is quoted, otherwise it would be written as a blank line
+        // and skipped when read back.
+        if    text.contains([ delimiter, '"', '\n', '\r' ])
+           || (field_count == 1 && text.is_empty())
+        {
+            line.push('"');
+            line.push_str(&text.replace('"', "\"\""));
+            line.push('"');
+        }
+        else
+        {
+            line.push_str(&text);
+        }
+    }
+
+    Ok(line)
+}
+
+
+
+/// Parse CSV text, or the rest of an open file, into an array of rows.  Each row is an array of
+/// strings.
+///
+/// Signature: `string-or-fd delimiter -- rows`
+fn word_csv_parse(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let delimiter = pop_delimiter(interpreter)?;
+    let records = read_all_records(interpreter, delimiter)?;
+    let rows: Vec<Value> = records.into_iter().map(record_to_value).collect();
+
+    interpreter.push(ValueVec::from_vec(rows).to_value());
+
+    Ok(())
+}
+
+/// Parse CSV text, or the rest of an open file, that starts with a header row.  Each following row
+/// becomes a hash table keyed by the header's names.  Fields missing from the end of a row are set
+/// to none.
+///
+/// Signature: `string-or-fd delimiter -- records`
+fn word_csv_parse_header(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let delimiter = pop_delimiter(interpreter)?;
+    let mut records = read_all_records(interpreter, delimiter)?.into_iter();
+    let header = records.next().unwrap_or_default();
+    let mut tables = Vec::new();
+
+    for ( index, record ) in records.enumerate()
+    {
+        if record.len() > header.len()
+        {
+            script_error(interpreter, format!("CSV record {} has {} fields, but the header has {}.",
+                                              index + 1,
+                                              record.len(),
+                                              header.len()))?;
+        }
+
+        let table = ValueHash::new();
+        let mut fields = record.into_iter();
+
+        for name in header.iter()
+        {
+            let value = match fields.next()
+                {
+                    Some(field) => field.to_value(),
+                    None        => Value::None
+                };
+
+            table.borrow_mut().insert(name.to_value(), value);
+        }
+
+        tables.push(table.to_value());
+    }
+
+    interpreter.push(ValueVec::from_vec(tables).to_value());
+
+    Ok(())
+}
+
+/// Read the next CSV record from an open file.  Quoted fields may span multiple lines.  None is
+/// pushed once the end of the file is reached.
+///
+/// Signature: `fd delimiter -- row-or-none`
+fn word_csv_row_read(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let delimiter = pop_delimiter(interpreter)?;
+    let fd = interpreter.pop_as_int()?;
+
+    let result = read_file(interpreter, fd, |reader|
+        {
+            CsvReader::new(reader, delimiter).read_record()
+        })?;
+
+    match result
+    {
+        Ok(Some(record)) => interpreter.push(record_to_value(record)),
+        Ok(None)         => interpreter.push(Value::None),
+        Err(message)     => script_error(interpreter, message)?
+    }
+
+    Ok(())
+}
+
+/// Write a single row to an open file as a line of CSV.
+///
+/// Signature: `row fd delimiter -- `
+fn word_csv_row_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let delimiter = pop_delimiter(interpreter)?;
+    let fd = interpreter.pop_as_int()?;
+    let row = interpreter.pop()?;
+    let mut file = get_file(interpreter, fd)?;
+    let description = file.description();
+
+    let result = format_row(&row, delimiter).and_then(|line|
+        {
+            match file.writer()
+            {
+                Some(writer) => writer.write_all((line + "\n").as_bytes())
+                                      .map_err(|error| format!("Could not write to file: {}.",
+                                                               error)),
+                None         => Err(format!("Can not write to {}.", description))
+            }
+        });
+
+    if let Err(message) = result
+    {
+        script_error(interpreter, message)?;
+    }
+
+    Ok(())
+}
+
+/// Convert an array of rows into CSV text.  Each row is an array of strings, numbers, booleans, or
+/// none.  Fields holding the delimiter, quotes, or new lines are quoted as per RFC 4180.
+///
+/// Signature: `rows delimiter -- string`
+fn word_csv_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let delimiter = pop_delimiter(interpreter)?;
+    let rows = interpreter.pop_as_array()?;
+    let mut text = String::new();
+
+    for row in rows.borrow().iter()
+    {
+        match format_row(row, delimiter)
+        {
+            Ok(line)     =>
+                {
+                    text.push_str(&line);
+                    text.push('\n');
+                },
+
+            Err(message) => script_error(interpreter, message)?
+        }
+    }
+
+    interpreter.push(text.to_value());
+
+    Ok(())
+}
+
+
+
+/// Register the CSV reading and writing words with the given interpreter.
+pub fn register_csv_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "csv.parse", word_csv_parse,
+        "Parse CSV text or the rest of a file into an array of row arrays.",
+        "string_or_fd delimiter -- rows");
+
+    add_native_word!(interpreter, "csv.parse.header", word_csv_parse_header,
+        "Parse CSV with a header row into an array of hash tables.",
+        "string_or_fd delimiter -- records");
+
+    add_native_word!(interpreter, "csv.row@", word_csv_row_read,
+        "Read the next CSV row from a file, or none at the end of the file.",
+        "fd delimiter -- row_or_none");
+
+    add_native_word!(interpreter, "csv.row!", word_csv_row_write,
+        "Write a row array to a file as a line of CSV.",
+        "row fd delimiter -- ");
+
+    add_native_word!(interpreter, "csv.write", word_csv_write,
+        "Convert an array of row arrays into CSV text.",
+        "rows delimiter -- string");
+}
@@ -0,0 +1,335 @@
+
+use std::{ cell::RefCell,
+           rc::Rc };
+use crate::{ add_native_word,
+             runtime::{ data_structures::{ byte_buffer::{ BufferPtr,
+                                                          ByteBuffer },
+                                           value::ToValue },
+                        error::{ self,
+                                 script_error },
+                        interpreter::Interpreter } };
+
+
+
+/// The alphabet used by standard Base64.
+const BASE64_STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The alphabet used by URL and filename safe Base64.
+const BASE64_URL: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+
+
+/// Pop either a string or a byte buffer from the stack and get it's raw bytes.  Strings are taken
+/// as their UTF-8 encoding.
+pub fn pop_as_bytes(interpreter: &mut dyn Interpreter) -> error::Result<Vec<u8>>
+{
+    let value = interpreter.pop()?;
+
+    if value.is_byte_buffer()
+    {
+        Ok(value.as_byte_buffer(interpreter)?.borrow().bytes().to_vec())
+    }
+    else if value.is_string()
+    {
+        Ok(value.get_string_val().into_bytes())
+    }
+    else
+    {
+        script_error(interpreter, format!("Expected a string or a byte buffer, found {}.", value))
+    }
+}
+
+
+/// Push a new byte buffer that holds the given bytes.
+pub fn push_bytes(interpreter: &mut dyn Interpreter, bytes: Vec<u8>)
+{
+    let buffer: BufferPtr = Rc::new(RefCell::new(ByteBuffer::from_bytes(bytes)));
+    interpreter.push(buffer.to_value());
+}
+
+
+/// Convert bytes to a string of lowercase hex digits.
+pub fn bytes_to_hex(bytes: &[u8]) -> String
+{
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+
+/// Encode bytes with the given Base64 alphabet, optionally padding the output with `=`.
+fn base64_encode(bytes: &[u8], alphabet: &[u8; 64], pad: bool) -> String
+{
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3)
+    {
+        let value =   (chunk[0] as u32) << 16
+                    | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+                    | (*chunk.get(2).unwrap_or(&0) as u32);
+
+        // Every input byte produces at least part of one more output character.
+        let characters = chunk.len() + 1;
+
+        for index in 0..4
+        {
+            if index < characters
+            {
+                let sextet = (value >> (18 - (index * 6))) & 0x3f;
+                encoded.push(alphabet[sextet as usize] as char);
+            }
+            else if pad
+            {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+
+/// Decode Base64 text with the given alphabet.  Padding is optional, but if present it must only
+/// appear at the end of the text.  On failure the offending character's index is returned.
+fn base64_decode(text: &str, alphabet: &[u8; 64]) -> Result<Vec<u8>, usize>
+{
+    let trimmed = text.trim_end_matches('=');
+
+    if text.len() - trimmed.len() > 2
+    {
+        return Err(trimmed.len());
+    }
+
+    let mut decoded = Vec::with_capacity((trimmed.len() * 3) / 4);
+    let mut accumulator: u32 = 0;
+    let mut bits = 0;
+
+    for ( index, character ) in trimmed.bytes().enumerate()
+    {
+        let Some(sextet) = alphabet.iter().position(|found| *found == character)
+            else
+            {
+                return Err(index);
+            };
+
+        accumulator = (accumulator << 6) | sextet as u32;
+        bits += 6;
+
+        if bits >= 8
+        {
+            bits -= 8;
+            decoded.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    // A single left over character can't hold a full byte.
+    if bits >= 6
+    {
+        return Err(trimmed.len() - 1);
+    }
+
+    Ok(decoded)
+}
+
+
+
+/// Convert a byte buffer into a string of hex digits, two per byte.
+///
+/// Signature: `buffer -- hex-string`
+fn word_buffer_to_hex(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let hex = bytes_to_hex(buffer.borrow().bytes());
+
+    interpreter.push(hex.to_value());
+
+    Ok(())
+}
+
+/// Convert a string of hex digits into a new byte buffer.
+///
+/// Signature: `hex-string -- buffer`
+fn word_hex_to_buffer(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let hex = interpreter.pop_as_string()?;
+
+    if hex.len() % 2 != 0
+    {
+        script_error(interpreter, format!("Hex string {} has an odd number of digits.", hex))?;
+    }
+
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+
+    for index in (0..hex.len()).step_by(2)
+    {
+        let digits = hex.get(index..index + 2).unwrap_or("");
+
+        // Check the digits first, from_str_radix also accepts a leading + sign.
+        let byte = if digits.len() == 2 && digits.bytes().all(|digit| digit.is_ascii_hexdigit())
+            {
+                u8::from_str_radix(digits, 16).ok()
+            }
+            else
+            {
+                None
+            };
+
+        match byte
+        {
+            Some(byte) => bytes.push(byte),
+            None       => script_error(interpreter, format!("Invalid hex digits {} at index {}.",
+                                                            digits,
+                                                            index))?
+        }
+    }
+
+    push_bytes(interpreter, bytes);
+
+    Ok(())
+}
+
+/// Get the hex dump of a byte buffer as a string.
+///
+/// Signature: `buffer -- dump-string`
+fn word_buffer_hexdump(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let dump = buffer.borrow().to_string();
+
+    interpreter.push(dump.to_value());
+
+    Ok(())
+}
+
+/// Encode a string or byte buffer as standard padded Base64.
+///
+/// Signature: `string-or-buffer -- base64-string`
+fn word_base64_encode(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let bytes = pop_as_bytes(interpreter)?;
+
+    interpreter.push(base64_encode(&bytes, BASE64_STANDARD, true).to_value());
+
+    Ok(())
+}
+
+/// Encode a string or byte buffer as URL safe Base64 without padding.
+///
+/// Signature: `string-or-buffer -- base64-string`
+fn word_base64_encode_url(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let bytes = pop_as_bytes(interpreter)?;
+
+    interpreter.push(base64_encode(&bytes, BASE64_URL, false).to_value());
+
+    Ok(())
+}
+
+/// Decode Base64 text with the given alphabet and push the resulting byte buffer.
+fn base64_decode_word(interpreter: &mut dyn Interpreter, alphabet: &[u8; 64]) -> error::Result<()>
+{
+    let text = interpreter.pop_as_string()?;
+
+    match base64_decode(&text, alphabet)
+    {
+        Ok(bytes)  => push_bytes(interpreter, bytes),
+        Err(index) => script_error(interpreter, format!("Invalid Base64 text at index {}.",
+                                                        index))?
+    }
+
+    Ok(())
+}
+
+/// Decode standard Base64 into a new byte buffer.
+///
+/// Signature: `base64-string -- buffer`
+fn word_base64_decode(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    base64_decode_word(interpreter, BASE64_STANDARD)
+}
+
+/// Decode URL safe Base64 into a new byte buffer.
+///
+/// Signature: `base64-string -- buffer`
+fn word_base64_decode_url(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    base64_decode_word(interpreter, BASE64_URL)
+}
+
+/// Convert a string into a new byte buffer holding it's UTF-8 encoding.
+///
+/// Signature: `string -- buffer`
+fn word_string_to_buffer(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let string = interpreter.pop_as_string()?;
+
+    push_bytes(interpreter, string.into_bytes());
+
+    Ok(())
+}
+
+/// Convert the entire contents of a byte buffer to a string.  The buffer must hold valid UTF-8.
+///
+/// Signature: `buffer -- string`
+fn word_buffer_to_string(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let result = String::from_utf8(buffer.borrow().bytes().to_vec());
+
+    match result
+    {
+        Ok(string) => interpreter.push(string.to_value()),
+        Err(error) =>
+            {
+                let index = error.utf8_error().valid_up_to();
+
+                script_error(interpreter, format!("Buffer holds invalid UTF-8 at byte {}.",
+                                                  index))?;
+            }
+    }
+
+    Ok(())
+}
+
+
+
+/// Register the hex, Base64, and text encoding words with the given interpreter.
+pub fn register_encoding_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "buffer.to-hex", word_buffer_to_hex,
+        "Convert a byte buffer to a string of hex digits.",
+        "buffer -- hex_string");
+
+    add_native_word!(interpreter, "hex.to-buffer", word_hex_to_buffer,
+        "Convert a string of hex digits to a new byte buffer.",
+        "hex_string -- buffer");
+
+    add_native_word!(interpreter, "buffer.hexdump", word_buffer_hexdump,
+        "Get a hex dump of a byte buffer as a string.",
+        "buffer -- dump_string");
+
+    add_native_word!(interpreter, "base64.encode", word_base64_encode,
+        "Encode a string or byte buffer as standard Base64.",
+        "string_or_buffer -- base64_string");
+
+    add_native_word!(interpreter, "base64.encode-url", word_base64_encode_url,
+        "Encode a string or byte buffer as unpadded URL safe Base64.",
+        "string_or_buffer -- base64_string");
+
+    add_native_word!(interpreter, "base64.decode", word_base64_decode,
+        "Decode standard Base64 into a new byte buffer.",
+        "base64_string -- buffer");
+
+    add_native_word!(interpreter, "base64.decode-url", word_base64_decode_url,
+        "Decode URL safe Base64 into a new byte buffer.",
+        "base64_string -- buffer");
+
+    add_native_word!(interpreter, "string.to-buffer", word_string_to_buffer,
+        "Convert a string to a new byte buffer of it's UTF-8 bytes.",
+        "string -- buffer");
+
+    add_native_word!(interpreter, "buffer.to-string", word_buffer_to_string,
+        "Convert a byte buffer holding UTF-8 text to a string.",
+        "buffer -- string");
+}
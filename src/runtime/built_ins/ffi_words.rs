@@ -497,8 +497,11 @@ impl FfiInterface
                         let str_size = string.len();
                         let str_padding = FfiInterface::alignment(str_size, align);
 
-                        buffer.borrow_mut().write_int(ptr_size,
-                                                      extra.borrow_mut().position_ptr_mut() as i64);
+                        let string_ptr =
+                            FfiInterface::writable_ptr(interpreter,
+                                                       extra.borrow_mut().position_ptr_mut())?;
+
+                        buffer.borrow_mut().write_int(ptr_size, string_ptr as i64);
                         buffer.borrow_mut().increment_position(ptr_padding);
 
                         extra.borrow_mut().write_string(str_size + str_padding, &string);
@@ -556,6 +559,19 @@ impl FfiInterface
         padding
     }
 
+    /// Make sure that a buffer handed out a mutable pointer for a foreign function to use.  Buffers
+    /// that can't be written to, like read-only mapped files, don't.
+    fn writable_ptr(interpreter: &mut dyn Interpreter,
+                    ptr: Option<*mut c_void>) -> error::Result<*mut c_void>
+    {
+        match ptr
+        {
+            Some(ptr) => Ok(ptr),
+            None      => script_error_str(interpreter, "Can not pass a read-only buffer to a \
+                                                        foreign function.")
+        }
+    }
+
     /// Convert a Value to a native integer type.
     fn conversion_to_int(interpreter: &mut dyn Interpreter,
                          value: &Value,
@@ -765,12 +781,12 @@ impl FfiWord
             return script_error_str(interpreter, "Failed to create FFI cif.");
         }
 
+        let return_ptr = FfiInterface::writable_ptr(interpreter,
+                                                    return_buffer.borrow_mut().byte_ptr_mut())?;
+
         unsafe
         {
-            ffi_call(&mut cif,
-                     code_ptr,
-                     return_buffer.borrow_mut().byte_ptr_mut(),
-                     param_value_ptrs.as_mut_ptr());
+            ffi_call(&mut cif, code_ptr, return_ptr, param_value_ptrs.as_mut_ptr());
         }
 
         // Convert the return value to an interpreter Value and push it onto the data stack.  But
@@ -824,7 +840,10 @@ impl FfiWord
 
         for index in 0..args_len
         {
-            arg_value_ptrs.push(buffer.borrow_mut().position_ptr_mut());
+            let arg_ptr = FfiInterface::writable_ptr(interpreter,
+                                                     buffer.borrow_mut().position_ptr_mut())?;
+
+            arg_value_ptrs.push(arg_ptr);
             (self.arg_types[index].borrow().conversion_from)(interpreter,
                                                              &arg_values[index],
                                                              self.alignment,
@@ -0,0 +1,578 @@
+
+use std::{ fs::{ copy,
+                 create_dir_all,
+                 metadata,
+                 read_dir,
+                 remove_dir,
+                 remove_dir_all,
+                 rename,
+                 symlink_metadata,
+                 Metadata },
+           path::{ Component, Path, PathBuf },
+           time::UNIX_EPOCH };
+use crate::{ add_native_word,
+             location_here,
+             runtime::{ data_structures::{ data_object::{ DataObject,
+                                                          DataObjectDefinition,
+                                                          DataObjectDefinitionPtr },
+                                           value::{ ToValue,
+                                                    Value } },
+                        error::{ self,
+                                 script_error },
+                        interpreter::Interpreter } };
+
+
+
+/// The field names of the `file.stat` structure.
+const FILE_STAT_FIELDS: [ &str; 6 ] = [ "size", "modified", "permissions", "is_file", "is_dir",
+                                        "is_symlink" ];
+
+
+
+/// Get the names of the entries in a directory, sorted by name.
+fn list_directory(path: &Path) -> std::io::Result<Vec<String>>
+{
+    let mut names = Vec::new();
+
+    for entry in read_dir(path)?
+    {
+        names.push(entry?.file_name().to_string_lossy().to_string());
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+
+/// Recursively collect every path found under the given directory.  Symbolic links to directories
+/// are reported but not followed.
+fn walk_directory(path: &Path, paths: &mut Vec<String>) -> std::io::Result<()>
+{
+    for name in list_directory(path)?
+    {
+        let entry_path = path.join(&name);
+
+        paths.push(entry_path.to_string_lossy().to_string());
+
+        if symlink_metadata(&entry_path)?.is_dir()
+        {
+            walk_directory(&entry_path, paths)?;
+        }
+    }
+
+    Ok(())
+}
+
+
+#[cfg(unix)]
+/// Get the permission bits of a file.
+fn permission_bits(metadata: &Metadata) -> i64
+{
+    use std::os::unix::fs::PermissionsExt;
+
+    (metadata.permissions().mode() & 0o7777) as i64
+}
+
+
+#[cfg(windows)]
+/// Get an approximation of unix style permission bits for a file.  Windows only tracks if the file
+/// is read only.
+fn permission_bits(metadata: &Metadata) -> i64
+{
+    if metadata.permissions().readonly() { 0o444 } else { 0o666 }
+}
+
+
+/// Does a single path component match a glob pattern component?  Supports `*`, `?`, and character
+/// classes like `[abc]`, `[a-z]`, and `[!abc]`.  Wildcards do not match a leading `.` so that hidden
+/// files are only matched when asked for explicitly.
+fn glob_match_component(pattern: &[char], text: &[char]) -> bool
+{
+    if    !text.is_empty()
+       && text[0] == '.'
+       && !pattern.is_empty()
+       && pattern[0] != '.'
+    {
+        return false;
+    }
+
+    glob_match_chars(pattern, text)
+}
+
+
+/// Match the characters of a glob pattern against some text.  When a match fails only the most
+/// recent `*` is backtracked, by having it swallow one more character, which keeps matching linear
+/// in the length of the pattern for each position in the text.
+fn glob_match_chars(pattern: &[char], text: &[char]) -> bool
+{
+    let mut pattern_index = 0;
+    let mut text_index = 0;
+
+    // The pattern index just past the most recent star, and the text index it last matched up to.
+    let mut star: Option<( usize, usize )> = None;
+
+    while text_index < text.len()
+    {
+        if pattern.get(pattern_index) == Some(&'*')
+        {
+            pattern_index += 1;
+            star = Some(( pattern_index, text_index ));
+            continue;
+        }
+
+        if let Some(consumed) = glob_match_char(&pattern[pattern_index..], text[text_index])
+        {
+            pattern_index += consumed;
+            text_index += 1;
+            continue;
+        }
+
+        match star
+        {
+            Some(( star_pattern, star_text )) =>
+                {
+                    pattern_index = star_pattern;
+                    text_index = star_text + 1;
+                    star = Some(( star_pattern, text_index ));
+                },
+
+            None => return false
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|next| *next == '*')
+}
+
+
+/// Match a single character against the start of a glob pattern that isn't a `*`.  Returns how
+/// many pattern characters were used, or none if the character doesn't match.
+fn glob_match_char(pattern: &[char], character: char) -> Option<usize>
+{
+    match pattern.first()
+    {
+        None | Some('*') => None,
+
+        Some('?') => Some(1),
+
+        Some('[') =>
+            {
+                match match_class(&pattern[1..], Some(&character))
+                {
+                    Some(( true, consumed )) => Some(consumed + 1),
+                    Some(( false, _ ))       => None,
+
+                    // The class was never closed, so treat the bracket as a literal character.
+                    None => if character == '[' { Some(1) } else { None }
+                }
+            },
+
+        Some(next) => if *next == character { Some(1) } else { None }
+    }
+}
+
+
+/// Check a character against a character class, the pattern starts just after the opening `[`.
+/// Returns if the character matched and how many pattern characters the class used, or none if
+/// the class isn't closed.
+fn match_class(pattern: &[char], character: Option<&char>) -> Option<( bool, usize )>
+{
+    let negated = matches!(pattern.first(), Some('!') | Some('^'));
+    let mut index = if negated { 1 } else { 0 };
+    let mut matched = false;
+    let start = index;
+
+    while index < pattern.len()
+    {
+        // A closing bracket as the first character of the class is a literal.
+        if pattern[index] == ']' && index > start
+        {
+            let is_match = character.is_some() && matched != negated;
+            return Some(( is_match, index + 1 ));
+        }
+
+        if    index + 2 < pattern.len()
+           && pattern[index + 1] == '-'
+           && pattern[index + 2] != ']'
+        {
+            if let Some(character) = character
+            {
+                matched |= pattern[index] <= *character && *character <= pattern[index + 2];
+            }
+
+            index += 3;
+        }
+        else
+        {
+            matched |= character == Some(&pattern[index]);
+            index += 1;
+        }
+    }
+
+    None
+}
+
+
+/// Does a pattern contain any glob wildcards?
+fn has_wildcards(pattern: &str) -> bool
+{
+    pattern.contains([ '*', '?', '[' ])
+}
+
+
+/// Split a path or pattern into it's components.  Both separators are accepted on Windows.
+fn split_components(path: &str) -> Vec<&str>
+{
+    path.split([ '/', std::path::MAIN_SEPARATOR ])
+        .filter(|component| !component.is_empty())
+        .collect()
+}
+
+
+/// Match a full path against a glob pattern.  A `**` component matches any number of directories.
+fn glob_match_path(pattern: &[&str], path: &[&str]) -> bool
+{
+    match pattern.first()
+    {
+        None => path.is_empty(),
+
+        Some(&"**") =>
+            {
+                ( 0..=path.len() ).any(|skip| glob_match_path(&pattern[1..], &path[skip..]))
+            },
+
+        Some(component) =>
+            {
+                   !path.is_empty()
+                && glob_match_component(&component.chars().collect::<Vec<char>>(),
+                                        &path[0].chars().collect::<Vec<char>>())
+                && glob_match_path(&pattern[1..], &path[1..])
+            }
+    }
+}
+
+
+/// Find all of the paths that match the remaining pattern components under the given base path.
+fn glob_walk(base: &Path, pattern: &[&str], results: &mut Vec<String>)
+{
+    // An empty base refers to the current directory, but we want to keep the results relative.
+    let directory = if base.as_os_str().is_empty() { Path::new(".") } else { base };
+
+    match pattern.first()
+    {
+        None => results.push(base.to_string_lossy().to_string()),
+
+        Some(&"**") =>
+            {
+                glob_walk(base, &pattern[1..], results);
+
+                for name in list_directory(directory).unwrap_or_default()
+                {
+                    let path = base.join(&name);
+
+                    if    !name.starts_with('.')
+                       && symlink_metadata(&path).map(|info| info.is_dir()).unwrap_or(false)
+                    {
+                        glob_walk(&path, pattern, results);
+                    }
+                }
+            },
+
+        Some(component) if !has_wildcards(component) =>
+            {
+                let path = base.join(component);
+
+                if path.exists()
+                {
+                    glob_walk(&path, &pattern[1..], results);
+                }
+            },
+
+        Some(component) =>
+            {
+                let component: Vec<char> = component.chars().collect();
+
+                for name in list_directory(directory).unwrap_or_default()
+                {
+                    let path = base.join(&name);
+
+                    if    glob_match_component(&component, &name.chars().collect::<Vec<char>>())
+                       && ( pattern.len() == 1 || path.is_dir() )
+                    {
+                        glob_walk(&path, &pattern[1..], results);
+                    }
+                }
+            }
+    }
+}
+
+
+
+/// Get a sorted list of the names of the entries within a directory.
+///
+/// Signature: `path -- names`
+fn word_dir_list(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    match list_directory(Path::new(&path))
+    {
+        Ok(names) => interpreter.push(Value::from(names)),
+        Err(error) => script_error(interpreter, format!("Could not list directory {}: {}.",
+                                                        path,
+                                                        error))?
+    }
+
+    Ok(())
+}
+
+/// Create a directory, including any missing parent directories.
+///
+/// Signature: `path -- `
+fn word_dir_create(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    if let Err(error) = create_dir_all(&path)
+    {
+        script_error(interpreter, format!("Could not create directory {}: {}.", path, error))?;
+    }
+
+    Ok(())
+}
+
+/// Remove an empty directory.
+///
+/// Signature: `path -- `
+fn word_dir_remove(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    if let Err(error) = remove_dir(&path)
+    {
+        script_error(interpreter, format!("Could not remove directory {}: {}.", path, error))?;
+    }
+
+    Ok(())
+}
+
+/// Remove a directory along with everything it contains.
+///
+/// Signature: `path -- `
+fn word_dir_remove_all(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    if let Err(error) = remove_dir_all(&path)
+    {
+        script_error(interpreter, format!("Could not remove directory {}: {}.", path, error))?;
+    }
+
+    Ok(())
+}
+
+/// Recursively list every file and directory under a directory.  The paths are relative to the
+/// given directory path.
+///
+/// Signature: `path -- paths`
+fn word_dir_walk(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    let mut paths = Vec::new();
+
+    match walk_directory(Path::new(&path), &mut paths)
+    {
+        Ok(()) => interpreter.push(Value::from(paths)),
+        Err(error) => script_error(interpreter, format!("Could not walk directory {}: {}.",
+                                                        path,
+                                                        error))?
+    }
+
+    Ok(())
+}
+
+/// Rename or move a file or directory.
+///
+/// Signature: `from-path to-path -- `
+fn word_file_rename(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let to = interpreter.pop_as_string()?;
+    let from = interpreter.pop_as_string()?;
+
+    if let Err(error) = rename(&from, &to)
+    {
+        script_error(interpreter, format!("Could not rename {} to {}: {}.", from, to, error))?;
+    }
+
+    Ok(())
+}
+
+/// Copy a file's contents and permissions to a new file.
+///
+/// Signature: `from-path to-path -- `
+fn word_file_copy(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let to = interpreter.pop_as_string()?;
+    let from = interpreter.pop_as_string()?;
+
+    if let Err(error) = copy(&from, &to)
+    {
+        script_error(interpreter, format!("Could not copy {} to {}: {}.", from, to, error))?;
+    }
+
+    Ok(())
+}
+
+/// Read a file's metadata into a file.stat structure.  Symbolic links are followed for everything
+/// but the is_symlink field, unless the link is broken.
+///
+/// Signature: `path -- file-stat`
+fn word_file_stat(interpreter: &mut dyn Interpreter,
+                  definition: &DataObjectDefinitionPtr) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    let link_info = match symlink_metadata(&path)
+        {
+            Ok(link_info) => link_info,
+            Err(error) => return script_error(interpreter, format!("Could not stat {}: {}.",
+                                                                   path,
+                                                                   error))
+        };
+
+    let info = metadata(&path).unwrap_or_else(|_| link_info.clone());
+
+    let modified = info.modified()
+                       .ok()
+                       .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                       .map(|duration| duration.as_secs() as i64)
+                       .unwrap_or(0);
+
+    let data_ptr = DataObject::new(definition);
+
+    data_ptr.borrow_mut().fields = vec![ info.len().to_value(),
+                                         modified.to_value(),
+                                         permission_bits(&info).to_value(),
+                                         info.is_file().to_value(),
+                                         info.is_dir().to_value(),
+                                         link_info.file_type().is_symlink().to_value() ];
+
+    interpreter.push(data_ptr.to_value());
+    Ok(())
+}
+
+/// Find all of the paths that match a glob pattern.  The pattern supports `*`, `?`, `[...]`, and
+/// `**` to match any number of directories.
+///
+/// Signature: `pattern -- paths`
+fn word_file_glob(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let pattern = interpreter.pop_as_string()?;
+
+    // Absolute patterns start their search from the root of the filesystem.
+    let base: PathBuf = Path::new(&pattern).components()
+                                           .take_while(|component|
+                                               {
+                                                   matches!(component, Component::Prefix(_)
+                                                                       | Component::RootDir)
+                                               })
+                                           .collect();
+
+    let components = split_components(&pattern[base.as_os_str().len()..]);
+
+    let mut results = Vec::new();
+
+    glob_walk(&base, &components, &mut results);
+
+    results.sort();
+    results.dedup();
+
+    interpreter.push(Value::from(results));
+    Ok(())
+}
+
+/// Check if a path matches a glob pattern, without touching the filesystem.
+///
+/// Signature: `path pattern -- matches?`
+fn word_glob_match(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let pattern = interpreter.pop_as_string()?;
+    let path = interpreter.pop_as_string()?;
+
+    let matches = glob_match_path(&split_components(&pattern), &split_components(&path));
+
+    interpreter.push(matches.to_value());
+    Ok(())
+}
+
+
+
+/// Register the file.stat structure and it's accessor words.
+fn register_file_stat_struct(interpreter: &mut dyn Interpreter) -> DataObjectDefinitionPtr
+{
+    let field_names = FILE_STAT_FIELDS.iter().map(|name| name.to_string()).collect();
+    let defaults = FILE_STAT_FIELDS.iter().map(|_| Value::None).collect();
+
+    let definition = DataObjectDefinition::new(interpreter,
+                                               "file.stat".to_string(),
+                                               field_names,
+                                               defaults,
+                                               false);
+
+    DataObjectDefinition::create_data_definition_words(interpreter,
+                                                       Some(location_here!()),
+                                                       definition.clone(),
+                                                       false);
+
+    definition
+}
+
+
+/// Register the filesystem words with the given interpreter.
+pub fn register_fs_words(interpreter: &mut dyn Interpreter)
+{
+    let definition = register_file_stat_struct(interpreter);
+
+    add_native_word!(interpreter, "dir.list", word_dir_list,
+        "Get a sorted list of the names of the entries in a directory.",
+        "path -- names");
+
+    add_native_word!(interpreter, "dir.create", word_dir_create,
+        "Create a directory along with any missing parent directories.",
+        "path -- ");
+
+    add_native_word!(interpreter, "dir.remove", word_dir_remove,
+        "Remove an empty directory.",
+        "path -- ");
+
+    add_native_word!(interpreter, "dir.remove-all", word_dir_remove_all,
+        "Remove a directory and everything within it.",
+        "path -- ");
+
+    add_native_word!(interpreter, "dir.walk", word_dir_walk,
+        "Recursively list the paths of everything within a directory.",
+        "path -- paths");
+
+    add_native_word!(interpreter, "file.rename", word_file_rename,
+        "Rename or move a file or directory.",
+        "from_path to_path -- ");
+
+    add_native_word!(interpreter, "file.copy", word_file_copy,
+        "Copy a file to a new path.",
+        "from_path to_path -- ");
+
+    add_native_word!(interpreter, "file.stat",
+        move |interpreter: &mut dyn Interpreter|
+        {
+            word_file_stat(interpreter, &definition)
+        },
+        "Read the size, modification time, permissions, and type of a file.",
+        "path -- file_stat");
+
+    add_native_word!(interpreter, "file.glob", word_file_glob,
+        "Get a sorted list of the paths that match a glob pattern.",
+        "pattern -- paths");
+
+    add_native_word!(interpreter, "glob.match?", word_glob_match,
+        "Check if a path matches a glob pattern.",
+        "path pattern -- bool");
+}
@@ -0,0 +1,228 @@
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{ Digest,
+            Sha256 };
+use crate::{ add_native_word,
+             runtime::{ built_ins::encoding_words::{ bytes_to_hex,
+                                                     pop_as_bytes,
+                                                     push_bytes },
+                        data_structures::value::ToValue,
+                        error::{ self,
+                                 script_error },
+                        interpreter::Interpreter } };
+
+
+
+/// The running state of one of the supported hash algorithms.
+enum Hasher
+{
+    Crc32(crc32fast::Hasher),
+    Sha256(Sha256),
+    Sha1(Sha1),
+    Md5(Md5)
+}
+
+
+impl Hasher
+{
+    /// Create a new hasher for the named algorithm, if the algorithm is supported.
+    fn new(algorithm: &str) -> Option<Hasher>
+    {
+        match algorithm
+        {
+            "crc32"  => Some(Hasher::Crc32(crc32fast::Hasher::new())),
+            "sha256" => Some(Hasher::Sha256(Sha256::new())),
+            "sha1"   => Some(Hasher::Sha1(Sha1::new())),
+            "md5"    => Some(Hasher::Md5(Md5::new())),
+            _        => None
+        }
+    }
+
+    /// Add more bytes to the running hash.
+    fn update(&mut self, bytes: &[u8])
+    {
+        match self
+        {
+            Hasher::Crc32(hasher)  => hasher.update(bytes),
+            Hasher::Sha256(hasher) => hasher.update(bytes),
+            Hasher::Sha1(hasher)   => hasher.update(bytes),
+            Hasher::Md5(hasher)    => hasher.update(bytes)
+        }
+    }
+
+    /// Finish the hash and get the digest's bytes.  The CRC32 digest is given in big endian order
+    /// so that it's hex form matches the usual way of writing the checksum.
+    fn finish(self) -> Vec<u8>
+    {
+        match self
+        {
+            Hasher::Crc32(hasher)  => hasher.finalize().to_be_bytes().to_vec(),
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha1(hasher)   => hasher.finalize().to_vec(),
+            Hasher::Md5(hasher)    => hasher.finalize().to_vec()
+        }
+    }
+}
+
+
+
+/// Hash a string or byte buffer with the named algorithm and push the digest as a hex string.
+fn hash_word(interpreter: &mut dyn Interpreter, algorithm: &str) -> error::Result<()>
+{
+    let bytes = pop_as_bytes(interpreter)?;
+    let mut hasher = Hasher::new(algorithm).expect("Unsupported hash algorithm.");
+
+    hasher.update(&bytes);
+    interpreter.push(bytes_to_hex(&hasher.finish()).to_value());
+
+    Ok(())
+}
+
+/// Pop a hash handle from the stack and remove it's hasher from the hash table.
+fn pop_hasher(interpreter: &mut dyn Interpreter) -> error::Result<Hasher>
+{
+    let handle = interpreter.pop_as_int()?;
+    let hasher = interpreter.resources_mut().remove::<Hasher>(handle);
+
+    match hasher
+    {
+        Some(hasher) => Ok(hasher),
+        None         => script_error(interpreter, format!("Hash handle {} is not open.", handle))
+    }
+}
+
+
+
+/// Compute the CRC32 checksum of a string or byte buffer.
+///
+/// Signature: `string-or-buffer -- hex-string`
+fn word_hash_crc32(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    hash_word(interpreter, "crc32")
+}
+
+/// Compute the SHA-256 digest of a string or byte buffer.
+///
+/// Signature: `string-or-buffer -- hex-string`
+fn word_hash_sha256(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    hash_word(interpreter, "sha256")
+}
+
+/// Compute the SHA-1 digest of a string or byte buffer.
+///
+/// Signature: `string-or-buffer -- hex-string`
+fn word_hash_sha1(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    hash_word(interpreter, "sha1")
+}
+
+/// Compute the MD5 digest of a string or byte buffer.
+///
+/// Signature: `string-or-buffer -- hex-string`
+fn word_hash_md5(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    hash_word(interpreter, "md5")
+}
+
+/// Start a new streaming hash with the named algorithm.  The algorithm is one of crc32, sha256,
+/// sha1, or md5.
+///
+/// Signature: `algorithm -- handle`
+fn word_hash_new(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let algorithm = interpreter.pop_as_string()?;
+
+    let Some(hasher) = Hasher::new(&algorithm)
+        else
+        {
+            return script_error(interpreter, format!("Unknown hash algorithm {}.", algorithm));
+        };
+
+    // In progress hashes are kept in the interpreter's resource table, indexed by their handle.
+    let handle = interpreter.resources_mut().add(hasher);
+
+    interpreter.push(handle.to_value());
+
+    Ok(())
+}
+
+/// Add the bytes of a string or byte buffer to a streaming hash.
+///
+/// Signature: `string-or-buffer handle -- `
+fn word_hash_update(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let handle = interpreter.pop_as_int()?;
+    let bytes = pop_as_bytes(interpreter)?;
+
+    match interpreter.resources_mut().get_mut::<Hasher>(handle)
+    {
+        Some(hasher) => hasher.update(&bytes),
+        None         => script_error(interpreter, format!("Hash handle {} is not open.", handle))?
+    }
+
+    Ok(())
+}
+
+/// Finish a streaming hash and push the digest as a new byte buffer.  The handle is closed.
+///
+/// Signature: `handle -- buffer`
+fn word_hash_finish(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let hasher = pop_hasher(interpreter)?;
+
+    push_bytes(interpreter, hasher.finish());
+
+    Ok(())
+}
+
+/// Finish a streaming hash and push the digest as a hex string.  The handle is closed.
+///
+/// Signature: `handle -- hex-string`
+fn word_hash_finish_hex(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let hasher = pop_hasher(interpreter)?;
+
+    interpreter.push(bytes_to_hex(&hasher.finish()).to_value());
+
+    Ok(())
+}
+
+
+
+/// Register the checksum and hash words with the given interpreter.
+pub fn register_hash_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "hash.crc32", word_hash_crc32,
+        "Compute the CRC32 checksum of a string or byte buffer as hex.",
+        "string_or_buffer -- hex_string");
+
+    add_native_word!(interpreter, "hash.sha256", word_hash_sha256,
+        "Compute the SHA-256 digest of a string or byte buffer as hex.",
+        "string_or_buffer -- hex_string");
+
+    add_native_word!(interpreter, "hash.sha1", word_hash_sha1,
+        "Compute the SHA-1 digest of a string or byte buffer as hex.",
+        "string_or_buffer -- hex_string");
+
+    add_native_word!(interpreter, "hash.md5", word_hash_md5,
+        "Compute the MD5 digest of a string or byte buffer as hex.",
+        "string_or_buffer -- hex_string");
+
+    add_native_word!(interpreter, "hash.new", word_hash_new,
+        "Start a streaming hash using crc32, sha256, sha1, or md5.",
+        "algorithm -- handle");
+
+    add_native_word!(interpreter, "hash.update", word_hash_update,
+        "Add a string or byte buffer to a streaming hash.",
+        "string_or_buffer handle -- ");
+
+    add_native_word!(interpreter, "hash.finish", word_hash_finish,
+        "Finish a streaming hash, returning the digest as a byte buffer.",
+        "handle -- buffer");
+
+    add_native_word!(interpreter, "hash.finish-hex", word_hash_finish_hex,
+        "Finish a streaming hash, returning the digest as a hex string.",
+        "handle -- hex_string");
+}
@@ -1,16 +1,25 @@
 
-use std::{ collections::HashMap,
+use std::{ cell::RefCell,
+           collections::HashMap,
            fs::{ remove_file,
                  File,
                  OpenOptions },
-           io::{ BufRead, BufReader, BufWriter, Read, Write, Seek, SeekFrom },
-           os::unix::net::UnixStream,
-           path::Path,
-           sync::{ atomic::{ AtomicI64, Ordering },
-                   Mutex } };
-use lazy_static::lazy_static;
+           io::{ BufRead, BufReader, BufWriter, ErrorKind, Read, Write, Seek, SeekFrom },
+           net::{ Shutdown, SocketAddr, TcpListener, TcpStream, UdpSocket },
+           os::{ fd::{ AsRawFd, RawFd },
+                 unix::net::{ UnixListener, UnixStream } },
+           path::{ Path, PathBuf },
+           rc::Rc,
+           time::{ Duration, Instant } };
 use crate::{ add_native_word,
-             runtime::{ data_structures::value::ToValue,
+             location_here,
+             runtime::{ data_structures::{ byte_buffer::{ BufferPtr,
+                                                          ByteBuffer,
+                                                          MappedBuffer },
+                                           contextual_data::ContextualData,
+                                           value::{ ToValue,
+                                                    Value },
+                                           value_hash::ValueHash },
                         error::{ self,
                                  script_error,
                                  script_error_str },
@@ -18,68 +27,387 @@ use crate::{ add_native_word,
 
 
 
-enum FileObject
+/// The objects that can be referred to by a fd within a script.
+pub enum FileObject
 {
     File(File),
-    Stream(UnixStream)
+    Stream(UnixStream),
+    Pipe(File),
+    TcpStream(TcpStream),
+    TcpListener(TcpListener),
+    UdpSocket(UdpSocket),
+
+    // Unix domain socket listeners keep track of their socket file so that it can be removed when
+    // the listener is closed.
+    UnixListener(UnixListener, PathBuf)
 }
 
 
-lazy_static!
+impl FileObject
 {
-    // The counter for generating new IDs.
-    static ref FD_COUNTER: AtomicI64 = AtomicI64::new(4);
+    /// Create a new handle to the same underlying object.
+    fn try_clone(&self) -> std::io::Result<FileObject>
+    {
+        match self
+        {
+            FileObject::File(file)     => Ok(FileObject::File(file.try_clone()?)),
+            FileObject::Stream(stream) => Ok(FileObject::Stream(stream.try_clone()?)),
+            FileObject::Pipe(pipe)     => Ok(FileObject::Pipe(pipe.try_clone()?)),
+
+            FileObject::TcpStream(stream) =>
+                Ok(FileObject::TcpStream(stream.try_clone()?)),
+
+            FileObject::TcpListener(listener) =>
+                Ok(FileObject::TcpListener(listener.try_clone()?)),
+
+            FileObject::UdpSocket(socket) =>
+                Ok(FileObject::UdpSocket(socket.try_clone()?)),
+
+            FileObject::UnixListener(listener, path) =>
+                Ok(FileObject::UnixListener(listener.try_clone()?, path.clone()))
+        }
+    }
+
+    /// Check if the object supports reading.
+    fn is_readable(&self) -> bool
+    {
+        matches!(self, FileObject::File(_) |
+                       FileObject::Stream(_) |
+                       FileObject::Pipe(_) |
+                       FileObject::TcpStream(_))
+    }
+
+    /// Get the object as something that can be read from, if it supports reading.
+    pub fn reader(&mut self) -> Option<&mut dyn Read>
+    {
+        match self
+        {
+            FileObject::File(file)         => Some(file),
+            FileObject::Stream(stream)     => Some(stream),
+            FileObject::Pipe(pipe)         => Some(pipe),
+            FileObject::TcpStream(stream)  => Some(stream),
+            FileObject::TcpListener(_)     => None,
+            FileObject::UdpSocket(_)       => None,
+            FileObject::UnixListener(_, _) => None
+        }
+    }
+
+    /// Get the object as something that can be written to, if it supports writing.
+    pub fn writer(&mut self) -> Option<&mut dyn Write>
+    {
+        match self
+        {
+            FileObject::File(file)         => Some(file),
+            FileObject::Stream(stream)     => Some(stream),
+            FileObject::Pipe(pipe)         => Some(pipe),
+            FileObject::TcpStream(stream)  => Some(stream),
+            FileObject::TcpListener(_)     => None,
+            FileObject::UdpSocket(_)       => None,
+            FileObject::UnixListener(_, _) => None
+        }
+    }
 
-    // Keep a table to map generated FDs to file structs.
-    static ref FILE_TABLE: Mutex<HashMap<i64, FileObject>> = Mutex::new(HashMap::new());
+    /// Get the operating system's fd for the object.
+    fn raw_fd(&self) -> RawFd
+    {
+        match self
+        {
+            FileObject::File(file)                => file.as_raw_fd(),
+            FileObject::Stream(stream)            => stream.as_raw_fd(),
+            FileObject::Pipe(pipe)                => pipe.as_raw_fd(),
+            FileObject::TcpStream(stream)         => stream.as_raw_fd(),
+            FileObject::TcpListener(listener)     => listener.as_raw_fd(),
+            FileObject::UdpSocket(socket)         => socket.as_raw_fd(),
+            FileObject::UnixListener(listener, _) => listener.as_raw_fd()
+        }
+    }
+
+    /// A description of the kind of object, used when reporting errors.
+    pub fn description(&self) -> &'static str
+    {
+        match self
+        {
+            FileObject::File(_)            => "a file",
+            FileObject::Stream(_)          => "a socket",
+            FileObject::Pipe(_)            => "a pipe",
+            FileObject::TcpStream(_)       => "a socket",
+            FileObject::TcpListener(_)     => "a listening socket",
+            FileObject::UdpSocket(_)       => "a UDP socket",
+            FileObject::UnixListener(_, _) => "a listening socket"
+        }
+    }
 }
 
-fn generate_fd() -> i64
+
+impl Read for FileObject
 {
-    FD_COUNTER.fetch_add(1, Ordering::SeqCst)
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize>
+    {
+        let description = self.description();
+
+        match self.reader()
+        {
+            Some(reader) => reader.read(buffer),
+            None         => Err(std::io::Error::new(ErrorKind::Unsupported,
+                                                    format!("Can not read from {}.", description)))
+        }
+    }
 }
 
-fn add_file(fd: i64, file: File)
+
+impl Seek for FileObject
 {
-    FILE_TABLE.lock().unwrap().insert(fd, FileObject::File(file));
+    fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64>
+    {
+        match self
+        {
+            FileObject::File(file) => file.seek(position),
+            other                  => Err(std::io::Error::new(ErrorKind::Unsupported,
+                                                              format!("Can not seek in {}.",
+                                                                      other.description())))
+        }
+    }
 }
 
-fn add_stream(fd: i64, stream: UnixStream)
+
+/// Seek origin for offsets from the start of a file.
+const SEEK_START: i64 = 0;
+
+/// Seek origin for offsets from the current position in a file.
+const SEEK_CURRENT: i64 = 1;
+
+/// Seek origin for offsets from the end of a file.
+const SEEK_END: i64 = 2;
+
+
+/// Flag for polling if a fd is ready to be read from.
+const POLL_READ: i64 = 0b0001;
+
+/// Flag for polling if a fd is ready to be written to.
+const POLL_WRITE: i64 = 0b0010;
+
+/// Flag reported by polling when the other end of a fd has hung up.
+const POLL_HANGUP: i64 = 0b0100;
+
+/// Flag reported by polling when a fd is in an error state.
+const POLL_ERROR: i64 = 0b1000;
+
+
+/// The largest datagram a UDP socket can receive.
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+
+/// The table of files, sockets, and pipes opened by an interpreter, indexed by the fds handed out
+/// to scripts.  Everything in the table is closed when the interpreter is reset.
+pub struct FileTable
 {
-    FILE_TABLE.lock().unwrap().insert(fd, FileObject::Stream(stream));
+    /// The next fd to hand out.  Fds are never reused while the table exists.
+    next_fd: i64,
+
+    /// The open objects indexed by their fds.  Every object has a single buffered reader that all
+    /// of the read words share, so data read ahead by one word is still there for the next.
+    objects: HashMap<i64, BufReader<FileObject>>,
+
+    /// The fds opened and the fds to close on release for each marked context.
+    scopes: Vec<FileScope>
 }
 
-fn get_file(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<FileObject>
+
+/// The fds that belong to a single marked context.
+#[derive(Default)]
+struct FileScope
 {
-    let table = FILE_TABLE.lock().unwrap();
-    let file = table.get(&fd);
+    /// The fds that were opened while this was the current context.
+    opened: Vec<i64>,
 
-    match file
+    /// The fds that should be closed when this context is released.
+    close_on_release: Vec<i64>
+}
+
+
+impl ContextualData for FileTable
+{
+    fn mark_context(&mut self)
+    {
+        self.scopes.push(FileScope::default());
+    }
+
+    fn release_context(&mut self)
+    {
+        if let Some(scope) = self.scopes.pop()
+        {
+            for fd in scope.close_on_release
+            {
+                let _ = self.close(fd);
+            }
+
+            // Anything still open now belongs to the enclosing context.
+            if let Some(parent) = self.scopes.last_mut()
+            {
+                parent.opened.extend(scope.opened.into_iter()
+                                                 .filter(|fd| self.objects.contains_key(fd)));
+            }
+        }
+    }
+}
+
+
+impl FileTable
+{
+    /// Create a new empty file table.
+    pub fn new() -> FileTable
     {
-        Some(file) =>
+        FileTable
             {
-                match file
+                next_fd: 4,
+                objects: HashMap::new(),
+                scopes: Vec::new()
+            }
+    }
+
+    /// Add a new object to the table and get the fd generated for it.
+    pub fn add(&mut self, object: FileObject) -> i64
+    {
+        let fd = self.next_fd;
+
+        self.next_fd += 1;
+        self.objects.insert(fd, BufReader::new(object));
+
+        if let Some(scope) = self.scopes.last_mut()
+        {
+            scope.opened.push(fd);
+        }
+
+        fd
+    }
+
+    /// Check if a fd is open.
+    pub fn contains(&self, fd: i64) -> bool
+    {
+        self.objects.contains_key(&fd)
+    }
+
+    /// Get a new handle to the object for a fd, if the fd is open.
+    pub fn get(&mut self, fd: i64) -> Option<std::io::Result<FileObject>>
+    {
+        let reader = self.objects.get_mut(&fd)?;
+
+        // A new handle to a file shares the file's position, so hand any data that was read ahead
+        // back to the file first.  Otherwise writes and seeks would happen past the data the script
+        // has actually read.
+        if !reader.buffer().is_empty() && matches!(reader.get_ref(), FileObject::File(_))
+        {
+            // Seeking always drops the reader's buffer.
+            let result = reader.stream_position()
+                               .and_then(|position| reader.seek(SeekFrom::Start(position)));
+
+            if let Err(error) = result
+            {
+                return Some(Err(error));
+            }
+        }
+
+        Some(reader.get_ref().try_clone())
+    }
+
+    /// Get the buffered reader for a fd, if the fd is open.
+    pub fn reader(&mut self, fd: i64) -> Option<&mut BufReader<FileObject>>
+    {
+        self.objects.get_mut(&fd)
+    }
+
+    /// Check if data has already been read ahead for a fd and is waiting in it's buffer.
+    pub fn has_buffered_input(&self, fd: i64) -> bool
+    {
+        self.objects
+            .get(&fd)
+            .is_some_and(|reader| !reader.buffer().is_empty())
+    }
+
+    /// Close a fd, returning false if the fd wasn't open.
+    pub fn close(&mut self, fd: i64) -> bool
+    {
+        match self.objects.remove(&fd).map(BufReader::into_inner)
+        {
+            // Listening Unix domain sockets leave their socket file behind, so clean it up now.
+            Some(FileObject::UnixListener(_, path)) =>
                 {
-                    FileObject::File(file)     => Ok(FileObject::File(file.try_clone()?)),
-                    FileObject::Stream(stream) => Ok(FileObject::Stream(stream.try_clone()?))
-                }
+                    let _ = remove_file(path);
+                    true
+                },
+
+            Some(_) => true,
+            None    => false
+        }
+    }
+
+    /// Close a fd automatically when the current context is released.  Returns false if there is
+    /// no context to attach the fd to.
+    pub fn close_on_release(&mut self, fd: i64) -> bool
+    {
+        match self.scopes.last_mut()
+        {
+            Some(scope) =>
+                {
+                    scope.close_on_release.push(fd);
+                    true
+                },
+
+            None => false
+        }
+    }
+
+    /// Close everything opened since the current context was marked.  Fds opened in enclosing
+    /// contexts are left open.
+    pub fn reset(&mut self)
+    {
+        if let Some(scope) = self.scopes.last_mut()
+        {
+            let fds: Vec<i64> = scope.opened.drain(..).collect();
+
+            scope.close_on_release.clear();
+
+            for fd in fds
+            {
+                let _ = self.close(fd);
             }
+        }
+    }
+}
+
 
-        None => script_error(interpreter, format!("File struct for fd {} not found.", fd))
+pub fn get_file(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<FileObject>
+{
+    match interpreter.files_mut().get(fd)
+    {
+        Some(file) => Ok(file?),
+        None       => script_error(interpreter, format!("File struct for fd {} not found.", fd))
     }
 }
 
-fn unregister_file(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<()>
+/// Read from a fd through it's shared buffered reader.  The read function's result is returned if
+/// the fd is open and can be read from.
+pub fn read_file<T, F>(interpreter: &mut dyn Interpreter, fd: i64, read: F) -> error::Result<T>
+    where F: FnOnce(&mut BufReader<FileObject>) -> T
 {
-    let mut table = FILE_TABLE.lock().unwrap();
+    let description = match interpreter.files_mut().reader(fd)
+        {
+            Some(reader) if reader.get_ref().is_readable() => return Ok(read(reader)),
+            Some(reader)                                   => reader.get_ref().description(),
+            None                                           =>
+                return script_error(interpreter, format!("File struct for fd {} not found.", fd))
+        };
+
+    script_error(interpreter, format!("Can not read from {}.", description))
+}
 
-    if !table.contains_key(&fd)
+fn unregister_file(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<()>
+{
+    if !interpreter.files_mut().close(fd)
     {
         script_error(interpreter, format!("File struct not found for fd {}.", fd))?;
     }
 
-    table.remove(&fd);
-
     Ok(())
 }
 
@@ -113,9 +441,7 @@ fn word_file_open(interpreter: &mut dyn Interpreter) -> error::Result<()>
     {
         Ok(file) =>
             {
-                let fd = generate_fd();
-
-                add_file(fd, file);
+                let fd = interpreter.files_mut().add(FileObject::File(file));
                 interpreter.push(fd.to_value());
             },
 
@@ -142,66 +468,647 @@ fn word_file_create(interpreter: &mut dyn Interpreter) -> error::Result<()>
     {
         Ok(file) =>
             {
-                let fd = generate_fd();
+                let fd = interpreter.files_mut().add(FileObject::File(file));
+                interpreter.push(fd.to_value());
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Could not open file {}: {}", path, error))?;
+            }
+    }
+
+    Ok(())
+}
+
+fn word_file_create_temp_file(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    script_error_str(interpreter, "Create temp file unimplemented.")
+}
+
+fn word_file_close(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+
+    unregister_file(interpreter, fd)?;
+
+    Ok(())
+}
+
+fn word_file_scoped(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+
+    if !interpreter.files().contains(fd)
+    {
+        script_error(interpreter, format!("File struct not found for fd {}.", fd))?;
+    }
+
+    if !interpreter.files_mut().close_on_release(fd)
+    {
+        script_error_str(interpreter, "There is no context to attach the fd to.")?;
+    }
+
+    interpreter.push(fd.to_value());
+
+    Ok(())
+}
+
+fn word_file_with(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let word_index = interpreter.pop_as_int()?;
+
+    word_file_open(interpreter)?;
+
+    let fd = interpreter.pop_as_int()?;
+
+    // Make sure the file is closed even if the word raises an error.
+    interpreter.push(fd.to_value());
+    let result = interpreter.execute_word_index(&location_here!(), word_index as usize);
+
+    let _ = interpreter.files_mut().close(fd);
+
+    result
+}
+
+fn word_file_delete(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    remove_file(&path)?;
+
+    Ok(())
+}
+
+fn word_socket_connect(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    match UnixStream::connect(&path)
+    {
+        Ok(stream) =>
+            {
+                let fd = interpreter.files_mut().add(FileObject::Stream(stream));
+                interpreter.push(fd.to_value());
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Failed to connect to socket {}: {}",
+                                                  path,
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+/// Pop a port number off of the stack and make sure it's in range.
+fn pop_port(interpreter: &mut dyn Interpreter) -> error::Result<u16>
+{
+    let port = interpreter.pop_as_int()?;
+
+    if !(0..=u16::MAX as i64).contains(&port)
+    {
+        script_error(interpreter, format!("Port {} is out of range.", port))?;
+    }
+
+    Ok(port as u16)
+}
+
+/// Push the host and port of a socket address onto the stack.
+fn push_address(interpreter: &mut dyn Interpreter, address: SocketAddr)
+{
+    interpreter.push(address.ip().to_string().to_value());
+    interpreter.push((address.port() as i64).to_value());
+}
+
+fn word_socket_unix_listen(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    match UnixListener::bind(&path)
+    {
+        Ok(listener) =>
+            {
+                let object = FileObject::UnixListener(listener, PathBuf::from(path));
+                let fd = interpreter.files_mut().add(object);
+
+                interpreter.push(fd.to_value());
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Failed to listen on socket {}: {}",
+                                                  path,
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+fn word_socket_tcp_connect(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let port = pop_port(interpreter)?;
+    let host = interpreter.pop_as_string()?;
+
+    match TcpStream::connect(( host.as_str(), port ))
+    {
+        Ok(stream) =>
+            {
+                let fd = interpreter.files_mut().add(FileObject::TcpStream(stream));
+                interpreter.push(fd.to_value());
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Failed to connect to {}:{}: {}",
+                                                  host,
+                                                  port,
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+fn word_socket_tcp_listen(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let port = pop_port(interpreter)?;
+    let host = interpreter.pop_as_string()?;
+
+    match TcpListener::bind(( host.as_str(), port ))
+    {
+        Ok(listener) =>
+            {
+                let fd = interpreter.files_mut().add(FileObject::TcpListener(listener));
+                interpreter.push(fd.to_value());
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Failed to listen on {}:{}: {}",
+                                                  host,
+                                                  port,
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+fn word_socket_accept(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let file = get_file(interpreter, fd)?;
+
+    let result = match &file
+        {
+            FileObject::TcpListener(listener) =>
+                listener.accept().map(|( stream, _ )| FileObject::TcpStream(stream)),
+
+            FileObject::UnixListener(listener, _) =>
+                listener.accept().map(|( stream, _ )| FileObject::Stream(stream)),
+
+            other =>
+                return script_error(interpreter, format!("Can not accept connections on {}.",
+                                                         other.description()))
+        };
+
+    match result
+    {
+        Ok(connection) =>
+            {
+                let new_fd = interpreter.files_mut().add(connection);
+                interpreter.push(new_fd.to_value());
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Failed to accept connection: {}", error))?;
+            }
+    }
+
+    Ok(())
+}
 
-                add_file(fd, file);
+fn word_socket_udp_bind(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let port = pop_port(interpreter)?;
+    let host = interpreter.pop_as_string()?;
+
+    match UdpSocket::bind(( host.as_str(), port ))
+    {
+        Ok(socket) =>
+            {
+                let fd = interpreter.files_mut().add(FileObject::UdpSocket(socket));
                 interpreter.push(fd.to_value());
             },
 
-        Err(error) =>
-            {
-                script_error(interpreter, format!("Could not open file {}: {}", path, error))?;
-            }
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Failed to bind to {}:{}: {}",
+                                                  host,
+                                                  port,
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+fn word_socket_udp_send_to(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let port = pop_port(interpreter)?;
+    let host = interpreter.pop_as_string()?;
+    let message = interpreter.pop_as_string()?;
+    let file = get_file(interpreter, fd)?;
+
+    let FileObject::UdpSocket(socket) = file
+        else
+        {
+            return script_error(interpreter, format!("Can not send a datagram on {}.",
+                                                     file.description()));
+        };
+
+    if let Err(error) = socket.send_to(message.as_bytes(), ( host.as_str(), port ))
+    {
+        script_error(interpreter, format!("Failed to send to {}:{}: {}", host, port, error))?;
+    }
+
+    Ok(())
+}
+
+fn word_socket_udp_recv_from(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let max_size = interpreter.pop_as_int()?;
+
+    if max_size <= 0
+    {
+        return script_error(interpreter, format!("Invalid datagram size {}.", max_size));
+    }
+
+    let file = get_file(interpreter, fd)?;
+
+    let FileObject::UdpSocket(socket) = file
+        else
+        {
+            return script_error(interpreter, format!("Can not receive a datagram on {}.",
+                                                     file.description()));
+        };
+
+    // No datagram is larger than the maximum, so there's no point in making room for more.
+    let mut buffer = vec![0; (max_size as usize).min(MAX_DATAGRAM_SIZE)];
+
+    match socket.recv_from(&mut buffer)
+    {
+        Ok(( size, address )) =>
+            {
+                interpreter.push(String::from_utf8_lossy(&buffer[..size]).to_string().to_value());
+                push_address(interpreter, address);
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Failed to receive datagram: {}", error))?;
+            }
+    }
+
+    Ok(())
+}
+
+fn word_socket_timeout(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let timeout_value = interpreter.pop()?;
+
+    if !timeout_value.is_none() && !timeout_value.is_numeric()
+    {
+        return script_error(interpreter, format!("Expected a timeout in milliseconds, found {}.",
+                                                 timeout_value));
+    }
+
+    let file = get_file(interpreter, fd)?;
+
+    // A timeout of none or zero means that reads and writes block forever.
+    let timeout = if timeout_value.is_none() || timeout_value.get_int_val() <= 0
+        {
+            None
+        }
+        else
+        {
+            Some(Duration::from_millis(timeout_value.get_int_val() as u64))
+        };
+
+    let result = match &file
+        {
+            FileObject::Stream(stream) =>
+                stream.set_read_timeout(timeout).and(stream.set_write_timeout(timeout)),
+
+            FileObject::TcpStream(stream) =>
+                stream.set_read_timeout(timeout).and(stream.set_write_timeout(timeout)),
+
+            FileObject::UdpSocket(socket) =>
+                socket.set_read_timeout(timeout).and(socket.set_write_timeout(timeout)),
+
+            other =>
+                return script_error(interpreter, format!("Can not set a timeout on {}.",
+                                                         other.description()))
+        };
+
+    if let Err(error) = result
+    {
+        script_error(interpreter, format!("Failed to set socket timeout: {}", error))?;
+    }
+
+    Ok(())
+}
+
+/// Shut down one or both directions of a connected socket.
+fn shutdown_socket(interpreter: &mut dyn Interpreter, how: Shutdown) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let file = get_file(interpreter, fd)?;
+
+    let result = match &file
+        {
+            FileObject::Stream(stream)    => stream.shutdown(how),
+            FileObject::TcpStream(stream) => stream.shutdown(how),
+
+            other =>
+                return script_error(interpreter, format!("Can not shutdown {}.",
+                                                         other.description()))
+        };
+
+    if let Err(error) = result
+    {
+        script_error(interpreter, format!("Failed to shutdown socket: {}", error))?;
+    }
+
+    Ok(())
+}
+
+fn word_socket_shutdown(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    shutdown_socket(interpreter, Shutdown::Both)
+}
+
+fn word_socket_shutdown_read(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    shutdown_socket(interpreter, Shutdown::Read)
+}
+
+fn word_socket_shutdown_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    shutdown_socket(interpreter, Shutdown::Write)
+}
+
+fn word_socket_local_address(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let file = get_file(interpreter, fd)?;
+
+    let result = match &file
+        {
+            FileObject::TcpStream(stream)     => stream.local_addr(),
+            FileObject::TcpListener(listener) => listener.local_addr(),
+            FileObject::UdpSocket(socket)     => socket.local_addr(),
+
+            other =>
+                return script_error(interpreter, format!("Can not get the address of {}.",
+                                                         other.description()))
+        };
+
+    match result
+    {
+        Ok(address) => push_address(interpreter, address),
+        Err(error)  => script_error(interpreter, format!("Failed to get socket address: {}",
+                                                         error))?
+    }
+
+    Ok(())
+}
+
+fn word_socket_peer_address(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let file = get_file(interpreter, fd)?;
+
+    let result = match &file
+        {
+            FileObject::TcpStream(stream) => stream.peer_addr(),
+
+            other =>
+                return script_error(interpreter, format!("Can not get the peer address of {}.",
+                                                         other.description()))
+        };
+
+    match result
+    {
+        Ok(address) => push_address(interpreter, address),
+        Err(error)  => script_error(interpreter, format!("Failed to get peer address: {}",
+                                                         error))?
+    }
+
+    Ok(())
+}
+
+/// Pop the fds to poll along with the interest flags for each of them.  The fds are either given as
+/// a hash table of fd -> interest flags, or as an array.  The items of the array are either a fd to
+/// poll for reading, or a `[ fd , interest ]` pair.
+fn pop_poll_interests(interpreter: &mut dyn Interpreter) -> error::Result<Vec<( i64, i64 )>>
+{
+    let value = interpreter.pop()?;
+    let mut entries = Vec::new();
+
+    if value.is_hash_map()
+    {
+        for ( fd_value, interest_value ) in value.as_hash_map(interpreter)?.borrow().iter()
+        {
+            entries.push(( fd_value.clone(), interest_value.clone() ));
+        }
+    }
+    else if value.is_vec()
+    {
+        for item in value.as_vec(interpreter)?.borrow().iter()
+        {
+            if !item.is_vec()
+            {
+                entries.push(( item.clone(), POLL_READ.to_value() ));
+                continue;
+            }
+
+            let pair: Vec<Value> = item.as_vec(interpreter)?.borrow().iter().cloned().collect();
+
+            match pair.as_slice()
+            {
+                [ fd_value, interest_value ] =>
+                    entries.push(( fd_value.clone(), interest_value.clone() )),
+
+                _ => script_error(interpreter, format!("Invalid poll entry {}.", item))?
+            }
+        }
+    }
+    else
+    {
+        script_error(interpreter, format!("Expected an array or hash table of fds, found {}.",
+                                          value))?;
+    }
+
+    let mut interests = Vec::with_capacity(entries.len());
+
+    for ( fd_value, interest_value ) in entries
+    {
+        if !fd_value.is_numeric() || !interest_value.is_numeric()
+        {
+            script_error(interpreter, format!("Invalid poll entry {} -> {}.",
+                                              fd_value,
+                                              interest_value))?;
+        }
+
+        interests.push(( fd_value.get_int_val(), interest_value.get_int_val() ));
+    }
+
+    Ok(interests)
+}
+
+fn word_io_poll(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let timeout_value = interpreter.pop()?;
+    let interests = pop_poll_interests(interpreter)?;
+
+    // A timeout of none or a negative value waits forever.
+    let mut timeout = if timeout_value.is_none()
+        {
+            -1
+        }
+        else if timeout_value.is_numeric()
+        {
+            timeout_value.get_int_val().clamp(-1, i32::MAX as i64) as libc::c_int
+        }
+        else
+        {
+            return script_error(interpreter,
+                                format!("Expected a poll timeout in milliseconds, found {}.",
+                                        timeout_value));
+        };
+
+    // Keep the file objects alive while their raw fds are being polled.
+    let mut objects = Vec::new();
+    let mut fds = Vec::new();
+    let mut poll_fds = Vec::new();
+    let mut buffered = Vec::new();
+
+    for ( fd, interest ) in interests
+    {
+        // The standard streams aren't in the file table, but they can still be polled.
+        let raw_fd = if (0..=2).contains(&fd)
+            {
+                fd as RawFd
+            }
+            else
+            {
+                let object = get_file(interpreter, fd)?;
+                let raw_fd = object.raw_fd();
+
+                objects.push(object);
+                raw_fd
+            };
+
+        let mut events = 0;
+
+        if interest & POLL_READ != 0
+        {
+            events |= libc::POLLIN;
+        }
+
+        if interest & POLL_WRITE != 0
+        {
+            events |= libc::POLLOUT;
+        }
+
+        // Data that's already been read ahead into the fd's buffer is ready to be read now, no
+        // matter what the os fd says.
+        let has_buffered_input =    interest & POLL_READ != 0
+                                 && interpreter.files().has_buffered_input(fd);
+
+        if has_buffered_input
+        {
+            timeout = 0;
+        }
+
+        fds.push(fd);
+        poll_fds.push(libc::pollfd { fd: raw_fd, events, revents: 0 });
+        buffered.push(has_buffered_input);
     }
 
-    Ok(())
-}
+    let start = Instant::now();
+    let mut remaining = timeout;
 
-fn word_file_create_temp_file(interpreter: &mut dyn Interpreter) -> error::Result<()>
-{
-    script_error_str(interpreter, "Create temp file unimplemented.")
-}
+    loop
+    {
+        let result = unsafe
+            {
+                libc::poll(poll_fds.as_mut_ptr(), poll_fds.len() as libc::nfds_t, remaining)
+            };
 
-fn word_file_close(interpreter: &mut dyn Interpreter) -> error::Result<()>
-{
-    let fd = interpreter.pop_as_int()?;
+        if result >= 0
+        {
+            break;
+        }
 
-    unregister_file(interpreter, fd)?;
+        let error = std::io::Error::last_os_error();
 
-    Ok(())
-}
+        if error.kind() != ErrorKind::Interrupted
+        {
+            script_error(interpreter, format!("Failed to poll fds: {}", error))?;
+        }
 
-fn word_file_delete(interpreter: &mut dyn Interpreter) -> error::Result<()>
-{
-    let path = interpreter.pop_as_string()?;
+        // If the poll was interrupted only wait for what's left of the timeout when trying again.
+        if timeout > 0
+        {
+            let elapsed = start.elapsed().as_millis().min(timeout as u128) as libc::c_int;
+            remaining = timeout - elapsed;
+        }
+    }
 
-    remove_file(&path)?;
+    // Report the fds that are ready along with what they're ready for.
+    let ready = ValueHash::new();
 
-    Ok(())
-}
+    for ( ( fd, poll_fd ), has_buffered_input ) in fds.iter().zip(poll_fds.iter()).zip(buffered)
+    {
+        let mut flags = 0;
 
-fn word_socket_connect(interpreter: &mut dyn Interpreter) -> error::Result<()>
-{
-    let path = interpreter.pop_as_string()?;
+        if poll_fd.revents & libc::POLLIN != 0 || has_buffered_input
+        {
+            flags |= POLL_READ;
+        }
 
-    match UnixStream::connect(&path)
-    {
-        Ok(stream) =>
-            {
-                let fd = generate_fd();
+        if poll_fd.revents & libc::POLLOUT != 0
+        {
+            flags |= POLL_WRITE;
+        }
 
-                add_stream(fd, stream);
-                interpreter.push(fd.to_value());
-            },
+        if poll_fd.revents & libc::POLLHUP != 0
+        {
+            flags |= POLL_HANGUP;
+        }
 
-        Err(error) =>
-            {
-                script_error(interpreter, format!("Failed to connect to socket {}: {}",
-                                                  path,
-                                                  error))?;
-            }
+        if poll_fd.revents & (libc::POLLERR | libc::POLLNVAL) != 0
+        {
+            flags |= POLL_ERROR;
+        }
+
+        if flags != 0
+        {
+            ready.borrow_mut().insert(fd.to_value(), flags.to_value());
+        }
     }
 
+    interpreter.push(ready.to_value());
     Ok(())
 }
 
@@ -220,9 +1127,10 @@ fn word_file_size_read(interpreter: &mut dyn Interpreter) -> error::Result<()>
                 interpreter.push(size.to_value());
             },
 
-        FileObject::Stream(_) =>
+        other =>
             {
-                script_error_str(interpreter, "Can not read size of a socket.")?;
+                script_error(interpreter, format!("Can not read size of {}.",
+                                                  other.description()))?;
             }
     }
 
@@ -262,9 +1170,10 @@ fn word_file_is_eof(interpreter: &mut dyn Interpreter) -> error::Result<()>
                 interpreter.push((current_pos == total_size).to_value());
             },
 
-        FileObject::Stream(_) =>
+        other =>
             {
-                script_error_str(interpreter, "Can not eof status of a socket.")?;
+                script_error(interpreter, format!("Can not eof status of {}.",
+                                                  other.description()))?;
             }
     }
 
@@ -273,86 +1182,216 @@ fn word_file_is_eof(interpreter: &mut dyn Interpreter) -> error::Result<()>
 
 fn word_file_read(interpreter: &mut dyn Interpreter) -> error::Result<()>
 {
-    script_error_str(interpreter, "Unimplemented.")
+    let fd = interpreter.pop_as_int()?;
+    let size = interpreter.pop_as_int()?;
+
+    if size < 0
+    {
+        return script_error(interpreter, format!("Can not read a negative size, {}.", size));
+    }
+
+    // Read until we have all of the requested bytes or we hit the end of the file.  The size comes
+    // from the script, so the buffer only grows as data actually arrives.
+    let mut bytes = Vec::new();
+
+    let result = read_file(interpreter, fd, |reader|
+        {
+            reader.take(size as u64).read_to_end(&mut bytes)
+        })?;
+
+    if let Err(error) = result
+    {
+        script_error(interpreter, format!("Could not read from file: {}.", error))?;
+    }
+
+    let buffer: BufferPtr = Rc::new(RefCell::new(ByteBuffer::from_bytes(bytes)));
+    interpreter.push(buffer.to_value());
+
+    Ok(())
+}
+
+fn word_file_buffer_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let mut file = get_file(interpreter, fd)?;
+
+    let Some(writer) = file.writer()
+        else
+        {
+            return script_error(interpreter, format!("Can not write to {}.", file.description()));
+        };
+
+    if let Err(error) = writer.write_all(buffer.borrow().bytes())
+    {
+        script_error(interpreter, format!("Could not write to file: {}.", error))?;
+    }
+
+    Ok(())
 }
 
-fn word_file_read_character(interpreter: &mut dyn Interpreter) -> error::Result<()>
+fn word_file_mmap(interpreter: &mut dyn Interpreter) -> error::Result<()>
 {
-    fn read<T>(interpreter: &mut dyn Interpreter, reader: &mut BufReader<T>) -> error::Result<()>
-        where T: Read
+    let flags = interpreter.pop_as_int()?;
+    let path = interpreter.pop_as_string()?;
+
+    if flags & 0b0001 == 0
     {
-        let mut buffer = [0; 1];
+        script_error(interpreter, format!("Mapping {} requires read access.", path))?;
+    }
 
-        match reader.read(&mut buffer)
+    let file = match flags_to_options(flags).open(&path)
         {
-            Ok(0) =>
-                {
-                    interpreter.push("".to_string().to_value());
-                },
+            Ok(file)   => file,
+            Err(error) => return script_error(interpreter, format!("Could not open file {}: {}",
+                                                                   path,
+                                                                   error))
+        };
+
+    // The mapping stays valid after the file is closed, so the file doesn't need to be kept in the
+    // file table.
+    match MappedBuffer::new_ptr(&file, flags & 0b0010 != 0)
+    {
+        Ok(buffer) => interpreter.push(buffer.to_value()),
+        Err(error) => script_error(interpreter, format!("Could not map file {}: {}",
+                                                        path,
+                                                        error))?
+    }
 
-            Ok(_) =>
-                {
-                    interpreter.push(buffer[0].to_string().to_value());
-                },
+    Ok(())
+}
 
-            Err(error) =>
-                {
-                    return script_error(interpreter,
-                                        format!("Could not read from file: {}.", error))
-                }
-        }
+fn word_file_seek(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let whence = interpreter.pop_as_int()?;
+    let offset = interpreter.pop_as_int()?;
 
-        Ok(())
+    let seek_from = match whence
+        {
+            SEEK_START if offset >= 0 => SeekFrom::Start(offset as u64),
+            SEEK_START                => return script_error(interpreter,
+                                                      format!("Can not seek to offset {}.", offset)),
+            SEEK_CURRENT              => SeekFrom::Current(offset),
+            SEEK_END                  => SeekFrom::End(offset),
+            _                         => return script_error(interpreter,
+                                                      format!("Invalid seek origin {}.", whence))
+        };
+
+    let file = get_file(interpreter, fd)?;
+
+    let FileObject::File(mut file) = file
+        else
+        {
+            return script_error(interpreter, format!("Can not seek in {}.", file.description()));
+        };
+
+    match file.seek(seek_from)
+    {
+        Ok(position) => interpreter.push(position.to_value()),
+        Err(error)   => script_error(interpreter, format!("Could not seek in file: {}.", error))?
     }
 
+    Ok(())
+}
+
+fn word_file_tell(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
     let fd = interpreter.pop_as_int()?;
     let file = get_file(interpreter, fd)?;
 
-    match file
+    let FileObject::File(mut file) = file
+        else
+        {
+            return script_error(interpreter, format!("Can not get the position of {}.",
+                                                     file.description()));
+        };
+
+    match file.stream_position()
     {
-        FileObject::File(file)     => read(interpreter, &mut BufReader::new(file)),
-        FileObject::Stream(stream) => read(interpreter, &mut BufReader::new(stream)),
+        Ok(position) => interpreter.push(position.to_value()),
+        Err(error)   => script_error(interpreter, format!("Could not get file position: {}.",
+                                                          error))?
     }
+
+    Ok(())
 }
 
-fn word_file_read_string(interpreter: &mut dyn Interpreter) -> error::Result<()>
+fn word_file_truncate(interpreter: &mut dyn Interpreter) -> error::Result<()>
 {
-    fn read<T>(interpreter: &mut dyn Interpreter,
-               reader: &mut BufReader<T>) -> error::Result<()>
-        where T: Read
-    {
-        let mut string = String::new();
+    let fd = interpreter.pop_as_int()?;
+    let size = interpreter.pop_as_int()?;
+    let file = get_file(interpreter, fd)?;
 
-        match reader.read_to_string(&mut string)
+    let FileObject::File(file) = file
+        else
         {
-            Ok(0) =>
-                {
-                    interpreter.push("".to_string().to_value());
-                },
+            return script_error(interpreter, format!("Can not truncate {}.", file.description()));
+        };
 
-            Ok(_) =>
-                {
-                    interpreter.push(string.to_value());
-                },
+    if size < 0
+    {
+        script_error(interpreter, format!("Can not truncate a file to size {}.", size))?;
+    }
 
-            Err(error) =>
-                {
-                    return script_error(interpreter,
-                                        format!("Could not read from file: {}.", error))
-                }
+    if let Err(error) = file.set_len(size as u64)
+    {
+        script_error(interpreter, format!("Could not truncate file: {}.", error))?;
+    }
+
+    Ok(())
+}
+
+fn word_file_read_character(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    fn read<T>(reader: &mut T) -> std::io::Result<String>
+        where T: Read + ?Sized
+    {
+        let mut buffer = [0; 4];
+
+        if reader.read(&mut buffer[..1])? == 0
+        {
+            return Ok(String::new());
         }
 
-        Ok(())
+        // Read the rest of the character's UTF-8 sequence based on it's leading byte.
+        let size = match buffer[0]
+            {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _           => 1
+            };
+
+        reader.read_exact(&mut buffer[1..size])?;
+
+        Ok(String::from_utf8_lossy(&buffer[..size]).to_string())
     }
 
     let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd)?;
 
-    match file
+    match read_file(interpreter, fd, read)?
+    {
+        Ok(character) => interpreter.push(character.to_value()),
+        Err(error)    => script_error(interpreter, format!("Could not read from file: {}.",
+                                                           error))?
+    }
+
+    Ok(())
+}
+
+fn word_file_read_string(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let fd = interpreter.pop_as_int()?;
+    let mut string = String::new();
+
+    match read_file(interpreter, fd, |reader| reader.read_to_string(&mut string))?
     {
-        FileObject::File(file)     => read(interpreter, &mut BufReader::new(file)),
-        FileObject::Stream(stream) => read(interpreter, &mut BufReader::new(stream)),
+        Ok(_)      => interpreter.push(string.to_value()),
+        Err(error) => script_error(interpreter, format!("Could not read from file: {}.", error))?
     }
+
+    Ok(())
 }
 
 fn word_file_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
@@ -360,21 +1399,22 @@ fn word_file_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
     fn write<T>(interpreter: &mut dyn Interpreter,
                string: String,
                writer: &mut BufWriter<T>) -> error::Result<()>
-        where T: Write
+        where T: Write + ?Sized
     {
         let bytes = string.into_bytes();
 
-        match writer.write(bytes.as_slice())
+        // Sockets and pipes can accept less than the full write, so keep writing until it's all
+        // sent.
+        match writer.write_all(bytes.as_slice()).and_then(|_| writer.flush())
         {
-            // TODO: Handle partial writes.
-            Ok(_) =>
+            Ok(()) =>
                 {
                     Ok(())
                 },
 
             Err(error) =>
                 {
-                    script_error(interpreter, format!("Could not read from file: {}.", error))
+                    script_error(interpreter, format!("Could not write to file: {}.", error))
                 }
         }
     }
@@ -382,53 +1422,35 @@ fn word_file_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
     // TODO: Implement ByteBuffer and better string conversion.
     let fd = interpreter.pop_as_int()?;
     let string = interpreter.pop_as_string()?;
-    let file = get_file(interpreter, fd)?;
+    let mut file = get_file(interpreter, fd)?;
 
-    match file
+    match file.writer()
     {
-        FileObject::File(file)     => write(interpreter, string, &mut BufWriter::new(file)),
-        FileObject::Stream(stream) => write(interpreter, string, &mut BufWriter::new(stream)),
+        Some(writer) => write(interpreter, string, &mut BufWriter::new(writer)),
+        None         => script_error(interpreter, format!("Can not write to {}.",
+                                                          file.description()))
     }
 }
 
 fn word_file_line_read(interpreter: &mut dyn Interpreter) -> error::Result<()>
 {
-    fn read<T>(interpreter: &mut dyn Interpreter, buffer: &mut BufReader<T>) -> error::Result<()>
-        where T: Read
-    {
-        let mut line = String::new();
-
-        match buffer.read_line(&mut line)
-        {
-            Ok(0) =>
-                {
-                    interpreter.push("".to_string().to_value());
-                },
+    let fd = interpreter.pop_as_int()?;
+    let mut bytes = Vec::new();
 
-            Ok(_) =>
-                {
-                    let line = line.trim_end_matches(&['\n', '\r'][..]).to_string();
-                    interpreter.push(line.to_value());
-                },
+    match read_file(interpreter, fd, |reader| reader.read_until(b'\n', &mut bytes))?
+    {
+        Ok(_) =>
+            {
+                let line = String::from_utf8_lossy(&bytes);
+                let line = line.trim_end_matches(&['\n', '\r'][..]).to_string();
 
-            Err(error) =>
-                {
-                    return script_error(interpreter,
-                                        format!("Could not read from file: {}.", error))
-                }
-        }
+                interpreter.push(line.to_value());
+            },
 
-        Ok(())
+        Err(error) => script_error(interpreter, format!("Could not read from file: {}.", error))?
     }
 
-    let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd)?;
-
-    match file
-    {
-        FileObject::File(file)     => read(interpreter, &mut BufReader::new(file)),
-        FileObject::Stream(stream) => read(interpreter, &mut BufReader::new(stream)),
-    }
+    Ok(())
 }
 
 fn word_file_line_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
@@ -436,21 +1458,21 @@ fn word_file_line_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
     fn write<T>(interpreter: &mut dyn Interpreter,
                 string: String,
                 writer: &mut BufWriter<T>) -> error::Result<()>
-        where T: Write
+        where T: Write + ?Sized
     {
         let bytes = (string + "\n").into_bytes();
 
-        match writer.write(bytes.as_slice())
+        // Make sure the whole line is written, including the newline.
+        match writer.write_all(bytes.as_slice()).and_then(|_| writer.flush())
         {
-            // TODO: Handle partial writes.
-            Ok(_) =>
+            Ok(()) =>
                 {
                     Ok(())
                 },
 
             Err(error) =>
                 {
-                    script_error(interpreter, format!("Could not read from file: {}.", error))
+                    script_error(interpreter, format!("Could not write to file: {}.", error))
                 }
         }
     }
@@ -458,12 +1480,13 @@ fn word_file_line_write(interpreter: &mut dyn Interpreter) -> error::Result<()>
     // TODO: Implement better string conversion.
     let fd = interpreter.pop_as_int()?;
     let string = interpreter.pop_as_string()?;
-    let file = get_file(interpreter, fd)?;
+    let mut file = get_file(interpreter, fd)?;
 
-    match file
+    match file.writer()
     {
-        FileObject::File(file)     => write(interpreter, string, &mut BufWriter::new(file)),
-        FileObject::Stream(stream) => write(interpreter, string, &mut BufWriter::new(stream)),
+        Some(writer) => write(interpreter, string, &mut BufWriter::new(writer)),
+        None         => script_error(interpreter, format!("Can not write to {}.",
+                                                          file.description()))
     }
 }
 
@@ -487,6 +1510,14 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter)
         "Take a fd and close it.",
         "fd -- ");
 
+    add_native_word!(interpreter, "file.scoped", word_file_scoped,
+        "Close a fd automatically when the current word or context ends.",
+        "fd -- fd");
+
+    add_native_word!(interpreter, "file.with", word_file_with,
+        "Open a file, run a word with it's fd, and then close it even if the word throws.",
+        "path flags word_index -- ");
+
     add_native_word!(interpreter, "file.delete", word_file_delete,
         "Delete the specified file.",
         "file_path -- ");
@@ -497,6 +1528,64 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter)
         "path -- fd");
 
 
+    add_native_word!(interpreter, "socket.unix.listen", word_socket_unix_listen,
+        "Listen for connections on a Unix domain socket.  The socket file is removed on close.",
+        "path -- fd");
+
+    add_native_word!(interpreter, "socket.tcp.connect", word_socket_tcp_connect,
+        "Connect to a TCP server and return a fd for the connection.",
+        "host port -- fd");
+
+    add_native_word!(interpreter, "socket.tcp.listen", word_socket_tcp_listen,
+        "Listen for TCP connections on the given address.  Use port 0 for any free port.",
+        "host port -- fd");
+
+    add_native_word!(interpreter, "socket.accept", word_socket_accept,
+        "Wait for a connection on a listening socket and return a fd for it.",
+        "listener_fd -- fd");
+
+    add_native_word!(interpreter, "socket.udp.bind", word_socket_udp_bind,
+        "Bind a UDP socket to the given address.  Use port 0 for any free port.",
+        "host port -- fd");
+
+    add_native_word!(interpreter, "socket.udp.send-to", word_socket_udp_send_to,
+        "Send a string as a datagram to the given address.",
+        "message host port fd -- ");
+
+    add_native_word!(interpreter, "socket.udp.recv-from", word_socket_udp_recv_from,
+        "Wait for a datagram of up to max_size bytes and return it with the sender's address.",
+        "max_size fd -- message host port");
+
+    add_native_word!(interpreter, "socket.timeout!", word_socket_timeout,
+        "Set the read and write timeout of a socket in milliseconds, none or 0 to block forever.",
+        "milliseconds fd -- ");
+
+    add_native_word!(interpreter, "socket.shutdown", word_socket_shutdown,
+        "Shutdown both directions of a connected socket.",
+        "fd -- ");
+
+    add_native_word!(interpreter, "socket.shutdown.read", word_socket_shutdown_read,
+        "Shutdown the reading half of a connected socket.",
+        "fd -- ");
+
+    add_native_word!(interpreter, "socket.shutdown.write", word_socket_shutdown_write,
+        "Shutdown the writing half of a connected socket.",
+        "fd -- ");
+
+    add_native_word!(interpreter, "socket.local-address@", word_socket_local_address,
+        "Get the local host and port a socket is bound to.",
+        "fd -- host port");
+
+    add_native_word!(interpreter, "socket.peer-address@", word_socket_peer_address,
+        "Get the host and port of the other end of a connection.",
+        "fd -- host port");
+
+
+    add_native_word!(interpreter, "io.poll", word_io_poll,
+        "Wait for an array of fds, or a hash of fd -> interest flags, and return the ready fds.",
+        "interests timeout_ms -- ready");
+
+
     add_native_word!(interpreter, "file.size@", word_file_size_read,
         "Return the size of a file represented by a fd.",
         "fd -- size");
@@ -516,11 +1605,15 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter)
 
 
     add_native_word!(interpreter, "file.@", word_file_read,
-        "Read from a given file.  (Unimplemented.)",
-        " -- ");
+        "Read up to size bytes from a file into a new byte buffer.",
+        "size fd -- buffer");
+
+    add_native_word!(interpreter, "file.buffer!", word_file_buffer_write,
+        "Write the contents of a byte buffer to a file.",
+        "buffer fd -- ");
 
     add_native_word!(interpreter, "file.char@", word_file_read_character,
-        "Read a character from a given file.",
+        "Read a UTF-8 character from a given file.",
         "fd -- character");
 
     add_native_word!(interpreter, "file.string@", word_file_read_string,
@@ -532,6 +1625,23 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter)
         "value fd -- ");
 
 
+    add_native_word!(interpreter, "file.mmap", word_file_mmap,
+        "Map a file into memory as a fixed size byte buffer.  Use file.r/o or file.r/w.",
+        "path flags -- buffer");
+
+    add_native_word!(interpreter, "file.seek", word_file_seek,
+        "Move the file position by offset from the given origin and return the new position.",
+        "offset origin fd -- position");
+
+    add_native_word!(interpreter, "file.tell", word_file_tell,
+        "Get the current position within a file.",
+        "fd -- position");
+
+    add_native_word!(interpreter, "file.truncate", word_file_truncate,
+        "Truncate or extend a file to the given size.",
+        "size fd -- ");
+
+
     add_native_word!(interpreter, "file.line@", word_file_line_read,
         "Read a full line from a file.",
         "fd -- string");
@@ -567,4 +1677,69 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter)
         },
         "Constant for opening a file for both reading and writing.",
         " -- flag");
+
+
+    add_native_word!(interpreter, "file.seek.start",
+        |interpreter|
+        {
+            interpreter.push(SEEK_START.to_value());
+            Ok(())
+        },
+        "Seek origin for offsets from the start of a file.",
+        " -- origin");
+
+    add_native_word!(interpreter, "file.seek.current",
+        |interpreter|
+        {
+            interpreter.push(SEEK_CURRENT.to_value());
+            Ok(())
+        },
+        "Seek origin for offsets from the current position in a file.",
+        " -- origin");
+
+    add_native_word!(interpreter, "file.seek.end",
+        |interpreter|
+        {
+            interpreter.push(SEEK_END.to_value());
+            Ok(())
+        },
+        "Seek origin for offsets from the end of a file.",
+        " -- origin");
+
+
+    add_native_word!(interpreter, "io.poll.read",
+        |interpreter|
+        {
+            interpreter.push(POLL_READ.to_value());
+            Ok(())
+        },
+        "Flag for polling if a fd can be read from.",
+        " -- flag");
+
+    add_native_word!(interpreter, "io.poll.write",
+        |interpreter|
+        {
+            interpreter.push(POLL_WRITE.to_value());
+            Ok(())
+        },
+        "Flag for polling if a fd can be written to.",
+        " -- flag");
+
+    add_native_word!(interpreter, "io.poll.hangup",
+        |interpreter|
+        {
+            interpreter.push(POLL_HANGUP.to_value());
+            Ok(())
+        },
+        "Flag reported when the other end of a fd has hung up.",
+        " -- flag");
+
+    add_native_word!(interpreter, "io.poll.error",
+        |interpreter|
+        {
+            interpreter.push(POLL_ERROR.to_value());
+            Ok(())
+        },
+        "Flag reported when a fd is in an error state.",
+        " -- flag");
 }
@@ -0,0 +1,982 @@
+
+use std::rc::Rc;
+use crate::{ add_native_word,
+             runtime::{ data_structures::{ data_object::DataObject,
+                                           value::{ ToValue, Value },
+                                           value_hash::ValueHash,
+                                           value_vec::ValueVec },
+                        error::{ self,
+                                 script_error },
+                        interpreter::Interpreter } };
+
+
+
+/// The deepest nesting of arrays and objects that the parser will accept, or the writer will write.
+const MAX_JSON_DEPTH: usize = 512;
+
+/// The most spaces per level that pretty printed JSON can be indented by.
+const MAX_JSON_INDENT: i64 = 16;
+
+/// The object key used by typed JSON to record the name of a structure's definition.
+const JSON_TYPE_KEY: &str = "$type";
+
+
+
+/// Parser for JSON text that produces interpreter values.  Objects become hash tables, arrays
+/// become arrays, and null becomes none.  Errors are reported with the line and column of the
+/// offending text.
+struct JsonParser<'a>
+{
+    text: &'a str,
+    bytes: &'a [u8],
+    position: usize,
+    depth: usize
+}
+
+
+impl<'a> JsonParser<'a>
+{
+    /// Create a new parser at the start of the given text.
+    fn new(text: &'a str) -> JsonParser<'a>
+    {
+        JsonParser
+            {
+                text,
+                bytes: text.as_bytes(),
+                position: 0,
+                depth: 0
+            }
+    }
+
+    /// Parse the whole text as a single JSON value, allowing for surrounding whitespace.
+    fn parse_document(&mut self) -> Result<Value, String>
+    {
+        let value = self.parse_value()?;
+
+        self.skip_whitespace();
+
+        if self.position < self.bytes.len()
+        {
+            return self.error("Unexpected text after the JSON value.");
+        }
+
+        Ok(value)
+    }
+
+    /// Generate an error message that includes the line and column of the current position.
+    fn error<T>(&self, message: &str) -> Result<T, String>
+    {
+        let before = &self.text[..self.position];
+        let line = before.matches('\n').count() + 1;
+        let column = match before.rfind('\n')
+            {
+                Some(index) => before[index + 1..].chars().count() + 1,
+                None        => before.chars().count() + 1
+            };
+
+        Err(format!("JSON error at line {}, column {}: {}", line, column, message))
+    }
+
+    /// Look at the next byte without consuming it.
+    fn peek(&self) -> Option<u8>
+    {
+        self.bytes.get(self.position).copied()
+    }
+
+    /// Move past any JSON whitespace.
+    fn skip_whitespace(&mut self)
+    {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek()
+        {
+            self.position += 1;
+        }
+    }
+
+    /// Consume the expected byte, or report an error naming what was expected.
+    fn expect(&mut self, expected: u8, description: &str) -> Result<(), String>
+    {
+        if self.peek() != Some(expected)
+        {
+            return self.unexpected(description);
+        }
+
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Report an unexpected character or end of text at the current position.
+    fn unexpected<T>(&self, expected: &str) -> Result<T, String>
+    {
+        match self.text[self.position..].chars().next()
+        {
+            Some(found) => self.error(&format!("Expected {}, found '{}'.", expected, found)),
+            None        => self.error(&format!("Expected {}, found the end of the text.",
+                                               expected))
+        }
+    }
+
+    /// Parse any JSON value at the current position.
+    fn parse_value(&mut self) -> Result<Value, String>
+    {
+        self.skip_whitespace();
+
+        match self.peek()
+        {
+            Some(b'{')               => self.parse_object(),
+            Some(b'[')               => self.parse_array(),
+            Some(b'"')               => Ok(self.parse_string()?.to_value()),
+            Some(b't')               => self.parse_literal("true", true.to_value()),
+            Some(b'f')               => self.parse_literal("false", false.to_value()),
+            Some(b'n')               => self.parse_literal("null", Value::None),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _                        => self.unexpected("a JSON value")
+        }
+    }
+
+    /// Parse one of the fixed literals, true, false, or null.
+    fn parse_literal(&mut self, literal: &str, value: Value) -> Result<Value, String>
+    {
+        if !self.text[self.position..].starts_with(literal)
+        {
+            return self.error(&format!("Invalid literal, expected {}.", literal));
+        }
+
+        self.position += literal.len();
+        Ok(value)
+    }
+
+    /// Move past a run of decimal digits, returning how many were found.
+    fn skip_digits(&mut self) -> usize
+    {
+        let start = self.position;
+
+        while let Some(b'0'..=b'9') = self.peek()
+        {
+            self.position += 1;
+        }
+
+        self.position - start
+    }
+
+    /// Parse a number.  Numbers without a fraction or exponent become integers if they fit,
+    /// everything else becomes a float.
+    fn parse_number(&mut self) -> Result<Value, String>
+    {
+        let start = self.position;
+        let mut is_float = false;
+
+        if self.peek() == Some(b'-')
+        {
+            self.position += 1;
+        }
+
+        if self.peek() == Some(b'0')
+        {
+            self.position += 1;
+        }
+        else if self.skip_digits() == 0
+        {
+            return self.unexpected("a digit");
+        }
+
+        if self.peek() == Some(b'.')
+        {
+            is_float = true;
+            self.position += 1;
+
+            if self.skip_digits() == 0
+            {
+                return self.unexpected("a digit after the decimal point");
+            }
+        }
+
+        if let Some(b'e' | b'E') = self.peek()
+        {
+            is_float = true;
+            self.position += 1;
+
+            if let Some(b'+' | b'-') = self.peek()
+            {
+                self.position += 1;
+            }
+
+            if self.skip_digits() == 0
+            {
+                return self.unexpected("a digit in the exponent");
+            }
+        }
+
+        let number_text = &self.text[start..self.position];
+
+        let integer = if is_float { None } else { number_text.parse::<i64>().ok() };
+
+        if let Some(value) = integer
+        {
+            return Ok(value.to_value());
+        }
+
+        match number_text.parse::<f64>()
+        {
+            Ok(value) => Ok(value.to_value()),
+            Err(_)    => self.error(&format!("Invalid number {}.", number_text))
+        }
+    }
+
+    /// Parse the four hex digits of a \u escape.
+    fn parse_hex4(&mut self) -> Result<u32, String>
+    {
+        let digits = self.text.get(self.position..self.position + 4).unwrap_or("");
+
+        if digits.len() != 4 || !digits.bytes().all(|digit| digit.is_ascii_hexdigit())
+        {
+            return self.unexpected("four hex digits");
+        }
+
+        match u32::from_str_radix(digits, 16)
+        {
+            Ok(value) =>
+                {
+                    self.position += 4;
+                    Ok(value)
+                },
+            _ => self.unexpected("four hex digits")
+        }
+    }
+
+    /// Parse a \u escape, including the second half of a UTF-16 surrogate pair.
+    fn parse_unicode_escape(&mut self) -> Result<char, String>
+    {
+        let escape_start = self.position - 2;
+        let high = self.parse_hex4()?;
+
+        let code_point = match high
+            {
+                0xd800..=0xdbff =>
+                    {
+                        if !self.text[self.position..].starts_with("\\u")
+                        {
+                            self.position = escape_start;
+                            return self.error("Unpaired UTF-16 surrogate in \\u escape.");
+                        }
+
+                        self.position += 2;
+
+                        let low = self.parse_hex4()?;
+
+                        if !(0xdc00..=0xdfff).contains(&low)
+                        {
+                            self.position = escape_start;
+                            return self.error("Unpaired UTF-16 surrogate in \\u escape.");
+                        }
+
+                        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                    },
+
+                0xdc00..=0xdfff =>
+                    {
+                        self.position = escape_start;
+                        return self.error("Unpaired UTF-16 surrogate in \\u escape.");
+                    },
+
+                _ => high
+            };
+
+        match char::from_u32(code_point)
+        {
+            Some(character) => Ok(character),
+            None            =>
+                {
+                    self.position = escape_start;
+                    self.error("Invalid code point in \\u escape.")
+                }
+        }
+    }
+
+    /// Parse a quoted string, processing any escape sequences.
+    fn parse_string(&mut self) -> Result<String, String>
+    {
+        self.expect(b'"', "a string")?;
+
+        let mut result = String::new();
+        let mut run_start = self.position;
+
+        loop
+        {
+            match self.peek()
+            {
+                Some(b'"') =>
+                    {
+                        result.push_str(&self.text[run_start..self.position]);
+                        self.position += 1;
+
+                        return Ok(result);
+                    },
+
+                Some(b'\\') =>
+                    {
+                        result.push_str(&self.text[run_start..self.position]);
+                        self.position += 1;
+
+                        let escape = self.peek();
+                        self.position += 1;
+
+                        match escape
+                        {
+                            Some(b'"')  => result.push('"'),
+                            Some(b'\\') => result.push('\\'),
+                            Some(b'/')  => result.push('/'),
+                            Some(b'b')  => result.push('\u{8}'),
+                            Some(b'f')  => result.push('\u{c}'),
+                            Some(b'n')  => result.push('\n'),
+                            Some(b'r')  => result.push('\r'),
+                            Some(b't')  => result.push('\t'),
+                            Some(b'u')  => result.push(self.parse_unicode_escape()?),
+                            _           =>
+                                {
+                                    self.position -= 1;
+                                    return self.unexpected("a valid escape character");
+                                }
+                        }
+
+                        run_start = self.position;
+                    },
+
+                Some(0x00..=0x1f) => return self.error("Unescaped control character in string."),
+                Some(_)           => self.position += 1,
+                None              => return self.unexpected("the end of the string")
+            }
+        }
+    }
+
+    /// Track the nesting depth of arrays and objects so that deeply nested text can't overflow
+    /// the stack.
+    fn enter_nested(&mut self) -> Result<(), String>
+    {
+        self.depth += 1;
+
+        if self.depth > MAX_JSON_DEPTH
+        {
+            return self.error(&format!("Nesting is deeper than {} levels.", MAX_JSON_DEPTH));
+        }
+
+        Ok(())
+    }
+
+    /// Parse an array of values.
+    fn parse_array(&mut self) -> Result<Value, String>
+    {
+        self.expect(b'[', "an array")?;
+        self.enter_nested()?;
+
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+
+        if self.peek() == Some(b']')
+        {
+            self.position += 1;
+        }
+        else
+        {
+            loop
+            {
+                values.push(self.parse_value()?);
+                self.skip_whitespace();
+
+                match self.peek()
+                {
+                    Some(b',') => self.position += 1,
+                    Some(b']') =>
+                        {
+                            self.position += 1;
+                            break;
+                        },
+                    _          => return self.unexpected("',' or ']'")
+                }
+            }
+        }
+
+        self.depth -= 1;
+        Ok(ValueVec::from_vec(values).to_value())
+    }
+
+    /// Parse an object into a hash table with string keys.
+    fn parse_object(&mut self) -> Result<Value, String>
+    {
+        self.expect(b'{', "an object")?;
+        self.enter_nested()?;
+
+        let hash = ValueHash::new();
+
+        self.skip_whitespace();
+
+        if self.peek() == Some(b'}')
+        {
+            self.position += 1;
+        }
+        else
+        {
+            loop
+            {
+                self.skip_whitespace();
+
+                let key = self.parse_string()?;
+
+                self.skip_whitespace();
+                self.expect(b':', "':'")?;
+
+                let value = self.parse_value()?;
+
+                hash.borrow_mut().insert(key.to_value(), value);
+                self.skip_whitespace();
+
+                match self.peek()
+                {
+                    Some(b',') => self.position += 1,
+                    Some(b'}') =>
+                        {
+                            self.position += 1;
+                            break;
+                        },
+                    _          => return self.unexpected("',' or '}'")
+                }
+            }
+        }
+
+        self.depth -= 1;
+        Ok(hash.to_value())
+    }
+}
+
+
+
+/// Writer that converts interpreter values into JSON text, either compact or pretty printed with
+/// the given indentation.  In typed mode structures are tagged with the name of their definition
+/// so that they can be rebuilt when the text is parsed.
+struct JsonWriter
+{
+    indent: Option<usize>,
+    typed: bool,
+    depth: usize,
+    containers: Vec<usize>,
+    output: String
+}
+
+
+impl JsonWriter
+{
+    /// Create a new writer.  If an indent is given the output is pretty printed.
+    fn new(indent: Option<usize>, typed: bool) -> JsonWriter
+    {
+        JsonWriter
+            {
+                indent,
+                typed,
+                depth: 0,
+                containers: Vec::new(),
+                output: String::new()
+            }
+    }
+
+    /// Start a new line at the current depth when pretty printing.
+    fn new_line(&mut self)
+    {
+        if let Some(indent) = self.indent
+        {
+            self.output.push('\n');
+            self.output.extend(std::iter::repeat_n(' ', indent * self.depth));
+        }
+    }
+
+    /// Write a quoted and escaped string.
+    fn write_string(&mut self, text: &str)
+    {
+        self.output.push('"');
+
+        for character in text.chars()
+        {
+            match character
+            {
+                '"'  => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                '\u{0}'..='\u{1f}' => self.output.push_str(&format!("\\u{:04x}",
+                                                                    character as u32)),
+                _    => self.output.push(character)
+            }
+        }
+
+        self.output.push('"');
+    }
+
+    /// Remember that we're inside of the container at the given address, failing if we already
+    /// are.  Otherwise a container that holds itself would be written forever.  Like the parser,
+    /// the writer also limits how deeply containers can be nested.
+    fn enter_container(&mut self, address: usize) -> Result<(), String>
+    {
+        if self.containers.contains(&address)
+        {
+            return Err("Can not convert a value that contains itself to JSON.".to_string());
+        }
+
+        if self.containers.len() >= MAX_JSON_DEPTH
+        {
+            return Err(format!("Nesting is deeper than {} levels.", MAX_JSON_DEPTH));
+        }
+
+        self.containers.push(address);
+        Ok(())
+    }
+
+    /// Write a series of entries surrounded by the open and close characters.  Each entry is
+    /// written with the given function.
+    fn write_entries<T>(&mut self,
+                        open: char,
+                        close: char,
+                        entries: &[T],
+                        write_entry: fn(&mut JsonWriter, &T) -> Result<(), String>)
+                        -> Result<(), String>
+    {
+        self.output.push(open);
+
+        if !entries.is_empty()
+        {
+            self.depth += 1;
+
+            for ( index, entry ) in entries.iter().enumerate()
+            {
+                if index > 0
+                {
+                    self.output.push(',');
+                }
+
+                self.new_line();
+                write_entry(self, entry)?;
+            }
+
+            self.depth -= 1;
+            self.new_line();
+        }
+
+        self.output.push(close);
+        Ok(())
+    }
+
+    /// Write a key and value pair of an object.
+    fn write_member(&mut self, member: &( String, Value )) -> Result<(), String>
+    {
+        self.write_string(&member.0);
+        self.output.push(':');
+
+        if self.indent.is_some()
+        {
+            self.output.push(' ');
+        }
+
+        self.write_value(&member.1)
+    }
+
+    /// Write any value that can be represented in JSON.
+    fn write_value(&mut self, value: &Value) -> Result<(), String>
+    {
+        match value
+        {
+            Value::None          => self.output.push_str("null"),
+            Value::Int(value)    => self.output.push_str(&value.to_string()),
+            Value::Bool(value)   => self.output.push_str(&value.to_string()),
+            Value::String(value) => self.write_string(value),
+
+            Value::Float(value) =>
+                {
+                    if !value.is_finite()
+                    {
+                        return Err(format!("Can not convert the float {} to JSON.", value));
+                    }
+
+                    self.output.push_str(&format!("{:?}", value));
+                },
+
+            Value::Vec(vec_ptr) =>
+                {
+                    self.enter_container(Rc::as_ptr(vec_ptr) as *const () as usize)?;
+
+                    let values: Vec<Value> = vec_ptr.borrow().iter().cloned().collect();
+                    self.write_entries('[', ']', &values, JsonWriter::write_value)?;
+
+                    self.containers.pop();
+                },
+
+            Value::HashMap(hash_ptr) =>
+                {
+                    self.enter_container(Rc::as_ptr(hash_ptr) as *const () as usize)?;
+
+                    let mut members = Vec::with_capacity(hash_ptr.borrow().len());
+
+                    for ( key, value ) in hash_ptr.borrow().iter()
+                    {
+                        let key = match key
+                            {
+                                Value::String(key) if self.typed && key == JSON_TYPE_KEY =>
+                                    return Err(format!("The key {} is reserved in typed JSON.",
+                                                       JSON_TYPE_KEY)),
+                                Value::String(key) => key.clone(),
+                                Value::Int(_) | Value::Float(_) | Value::Bool(_) => key.to_string(),
+                                _ => return Err(format!("Can not use {} as a JSON object key.",
+                                                        key))
+                            };
+
+                        members.push(( key, value.clone() ));
+                    }
+
+                    // Hash tables are unordered, so sort the keys to keep the output stable.
+                    members.sort_by(|a, b| a.0.cmp(&b.0));
+                    self.write_entries('{', '}', &members, JsonWriter::write_member)?;
+
+                    self.containers.pop();
+                },
+
+            Value::DataObject(data_ptr) =>
+                {
+                    self.enter_container(Rc::as_ptr(data_ptr) as *const () as usize)?;
+
+                    let mut members: Vec<( String, Value )> = Vec::new();
+
+                    {
+                        let data = data_ptr.borrow();
+                        let definition = data.definition_ptr.borrow();
+
+                        if self.typed
+                        {
+                            members.push(( JSON_TYPE_KEY.to_string(),
+                                           definition.name().to_value() ));
+                        }
+
+                        members.extend(definition.field_names().iter()
+                                                               .cloned()
+                                                               .zip(data.fields.iter().cloned()));
+                    }
+
+                    self.write_entries('{', '}', &members, JsonWriter::write_member)?;
+
+                    self.containers.pop();
+                },
+
+            Value::ByteBuffer(_) => return Err("Can not convert byte buffers to JSON.".to_string()),
+            Value::Token(_)      => return Err("Can not convert tokens to JSON.".to_string()),
+            Value::Code(_)       => return Err("Can not convert byte code to JSON.".to_string())
+        }
+
+        Ok(())
+    }
+}
+
+
+
+/// Parse JSON text into a value.  Any JSON value is accepted at the top level.
+pub fn value_from_json(text: &str) -> Result<Value, String>
+{
+    JsonParser::new(text).parse_document()
+}
+
+
+/// Convert a value to JSON text.  If an indent is given the text is pretty printed using that many
+/// spaces per level.  Typed text tags structures with their definition's name.
+pub fn value_to_json(value: &Value, indent: Option<usize>, typed: bool) -> Result<String, String>
+{
+    let mut writer = JsonWriter::new(indent, typed);
+
+    writer.write_value(value)?;
+
+    Ok(writer.output)
+}
+
+
+
+/// Rebuild the structures tagged in parsed typed JSON.  Objects with a type key become instances
+/// of the named structure, fields missing from the text keep their defaults, and fields that the
+/// structure doesn't define are an error.
+fn structures_from_json(interpreter: &mut dyn Interpreter, value: Value) -> error::Result<Value>
+{
+    match value
+    {
+        Value::Vec(vec_ptr) =>
+            {
+                let count = vec_ptr.borrow().len();
+
+                for index in 0..count
+                {
+                    let item = vec_ptr.borrow()[index].clone();
+                    let item = structures_from_json(interpreter, item)?;
+
+                    vec_ptr.borrow_mut()[index] = item;
+                }
+
+                Ok(Value::Vec(vec_ptr))
+            },
+
+        Value::HashMap(hash_ptr) =>
+            {
+                let members: Vec<( Value, Value )> = hash_ptr.borrow()
+                                                             .iter()
+                                                             .map(|( key, value )|
+                                                                  ( key.clone(), value.clone() ))
+                                                             .collect();
+                let type_name = hash_ptr.borrow().get(&JSON_TYPE_KEY.to_string().to_value()).cloned();
+
+                let Some(type_name) = type_name
+                    else
+                    {
+                        for ( key, value ) in members
+                        {
+                            let value = structures_from_json(interpreter, value)?;
+                            hash_ptr.borrow_mut().insert(key, value);
+                        }
+
+                        return Ok(Value::HashMap(hash_ptr));
+                    };
+
+                if !type_name.is_string()
+                {
+                    return script_error(interpreter,
+                                        format!("The {} of a typed JSON object must be a string.",
+                                                JSON_TYPE_KEY));
+                }
+
+                let name = type_name.get_string_val();
+                let definition = interpreter.structure_definitions()
+                                            .iter()
+                                            .filter(|found| found.borrow().name() == &name)
+                                            .last()
+                                            .cloned();
+
+                let Some(definition) = definition
+                    else
+                    {
+                        return script_error(interpreter, format!("Structure {} not found.", name));
+                    };
+
+                let data_ptr = DataObject::new(&definition);
+
+                for ( key, value ) in members
+                {
+                    let key = key.get_string_val();
+
+                    if key == JSON_TYPE_KEY
+                    {
+                        continue;
+                    }
+
+                    let index = definition.borrow()
+                                          .field_names()
+                                          .iter()
+                                          .position(|field| field == &key);
+
+                    let Some(index) = index
+                        else
+                        {
+                            return script_error(interpreter,
+                                                format!("Structure {} has no field {}.",
+                                                        name,
+                                                        key));
+                        };
+
+                    data_ptr.borrow_mut().fields[index] = structures_from_json(interpreter, value)?;
+                }
+
+                Ok(data_ptr.to_value())
+            },
+
+        _ => Ok(value)
+    }
+}
+
+
+/// Parse JSON text and push the result as an interpreter value.
+fn parse_json_word(interpreter: &mut dyn Interpreter) -> error::Result<Value>
+{
+    let text = interpreter.pop_as_string()?;
+
+    match value_from_json(&text)
+    {
+        Ok(value)    => Ok(value),
+        Err(message) => script_error(interpreter, message)
+    }
+}
+
+/// Pop the number of spaces per level to indent pretty printed JSON by.
+fn pop_indent(interpreter: &mut dyn Interpreter) -> error::Result<usize>
+{
+    let indent = interpreter.pop_as_int()?;
+
+    if !(0..=MAX_JSON_INDENT).contains(&indent)
+    {
+        return script_error(interpreter, format!("Invalid JSON indent {}, expected 0 to {}.",
+                                                 indent,
+                                                 MAX_JSON_INDENT));
+    }
+
+    Ok(indent as usize)
+}
+
+/// Convert a value popped from the stack to JSON text and push the text.
+fn stringify_json_word(interpreter: &mut dyn Interpreter,
+                       indent: Option<usize>,
+                       typed: bool) -> error::Result<()>
+{
+    let value = interpreter.pop()?;
+
+    match value_to_json(&value, indent, typed)
+    {
+        Ok(text)     => interpreter.push(text.to_value()),
+        Err(message) => script_error(interpreter, message)?
+    }
+
+    Ok(())
+}
+
+
+
+/// Parse JSON text into a value.  Objects become hash tables, arrays become arrays, and null
+/// becomes none.
+///
+/// Signature: `json-string -- value`
+fn word_json_parse(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let value = parse_json_word(interpreter)?;
+
+    interpreter.push(value);
+
+    Ok(())
+}
+
+/// Convert a value to compact JSON text.
+///
+/// Signature: `value -- json-string`
+fn word_json_stringify(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    stringify_json_word(interpreter, None, false)
+}
+
+/// Convert a value to JSON text, pretty printed with the given number of spaces per level.
+///
+/// Signature: `value indent -- json-string`
+fn word_json_stringify_pretty(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let indent = pop_indent(interpreter)?;
+
+    stringify_json_word(interpreter, Some(indent), false)
+}
+
+/// Parse typed JSON text, rebuilding any tagged structures.
+///
+/// Signature: `json-string -- value`
+fn word_json_parse_typed(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let value = parse_json_word(interpreter)?;
+    let value = structures_from_json(interpreter, value)?;
+
+    interpreter.push(value);
+
+    Ok(())
+}
+
+/// Convert a value to compact typed JSON text, tagging structures with their definition's name.
+///
+/// Signature: `value -- json-string`
+fn word_json_stringify_typed(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    stringify_json_word(interpreter, None, true)
+}
+
+/// Convert a value to typed JSON text, pretty printed with the given number of spaces per level.
+///
+/// Signature: `value indent -- json-string`
+fn word_json_stringify_typed_pretty(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let indent = pop_indent(interpreter)?;
+
+    stringify_json_word(interpreter, Some(indent), true)
+}
+
+/// Convert a hash table to JSON text.
+///
+/// Signature: `hash-table -- json-string`
+fn word_hash_table_to_json(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let hash = interpreter.pop_as_hash_map()?;
+
+    interpreter.push(hash.to_value());
+    stringify_json_word(interpreter, None, false)
+}
+
+/// Convert a structure to JSON text.
+///
+/// Signature: `structure -- json-string`
+fn word_structure_to_json(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let data = interpreter.pop_as_data_object()?;
+
+    interpreter.push(data.to_value());
+    stringify_json_word(interpreter, None, false)
+}
+
+/// Parse JSON text that holds an object into a hash table.
+///
+/// Signature: `json-string -- hash-table`
+fn word_hash_table_from_json(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let value = parse_json_word(interpreter)?;
+
+    if !value.is_hash_map()
+    {
+        script_error(interpreter, "Expected the JSON text to hold an object.".to_string())?;
+    }
+
+    interpreter.push(value);
+
+    Ok(())
+}
+
+
+
+/// Register the JSON words with the given interpreter.
+pub fn register_json_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "json.parse", word_json_parse,
+        "Parse JSON text into a value, null becomes none.",
+        "json_string -- value");
+
+    add_native_word!(interpreter, "json.stringify", word_json_stringify,
+        "Convert a value to compact JSON text.",
+        "value -- json_string");
+
+    add_native_word!(interpreter, "json.stringify-pretty", word_json_stringify_pretty,
+        "Convert a value to JSON text indented by the given number of spaces.",
+        "value indent -- json_string");
+
+    add_native_word!(interpreter, "json.parse-typed", word_json_parse_typed,
+        "Parse typed JSON text, rebuilding tagged structures.",
+        "json_string -- value");
+
+    add_native_word!(interpreter, "json.stringify-typed", word_json_stringify_typed,
+        "Convert a value to compact JSON text with structures tagged by type.",
+        "value -- json_string");
+
+    add_native_word!(interpreter, "json.stringify-typed-pretty", word_json_stringify_typed_pretty,
+        "Convert a value to indented JSON text with structures tagged by type.",
+        "value indent -- json_string");
+
+    add_native_word!(interpreter, "{}.to_json", word_hash_table_to_json,
+        "Convert a hash table into a JSON string.",
+        "hash_table -- json_string");
+
+    add_native_word!(interpreter, "#.to_json", word_structure_to_json,
+        "Convert a structure object to a JSON string.",
+        "structure -- json_string");
+
+    add_native_word!(interpreter, "{}.from_json", word_hash_table_from_json,
+        "Convert a JSON formatted string into a hash table.",
+        "json_string -- hash_table");
+}
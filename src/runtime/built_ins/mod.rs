@@ -5,11 +5,44 @@ pub mod base_words;
 /// Words that perform I/O operations.
 pub mod io_words;
 
+/// Words that encode and decode hex, Base64, and text.
+pub mod encoding_words;
+
+/// Words that compute checksums and cryptographic hashes.
+pub mod hash_words;
+
+/// Words that parse and generate JSON.
+pub mod json_words;
+
+/// Words that convert values to and from MessagePack.
+pub mod msgpack_words;
+
+/// Words that read and write CSV data.
+pub mod csv_words;
+
+/// Words that load and save configuration files.
+pub mod config_words;
+
+/// Words that match, search, and replace with regular expressions.
+pub mod regex_words;
+
+/// Words that work with directories, file metadata, and glob patterns.
+pub mod fs_words;
+
+/// Words that manipulate filesystem paths.
+pub mod path_words;
+
 /// Words that work with the terminal.
 pub mod terminal_words;
 
 /// Words that access the user's environment.
 pub mod user_words;
 
+/// Words that work with dates, times, and clocks.
+pub mod time_words;
+
+/// Words that start and manage child processes.
+pub mod process_words;
+
 /// Words that interface with foreign functions.
 pub mod ffi_words;
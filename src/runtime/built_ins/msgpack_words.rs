@@ -0,0 +1,721 @@
+
+use std::{ cell::RefCell,
+           collections::HashMap,
+           rc::Rc };
+use crate::{ add_native_word,
+             runtime::{ built_ins::encoding_words::push_bytes,
+                        data_structures::{ byte_buffer::{ BufferPtr,
+                                                          ByteBuffer },
+                                           data_object::{ DataDefinitionList,
+                                                          DataObject },
+                                           value::{ ToValue, Value },
+                                           value_hash::ValueHash,
+                                           value_vec::ValueVec },
+                        error::{ self,
+                                 script_error },
+                        interpreter::Interpreter } };
+
+
+
+/// The extension type used to hold a structure.  The payload is an array of the structure's name
+/// followed by it's field values in definition order.
+const EXT_STRUCTURE: i8 = 1;
+
+/// The extension type used for a value that was already written.  The payload is the index of the
+/// array, hash table, or structure in the order that they were first written.
+const EXT_REFERENCE: i8 = 2;
+
+/// The deepest nesting of arrays, hash tables, and structures that the reader will accept, or the
+/// writer will write.
+const MAX_MSGPACK_DEPTH: usize = 512;
+
+
+
+/// Write a MessagePack header that holds a length.  The fixed form is used if it's available and
+/// the length fits, otherwise the smallest of the 8, 16, or 32 bit forms is used.
+fn write_length(output: &mut Vec<u8>,
+                length: usize,
+                fixed: Option<( u8, usize )>,
+                codes: [ Option<u8>; 3 ]) -> Result<(), String>
+{
+    match ( fixed, codes )
+    {
+        ( Some(( base, max )), _ ) if length <= max =>
+            output.push(base | length as u8),
+
+        ( _, [ Some(code), _, _ ] ) if length <= u8::MAX as usize =>
+            {
+                output.push(code);
+                output.push(length as u8);
+            },
+
+        ( _, [ _, Some(code), _ ] ) if length <= u16::MAX as usize =>
+            {
+                output.push(code);
+                output.extend_from_slice(&(length as u16).to_be_bytes());
+            },
+
+        ( _, [ _, _, Some(code) ] ) if length <= u32::MAX as usize =>
+            {
+                output.push(code);
+                output.extend_from_slice(&(length as u32).to_be_bytes());
+            },
+
+        _ => return Err(format!("Length {} is too large for MessagePack.", length))
+    }
+
+    Ok(())
+}
+
+
+/// Write the header of an array with the given number of items.
+fn write_array_header(output: &mut Vec<u8>, length: usize) -> Result<(), String>
+{
+    write_length(output, length, Some(( 0x90, 15 )), [ None, Some(0xdc), Some(0xdd) ])
+}
+
+
+/// Write an integer using the smallest MessagePack form that can hold it.
+fn write_int(output: &mut Vec<u8>, value: i64)
+{
+    match value
+    {
+        0..=0x7f               => output.push(value as u8),
+        -32..=-1               => output.push(value as i8 as u8),
+        0x80..=0xff            =>
+            {
+                output.push(0xcc);
+                output.push(value as u8);
+            },
+        0x100..=0xffff         =>
+            {
+                output.push(0xcd);
+                output.extend_from_slice(&(value as u16).to_be_bytes());
+            },
+        0x10000..=0xffff_ffff  =>
+            {
+                output.push(0xce);
+                output.extend_from_slice(&(value as u32).to_be_bytes());
+            },
+        -0x80..=-33            =>
+            {
+                output.push(0xd0);
+                output.push(value as i8 as u8);
+            },
+        -0x8000..=-0x81        =>
+            {
+                output.push(0xd1);
+                output.extend_from_slice(&(value as i16).to_be_bytes());
+            },
+        -0x8000_0000..=-0x8001 =>
+            {
+                output.push(0xd2);
+                output.extend_from_slice(&(value as i32).to_be_bytes());
+            },
+        _                      =>
+            {
+                output.push(0xd3);
+                output.extend_from_slice(&value.to_be_bytes());
+            }
+    }
+}
+
+
+/// Write an extension value with the given type and payload.
+fn write_extension(output: &mut Vec<u8>, ext_type: i8, payload: &[u8]) -> Result<(), String>
+{
+    write_length(output, payload.len(), None, [ Some(0xc7), Some(0xc8), Some(0xc9) ])?;
+    output.push(ext_type as u8);
+    output.extend_from_slice(payload);
+
+    Ok(())
+}
+
+
+
+/// Writer that converts interpreter values into MessagePack.  Arrays, hash tables, and structures
+/// that are referenced more than once are written once and then referred to by index, while
+/// values that contain themselves are rejected.
+struct MsgPackWriter
+{
+    containers: HashMap<usize, usize>,
+    path: Vec<usize>
+}
+
+
+impl MsgPackWriter
+{
+    /// Create a new writer.
+    fn new() -> MsgPackWriter
+    {
+        MsgPackWriter
+            {
+                containers: HashMap::new(),
+                path: Vec::new()
+            }
+    }
+
+    /// Start writing the container at the given address.  If the container has already been
+    /// written a reference to it is written instead, and true is returned.  Containers nested
+    /// deeper than the reader would accept are rejected.
+    fn enter_container(&mut self, output: &mut Vec<u8>, address: usize) -> Result<bool, String>
+    {
+        if self.path.contains(&address)
+        {
+            return Err("Can not convert a value that contains itself to MessagePack.".to_string());
+        }
+
+        if let Some(index) = self.containers.get(&address)
+        {
+            let mut payload = Vec::new();
+
+            write_int(&mut payload, *index as i64);
+            write_extension(output, EXT_REFERENCE, &payload)?;
+
+            return Ok(true);
+        }
+
+        if self.path.len() >= MAX_MSGPACK_DEPTH
+        {
+            return Err(format!("Nesting is deeper than {} levels.", MAX_MSGPACK_DEPTH));
+        }
+
+        self.containers.insert(address, self.containers.len());
+        self.path.push(address);
+
+        Ok(false)
+    }
+
+    /// Write any value that can be represented in MessagePack.
+    fn write_value(&mut self, output: &mut Vec<u8>, value: &Value) -> Result<(), String>
+    {
+        match value
+        {
+            Value::None          => output.push(0xc0),
+            Value::Bool(value)   => output.push(if *value { 0xc3 } else { 0xc2 }),
+            Value::Int(value)    => write_int(output, *value),
+
+            Value::Float(value) =>
+                {
+                    output.push(0xcb);
+                    output.extend_from_slice(&value.to_be_bytes());
+                },
+
+            Value::String(value) =>
+                {
+                    write_length(output,
+                                 value.len(),
+                                 Some(( 0xa0, 31 )),
+                                 [ Some(0xd9), Some(0xda), Some(0xdb) ])?;
+                    output.extend_from_slice(value.as_bytes());
+                },
+
+            Value::ByteBuffer(buffer) =>
+                {
+                    let buffer = buffer.borrow();
+                    let bytes = buffer.bytes();
+
+                    write_length(output,
+                                 bytes.len(),
+                                 None,
+                                 [ Some(0xc4), Some(0xc5), Some(0xc6) ])?;
+                    output.extend_from_slice(bytes);
+                },
+
+            Value::Vec(vec_ptr) =>
+                {
+                    if self.enter_container(output, Rc::as_ptr(vec_ptr) as *const () as usize)?
+                    {
+                        return Ok(());
+                    }
+
+                    let values: Vec<Value> = vec_ptr.borrow().iter().cloned().collect();
+
+                    write_array_header(output, values.len())?;
+
+                    for value in values.iter()
+                    {
+                        self.write_value(output, value)?;
+                    }
+
+                    self.path.pop();
+                },
+
+            Value::HashMap(hash_ptr) =>
+                {
+                    if self.enter_container(output, Rc::as_ptr(hash_ptr) as *const () as usize)?
+                    {
+                        return Ok(());
+                    }
+
+                    let members: Vec<( Value, Value )> =
+                        hash_ptr.borrow()
+                                .iter()
+                                .map(|( key, value )| ( key.clone(), value.clone() ))
+                                .collect();
+
+                    write_length(output,
+                                 members.len(),
+                                 Some(( 0x80, 15 )),
+                                 [ None, Some(0xde), Some(0xdf) ])?;
+
+                    for ( key, value ) in members.iter()
+                    {
+                        self.write_value(output, key)?;
+                        self.write_value(output, value)?;
+                    }
+
+                    self.path.pop();
+                },
+
+            Value::DataObject(data_ptr) =>
+                {
+                    if self.enter_container(output, Rc::as_ptr(data_ptr) as *const () as usize)?
+                    {
+                        return Ok(());
+                    }
+
+                    let ( name, fields ) =
+                        {
+                            let data = data_ptr.borrow();
+                            let name = data.definition_ptr.borrow().name().clone();
+
+                            ( name, data.fields.clone() )
+                        };
+
+                    let mut payload = Vec::new();
+
+                    write_array_header(&mut payload, fields.len() + 1)?;
+                    self.write_value(&mut payload, &name.to_value())?;
+
+                    for field in fields.iter()
+                    {
+                        self.write_value(&mut payload, field)?;
+                    }
+
+                    write_extension(output, EXT_STRUCTURE, &payload)?;
+
+                    self.path.pop();
+                },
+
+            Value::Token(_) => return Err("Can not convert tokens to MessagePack.".to_string()),
+            Value::Code(_)  => return Err("Can not convert byte code to MessagePack.".to_string())
+        }
+
+        Ok(())
+    }
+}
+
+
+
+/// Reader that converts MessagePack back into interpreter values.  Structures are looked up by name
+/// in the given list of definitions.
+struct MsgPackReader<'a>
+{
+    bytes: &'a [u8],
+    position: usize,
+    depth: usize,
+    definitions: &'a DataDefinitionList,
+    containers: Vec<( Value, bool )>
+}
+
+
+impl<'a> MsgPackReader<'a>
+{
+    /// Create a new reader at the start of the given bytes.
+    fn new(bytes: &'a [u8], definitions: &'a DataDefinitionList) -> MsgPackReader<'a>
+    {
+        MsgPackReader
+            {
+                bytes,
+                position: 0,
+                depth: 0,
+                definitions,
+                containers: Vec::new()
+            }
+    }
+
+    /// Generate an error message that includes the current byte offset.
+    fn error<T>(&self, message: &str) -> Result<T, String>
+    {
+        Err(format!("MessagePack error at byte {}: {}", self.position, message))
+    }
+
+    /// Read the whole buffer as a single value.
+    fn read_document(&mut self) -> Result<Value, String>
+    {
+        let value = self.read_value()?;
+
+        if self.position < self.bytes.len()
+        {
+            return self.error("Unexpected data after the value.");
+        }
+
+        Ok(value)
+    }
+
+    /// Read a run of raw bytes.
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], String>
+    {
+        if count > self.bytes.len() - self.position
+        {
+            return self.error("Unexpected end of data.");
+        }
+
+        let bytes = &self.bytes[self.position..self.position + count];
+
+        self.position += count;
+        Ok(bytes)
+    }
+
+    /// Read a fixed size run of bytes.
+    fn read_array<const N: usize>(&mut self) -> Result<[ u8; N ], String>
+    {
+        let mut array = [ 0; N ];
+
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    /// Read a big endian length of the given number of bytes.
+    fn read_length(&mut self, size: usize) -> Result<usize, String>
+    {
+        let length = match size
+            {
+                1 => u8::from_be_bytes(self.read_array()?) as usize,
+                2 => u16::from_be_bytes(self.read_array()?) as usize,
+                _ => u32::from_be_bytes(self.read_array()?) as usize
+            };
+
+        Ok(length)
+    }
+
+    /// Register a new array, hash table, or structure so that later references can find it.
+    fn add_container(&mut self, value: &Value) -> Result<(), String>
+    {
+        self.depth += 1;
+
+        if self.depth > MAX_MSGPACK_DEPTH
+        {
+            return self.error(&format!("Nesting is deeper than {} levels.", MAX_MSGPACK_DEPTH));
+        }
+
+        self.containers.push(( value.clone(), false ));
+        Ok(())
+    }
+
+    /// Mark a container as complete.
+    fn finish_container(&mut self, index: usize)
+    {
+        self.containers[index].1 = true;
+        self.depth -= 1;
+    }
+
+    /// Read the array header at the current position and return it's length.
+    fn read_array_header(&mut self) -> Result<usize, String>
+    {
+        match self.read_array::<1>()?[0]
+        {
+            code @ 0x90..=0x9f => Ok((code & 0x0f) as usize),
+            0xdc               => self.read_length(2),
+            0xdd               => self.read_length(4),
+            _                  => self.error("Expected an array.")
+        }
+    }
+
+    /// Read a string of the given length.
+    fn read_string(&mut self, length: usize) -> Result<Value, String>
+    {
+        let bytes = self.read_bytes(length)?;
+
+        match std::str::from_utf8(bytes)
+        {
+            Ok(text) => Ok(text.to_string().to_value()),
+            Err(_)   =>
+                {
+                    self.position -= length;
+                    self.error("String holds invalid UTF-8.")
+                }
+        }
+    }
+
+    /// Read binary data into a new byte buffer.
+    fn read_binary(&mut self, length: usize) -> Result<Value, String>
+    {
+        let bytes = self.read_bytes(length)?.to_vec();
+        let buffer: BufferPtr = Rc::new(RefCell::new(ByteBuffer::from_bytes(bytes)));
+
+        Ok(buffer.to_value())
+    }
+
+    /// Read the given number of values into a new array.
+    fn read_array_values(&mut self, length: usize) -> Result<Value, String>
+    {
+        let vec_ptr = ValueVec::from_vec(Vec::new());
+        let index = self.containers.len();
+
+        self.add_container(&vec_ptr.to_value())?;
+
+        for _ in 0..length
+        {
+            let value = self.read_value()?;
+            vec_ptr.borrow_mut().push_back(value);
+        }
+
+        self.finish_container(index);
+        Ok(vec_ptr.to_value())
+    }
+
+    /// Read the given number of key and value pairs into a new hash table.
+    fn read_map_values(&mut self, length: usize) -> Result<Value, String>
+    {
+        let hash_ptr = ValueHash::new();
+        let index = self.containers.len();
+
+        self.add_container(&hash_ptr.to_value())?;
+
+        for _ in 0..length
+        {
+            let key = self.read_value()?;
+            let value = self.read_value()?;
+
+            hash_ptr.borrow_mut().insert(key, value);
+        }
+
+        self.finish_container(index);
+        Ok(hash_ptr.to_value())
+    }
+
+    /// Read a structure extension, creating a new instance of the named structure.
+    fn read_structure(&mut self) -> Result<Value, String>
+    {
+        let length = self.read_array_header()?;
+
+        if length == 0
+        {
+            return self.error("Structure is missing it's name.");
+        }
+
+        let name = self.read_value()?;
+
+        if !name.is_string()
+        {
+            return self.error("Structure name must be a string.");
+        }
+
+        let name = name.get_string_val();
+        let definition = self.definitions
+                             .iter()
+                             .filter(|found| found.borrow().name() == &name)
+                             .last()
+                             .cloned();
+
+        let Some(definition) = definition
+            else
+            {
+                return self.error(&format!("Structure {} not found.", name));
+            };
+
+        if definition.borrow().field_names().len() != length - 1
+        {
+            return self.error(&format!("Structure {} expects {} fields, found {}.",
+                                       name,
+                                       definition.borrow().field_names().len(),
+                                       length - 1));
+        }
+
+        let data_ptr = DataObject::new(&definition);
+        let index = self.containers.len();
+
+        self.add_container(&data_ptr.to_value())?;
+
+        for field in 0..length - 1
+        {
+            let value = self.read_value()?;
+            data_ptr.borrow_mut().fields[field] = value;
+        }
+
+        self.finish_container(index);
+        Ok(data_ptr.to_value())
+    }
+
+    /// Read a reference extension, returning the container that was already read.
+    fn read_reference(&mut self) -> Result<Value, String>
+    {
+        let index = self.read_value()?;
+
+        if !index.is_int()
+        {
+            return self.error("Reference index must be an integer.");
+        }
+
+        match self.containers.get(index.get_int_val() as usize)
+        {
+            Some(( value, true ))  => Ok(value.clone()),
+            Some(( _, false ))     => self.error("Reference to a value that contains itself."),
+            None                   => self.error(&format!("Invalid reference index {}.",
+                                                          index.get_int_val()))
+        }
+    }
+
+    /// Read an extension of the given payload length.
+    fn read_extension(&mut self, length: usize) -> Result<Value, String>
+    {
+        let ext_type = self.read_array::<1>()?[0] as i8;
+        let end = self.position + length;
+
+        if end > self.bytes.len()
+        {
+            return self.error("Unexpected end of data.");
+        }
+
+        let value = match ext_type
+            {
+                EXT_STRUCTURE => self.read_structure()?,
+                EXT_REFERENCE => self.read_reference()?,
+                _             => return self.error(&format!("Unknown extension type {}.",
+                                                            ext_type))
+            };
+
+        if self.position != end
+        {
+            return self.error("Extension payload has the wrong length.");
+        }
+
+        Ok(value)
+    }
+
+    /// Read any MessagePack value at the current position.
+    fn read_value(&mut self) -> Result<Value, String>
+    {
+        let code = self.read_array::<1>()?[0];
+
+        let value = match code
+            {
+                0x00..=0x7f => (code as i64).to_value(),
+                0x80..=0x8f => self.read_map_values((code & 0x0f) as usize)?,
+                0x90..=0x9f => self.read_array_values((code & 0x0f) as usize)?,
+                0xa0..=0xbf => self.read_string((code & 0x1f) as usize)?,
+                0xc0        => Value::None,
+                0xc2        => false.to_value(),
+                0xc3        => true.to_value(),
+
+                0xc4..=0xc6 =>
+                    {
+                        let length = self.read_length(1 << (code - 0xc4))?;
+                        self.read_binary(length)?
+                    },
+
+                0xc7..=0xc9 =>
+                    {
+                        let length = self.read_length(1 << (code - 0xc7))?;
+                        self.read_extension(length)?
+                    },
+
+                0xca => (f32::from_be_bytes(self.read_array()?) as f64).to_value(),
+                0xcb => f64::from_be_bytes(self.read_array()?).to_value(),
+                0xcc => (u8::from_be_bytes(self.read_array()?) as i64).to_value(),
+                0xcd => (u16::from_be_bytes(self.read_array()?) as i64).to_value(),
+                0xce => (u32::from_be_bytes(self.read_array()?) as i64).to_value(),
+
+                0xcf =>
+                    {
+                        let value = u64::from_be_bytes(self.read_array()?);
+
+                        match i64::try_from(value)
+                        {
+                            Ok(value) => value.to_value(),
+                            Err(_)    => return self.error(&format!("Integer {} is too large.",
+                                                                    value))
+                        }
+                    },
+
+                0xd0 => (i8::from_be_bytes(self.read_array()?) as i64).to_value(),
+                0xd1 => (i16::from_be_bytes(self.read_array()?) as i64).to_value(),
+                0xd2 => (i32::from_be_bytes(self.read_array()?) as i64).to_value(),
+                0xd3 => i64::from_be_bytes(self.read_array()?).to_value(),
+
+                0xd4..=0xd8 => self.read_extension(1 << (code - 0xd4))?,
+
+                0xd9..=0xdb =>
+                    {
+                        let length = self.read_length(1 << (code - 0xd9))?;
+                        self.read_string(length)?
+                    },
+
+                0xdc..=0xdd =>
+                    {
+                        let length = self.read_length(2 << (code - 0xdc))?;
+                        self.read_array_values(length)?
+                    },
+
+                0xde..=0xdf =>
+                    {
+                        let length = self.read_length(2 << (code - 0xde))?;
+                        self.read_map_values(length)?
+                    },
+
+                0xe0..=0xff => (code as i8 as i64).to_value(),
+
+                _ =>
+                    {
+                        self.position -= 1;
+                        return self.error(&format!("Invalid type code 0x{:02x}.", code));
+                    }
+            };
+
+        Ok(value)
+    }
+}
+
+
+
+/// Convert a value to MessagePack and store it in a new byte buffer.  Structures are written as
+/// extensions, and arrays, hash tables, and structures that are shared are only written once.
+///
+/// Signature: `value -- buffer`
+fn word_value_to_msgpack(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let value = interpreter.pop()?;
+    let mut output = Vec::new();
+
+    match MsgPackWriter::new().write_value(&mut output, &value)
+    {
+        Ok(())       => push_bytes(interpreter, output),
+        Err(message) => script_error(interpreter, message)?
+    }
+
+    Ok(())
+}
+
+/// Read a value from the MessagePack held in a byte buffer.
+///
+/// Signature: `buffer -- value`
+fn word_msgpack_to_value(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let bytes = buffer.borrow().bytes().to_vec();
+    let result = MsgPackReader::new(&bytes, interpreter.structure_definitions()).read_document();
+
+    match result
+    {
+        Ok(value)    => interpreter.push(value),
+        Err(message) => script_error(interpreter, message)?
+    }
+
+    Ok(())
+}
+
+
+
+/// Register the MessagePack words with the given interpreter.
+pub fn register_msgpack_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "value.to-msgpack", word_value_to_msgpack,
+        "Convert a value to MessagePack in a new byte buffer.",
+        "value -- buffer");
+
+    add_native_word!(interpreter, "msgpack.to-value", word_msgpack_to_value,
+        "Read a value from MessagePack held in a byte buffer.",
+        "buffer -- value");
+}
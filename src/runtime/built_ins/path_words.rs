@@ -0,0 +1,285 @@
+
+use std::{ fs::canonicalize,
+           path::{ absolute, Component, Path, PathBuf } };
+use crate::{ add_native_word,
+             runtime::{ data_structures::value::{ ToValue, Value },
+                        error::{ self,
+                                 script_error,
+                                 script_error_str },
+                        interpreter::Interpreter } };
+
+
+
+/// Convert a path into a string, failing if the path isn't valid UTF-8.
+fn path_to_string(interpreter: &mut dyn Interpreter, path: &Path) -> error::Result<String>
+{
+    match path.to_str()
+    {
+        Some(path) => Ok(path.to_string()),
+        None       => script_error_str(interpreter, "Path contains invalid characters.")
+    }
+}
+
+
+/// Push an optional path component, pushing none if the component is missing.
+fn push_optional(interpreter: &mut dyn Interpreter, path: Option<&Path>) -> error::Result<()>
+{
+    let value = match path
+        {
+            Some(path) => path_to_string(interpreter, path)?.to_value(),
+            None       => Value::None
+        };
+
+    interpreter.push(value);
+    Ok(())
+}
+
+
+/// Compute the path that leads from the base directory to the given path.  Both paths need to be
+/// either absolute or relative, and the base can not climb out of the common part of the paths with
+/// `..`.
+fn relative_path(path: &Path, base: &Path) -> Option<PathBuf>
+{
+    if path.has_root() != base.has_root()
+    {
+        return None;
+    }
+
+    let path_components: Vec<Component> = path.components()
+                                               .filter(|component| *component != Component::CurDir)
+                                               .collect();
+    let base_components: Vec<Component> = base.components()
+                                               .filter(|component| *component != Component::CurDir)
+                                               .collect();
+
+    let common = path_components.iter()
+                                .zip(base_components.iter())
+                                .take_while(|( path_component, base_component )|
+                                    {
+                                        path_component == base_component
+                                    })
+                                .count();
+
+    // Absolute paths on different drives have no relative path between them.
+    if path.has_root() && common == 0
+    {
+        return None;
+    }
+
+    if base_components[common..].contains(&Component::ParentDir)
+    {
+        return None;
+    }
+
+    let mut relative = PathBuf::new();
+
+    for _ in common..base_components.len()
+    {
+        relative.push(Component::ParentDir);
+    }
+
+    for component in &path_components[common..]
+    {
+        relative.push(component);
+    }
+
+    if relative.as_os_str().is_empty()
+    {
+        relative.push(Component::CurDir);
+    }
+
+    Some(relative)
+}
+
+
+
+/// Join a path onto a base path.  If the new path is absolute it replaces the base path.
+///
+/// Signature: `base-path path -- joined-path`
+fn word_path_join(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    let base = interpreter.pop_as_string()?;
+
+    let value = path_to_string(interpreter, &Path::new(&base).join(path))?.to_value();
+
+    interpreter.push(value);
+    Ok(())
+}
+
+/// Get the parent directory of a path, or none if the path has no parent.
+///
+/// Signature: `path -- parent-or-none`
+fn word_path_parent(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    push_optional(interpreter, Path::new(&path).parent())
+}
+
+/// Get the final component of a path, or none if the path ends in `..` or is a root.
+///
+/// Signature: `path -- file-name-or-none`
+fn word_path_file_name(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    push_optional(interpreter, Path::new(&path).file_name().map(Path::new))
+}
+
+/// Get the file name of a path without it's extension, or none if the path has no file name.
+///
+/// Signature: `path -- stem-or-none`
+fn word_path_stem(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    push_optional(interpreter, Path::new(&path).file_stem().map(Path::new))
+}
+
+/// Get the extension of a path without the leading `.`, or none if the path has no extension.
+///
+/// Signature: `path -- extension-or-none`
+fn word_path_extension(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    push_optional(interpreter, Path::new(&path).extension().map(Path::new))
+}
+
+/// Make a path absolute by joining it to the current directory.  Unlike path.canonical the path
+/// does not need to exist and symbolic links are not resolved.
+///
+/// Signature: `path -- absolute-path`
+fn word_path_absolute(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    match absolute(&path)
+    {
+        Ok(absolute_path) =>
+            {
+                let value = path_to_string(interpreter, &absolute_path)?.to_value();
+                interpreter.push(value);
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Could not make path {} absolute: {}.",
+                                                  path,
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+/// Get the canonical form of a path, with all symbolic links and `.` and `..` components resolved.
+/// The path must exist.
+///
+/// Signature: `path -- canonical-path`
+fn word_path_canonical(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+
+    match canonicalize(&path)
+    {
+        Ok(canonical_path) =>
+            {
+                let value = path_to_string(interpreter, &canonical_path)?.to_value();
+                interpreter.push(value);
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Could not canonicalize path {}: {}.",
+                                                  path,
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+/// Get the path relative to a base directory.
+///
+/// Signature: `path base-path -- relative-path`
+fn word_path_relative_to(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let base = interpreter.pop_as_string()?;
+    let path = interpreter.pop_as_string()?;
+
+    match relative_path(Path::new(&path), Path::new(&base))
+    {
+        Some(relative) =>
+            {
+                let value = path_to_string(interpreter, &relative)?.to_value();
+                interpreter.push(value);
+            },
+
+        None =>
+            {
+                script_error(interpreter, format!("Path {} can not be made relative to {}.",
+                                                  path,
+                                                  base))?;
+            }
+    }
+
+    Ok(())
+}
+
+/// Split a path into an array of it's components.  An absolute path's first component is it's
+/// root.
+///
+/// Signature: `path -- components`
+fn word_path_split(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let path = interpreter.pop_as_string()?;
+    let mut components = Vec::new();
+
+    for component in Path::new(&path).components()
+    {
+        components.push(path_to_string(interpreter, component.as_ref())?);
+    }
+
+    interpreter.push(Value::from(components));
+    Ok(())
+}
+
+
+
+/// Register the path manipulation words with the given interpreter.
+pub fn register_path_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "path.join", word_path_join,
+        "Join a path onto a base path.",
+        "base_path path -- joined_path");
+
+    add_native_word!(interpreter, "path.parent", word_path_parent,
+        "Get the parent directory of a path, or none if there isn't one.",
+        "path -- parent_or_none");
+
+    add_native_word!(interpreter, "path.file-name", word_path_file_name,
+        "Get the final component of a path, or none if there isn't one.",
+        "path -- file_name_or_none");
+
+    add_native_word!(interpreter, "path.stem", word_path_stem,
+        "Get the file name of a path without it's extension, or none if there isn't one.",
+        "path -- stem_or_none");
+
+    add_native_word!(interpreter, "path.extension", word_path_extension,
+        "Get the extension of a path, or none if there isn't one.",
+        "path -- extension_or_none");
+
+    add_native_word!(interpreter, "path.absolute", word_path_absolute,
+        "Make a path absolute without resolving symbolic links.",
+        "path -- absolute_path");
+
+    add_native_word!(interpreter, "path.canonical", word_path_canonical,
+        "Get the canonical absolute form of an existing path.",
+        "path -- canonical_path");
+
+    add_native_word!(interpreter, "path.relative-to", word_path_relative_to,
+        "Get a path relative to a base directory.",
+        "path base_path -- relative_path");
+
+    add_native_word!(interpreter, "path.split", word_path_split,
+        "Split a path into an array of it's components.",
+        "path -- components");
+}
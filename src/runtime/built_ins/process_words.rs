@@ -0,0 +1,336 @@
+
+use std::{ fs::File,
+           process::{ Child, Command, ExitStatus, Stdio } };
+use crate::{ add_native_word,
+             runtime::{ built_ins::io_words::FileObject,
+                        data_structures::value::{ ToValue, Value },
+                        error::{ self,
+                                 script_error,
+                                 script_error_str },
+                        interpreter::Interpreter } };
+
+
+
+/// Pop the command, environment, and working directory from the stack and build the command from
+/// them.
+///
+/// The command can be either a string naming the program or an array of the program and it's
+/// arguments.  The environment is either none or a hash table of variables to add to the inherited
+/// environment.  A variable with a value of none is removed from the environment instead.  The
+/// working directory is either none or the path the command should be run in.
+///
+/// Signature: `command env cwd -- `
+fn pop_command(interpreter: &mut dyn Interpreter) -> error::Result<Command>
+{
+    let cwd = interpreter.pop()?;
+    let env = interpreter.pop()?;
+    let command_value = interpreter.pop()?;
+
+    let mut arguments = Vec::new();
+
+    if command_value.is_vec()
+    {
+        for argument in command_value.as_vec(interpreter)?.borrow().iter()
+        {
+            if !argument.is_stringable()
+            {
+                script_error(interpreter, format!("Invalid command argument {}.", argument))?;
+            }
+
+            arguments.push(argument.get_string_val());
+        }
+    }
+    else if command_value.is_stringable()
+    {
+        arguments.push(command_value.get_string_val());
+    }
+    else
+    {
+        script_error(interpreter, format!("Expected a command array, found {}.", command_value))?;
+    }
+
+    if arguments.is_empty()
+    {
+        script_error_str(interpreter, "The command array is empty.")?;
+    }
+
+    let mut command = Command::new(&arguments[0]);
+
+    command.args(&arguments[1..]);
+
+    if env.is_hash_map()
+    {
+        for ( name, value ) in env.as_hash_map(interpreter)?.borrow().iter()
+        {
+            if !name.is_stringable()
+            {
+                script_error(interpreter, format!("Invalid environment variable name {}.", name))?;
+            }
+
+            if value.is_none()
+            {
+                command.env_remove(name.get_string_val());
+            }
+            else
+            {
+                command.env(name.get_string_val(), value.to_string());
+            }
+        }
+    }
+    else if !env.is_none()
+    {
+        script_error(interpreter, format!("Expected an environment hash table or none, found {}.",
+                                          env))?;
+    }
+
+    if cwd.is_stringable() && !cwd.is_none()
+    {
+        command.current_dir(cwd.get_string_val());
+    }
+    else if !cwd.is_none()
+    {
+        script_error(interpreter, format!("Expected a working directory or none, found {}.",
+                                          cwd))?;
+    }
+
+    Ok(command)
+}
+
+
+#[cfg(unix)]
+/// Convert an exit status into the code reported to scripts.  If the process was killed by a
+/// signal the negated signal number is returned instead.
+fn exit_status_code(status: &ExitStatus) -> i64
+{
+    use std::os::unix::process::ExitStatusExt;
+
+    match ( status.code(), status.signal() )
+    {
+        ( Some(code), _      ) => code as i64,
+        ( None, Some(signal) ) => -(signal as i64),
+        ( None, None         ) => -1
+    }
+}
+
+
+#[cfg(windows)]
+/// Convert an exit status into the code reported to scripts.
+fn exit_status_code(status: &ExitStatus) -> i64
+{
+    status.code().map(|code| code as i64).unwrap_or(-1)
+}
+
+
+#[cfg(unix)]
+/// Convert one end of a child process pipe into a file that can be stored in the file table.
+fn pipe_to_file<T>(pipe: T) -> File
+    where std::os::fd::OwnedFd: From<T>
+{
+    File::from(std::os::fd::OwnedFd::from(pipe))
+}
+
+
+#[cfg(windows)]
+/// Convert one end of a child process pipe into a file that can be stored in the file table.
+fn pipe_to_file<T>(pipe: T) -> File
+    where std::os::windows::io::OwnedHandle: From<T>
+{
+    File::from(std::os::windows::io::OwnedHandle::from(pipe))
+}
+
+
+/// Register a pipe with the interpreter's file table and get it's new fd.
+fn register_pipe(interpreter: &mut dyn Interpreter, pipe: File) -> i64
+{
+    interpreter.files_mut().add(FileObject::Pipe(pipe))
+}
+
+
+
+/// Run a command to completion and capture it's output and exit code.
+///
+/// Signature: `command env cwd -- stdout stderr exit-code`
+fn word_process_run(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let mut command = pop_command(interpreter)?;
+
+    command.stdin(Stdio::null());
+
+    match command.output()
+    {
+        Ok(output) =>
+            {
+                interpreter.push(String::from_utf8_lossy(&output.stdout).to_string().to_value());
+                interpreter.push(String::from_utf8_lossy(&output.stderr).to_string().to_value());
+                interpreter.push(exit_status_code(&output.status).to_value());
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Could not run command {:?}: {}.",
+                                                  command.get_program(),
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+/// Start a command running in the background.  The process's standard input, output, and error are
+/// connected to pipes that are registered as fds usable by the file words.
+///
+/// Signature: `command env cwd -- pid stdin-fd stdout-fd stderr-fd`
+fn word_process_spawn(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let mut command = pop_command(interpreter)?;
+
+    command.stdin(Stdio::piped())
+           .stdout(Stdio::piped())
+           .stderr(Stdio::piped());
+
+    match command.spawn()
+    {
+        Ok(mut child) =>
+            {
+                let pid = child.id() as i64;
+
+                let stdin_fd = child.stdin
+                                    .take()
+                                    .map(|pipe| register_pipe(interpreter, pipe_to_file(pipe)));
+                let stdout_fd = child.stdout
+                                     .take()
+                                     .map(|pipe| register_pipe(interpreter, pipe_to_file(pipe)));
+                let stderr_fd = child.stderr
+                                     .take()
+                                     .map(|pipe| register_pipe(interpreter, pipe_to_file(pipe)));
+
+                // Child processes are kept in the interpreter's resource table, indexed by their
+                // process id.
+                interpreter.resources_mut().insert(pid, child);
+
+                interpreter.push(pid.to_value());
+
+                for fd in [ stdin_fd, stdout_fd, stderr_fd ]
+                {
+                    interpreter.push(fd.map(|fd| fd.to_value()).unwrap_or(Value::None));
+                }
+            },
+
+        Err(error) =>
+            {
+                script_error(interpreter, format!("Could not spawn command {:?}: {}.",
+                                                  command.get_program(),
+                                                  error))?;
+            }
+    }
+
+    Ok(())
+}
+
+/// Wait for a spawned process to exit and get it's exit code.  Close the process's stdin fd first
+/// if the process is waiting for it's input to end.
+///
+/// Signature: `pid -- exit-code`
+fn word_process_wait(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let pid = interpreter.pop_as_int()?;
+    let child = interpreter.resources_mut().remove::<Child>(pid);
+
+    match child
+    {
+        Some(mut child) =>
+            {
+                match child.wait()
+                {
+                    Ok(status) => interpreter.push(exit_status_code(&status).to_value()),
+                    Err(error) => script_error(interpreter,
+                                               format!("Could not wait for process {}: {}.",
+                                                       pid,
+                                                       error))?
+                }
+            },
+
+        None => script_error(interpreter, format!("Process {} not found.", pid))?
+    }
+
+    Ok(())
+}
+
+/// Check if a spawned process has exited without blocking.  If the process is still running none
+/// is returned.
+///
+/// Signature: `pid -- exit-code-or-none`
+fn word_process_try_wait(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let pid = interpreter.pop_as_int()?;
+
+    let result = match interpreter.resources_mut().get_mut::<Child>(pid)
+        {
+            Some(child) => child.try_wait(),
+            None        => return script_error(interpreter, format!("Process {} not found.", pid))
+        };
+
+    match result
+    {
+        Ok(Some(status)) =>
+            {
+                let _ = interpreter.resources_mut().remove::<Child>(pid);
+                interpreter.push(exit_status_code(&status).to_value());
+            },
+
+        Ok(None) => interpreter.push(Value::None),
+
+        Err(error) => script_error(interpreter, format!("Could not check process {}: {}.",
+                                                        pid,
+                                                        error))?
+    }
+
+    Ok(())
+}
+
+/// Kill a spawned process.  The process still needs to be waited on to collect it's exit code.
+///
+/// Signature: `pid -- `
+fn word_process_kill(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let pid = interpreter.pop_as_int()?;
+
+    let result = match interpreter.resources_mut().get_mut::<Child>(pid)
+        {
+            Some(child) => child.kill(),
+            None        => return script_error(interpreter, format!("Process {} not found.", pid))
+        };
+
+    if let Err(error) = result
+    {
+        script_error(interpreter, format!("Could not kill process {}: {}.", pid, error))?;
+    }
+
+    Ok(())
+}
+
+
+
+/// Register the process words with the given interpreter.
+pub fn register_process_words(interpreter: &mut dyn Interpreter)
+{
+    add_native_word!(interpreter, "process.run", word_process_run,
+        "Run a command and capture it's output.  The env and cwd can be none.",
+        "command env cwd -- stdout stderr exit_code");
+
+    add_native_word!(interpreter, "process.spawn", word_process_spawn,
+        "Start a command in the background with it's standard streams connected to fds.",
+        "command env cwd -- pid stdin_fd stdout_fd stderr_fd");
+
+    add_native_word!(interpreter, "process.wait", word_process_wait,
+        "Wait for a spawned process to exit and return it's exit code.",
+        "pid -- exit_code");
+
+    add_native_word!(interpreter, "process.try-wait", word_process_try_wait,
+        "Get a spawned process's exit code, or none if it is still running.",
+        "pid -- exit_code_or_none");
+
+    add_native_word!(interpreter, "process.kill", word_process_kill,
+        "Kill a spawned process.",
+        "pid -- ");
+}
@@ -0,0 +1,357 @@
+
+use regex::Regex;
+use crate::{ add_native_word,
+             runtime::{ data_structures::{ data_object::{ DataObject,
+                                                          DataObjectDefinition,
+                                                          DataObjectDefinitionPtr },
+                                           value::{ ToValue,
+                                                    Value },
+                                           value_hash::ValueHash,
+                                           value_vec::ValueVec },
+                        error::{ self,
+                                 script_error },
+                        interpreter::Interpreter } };
+
+
+
+/// The most compiled patterns an interpreter keeps in it's regex cache.
+const REGEX_CACHE_SIZE: usize = 64;
+
+/// The resource handle of the interpreter's regex cache.  There's only ever one cache per
+/// interpreter.
+const REGEX_CACHE_HANDLE: i64 = 0;
+
+
+/// The most recently used compiled regular expressions, so that a pattern that's used over and over
+/// is only compiled once.  Each interpreter keeps it's own cache in it's resource table.
+struct RegexCache
+{
+    /// The compiled patterns ordered from least to most recently used.
+    entries: Vec<( String, Regex )>
+}
+
+
+impl RegexCache
+{
+    /// Find a compiled pattern and mark it as the most recently used.
+    fn get(&mut self, pattern: &String) -> Option<Regex>
+    {
+        let index = self.entries.iter().position(|( key, _ )| key == pattern)?;
+        let entry = self.entries.remove(index);
+        let regex = entry.1.clone();
+
+        self.entries.push(entry);
+        Some(regex)
+    }
+
+    /// Add a newly compiled pattern, dropping the least recently used pattern if the cache is full.
+    fn insert(&mut self, pattern: String, regex: Regex)
+    {
+        if self.entries.len() >= REGEX_CACHE_SIZE
+        {
+            let _ = self.entries.remove(0);
+        }
+
+        self.entries.push(( pattern, regex ));
+    }
+}
+
+
+
+/// Get the interpreter's regex cache, creating it if this is the first time it's been needed.
+fn regex_cache(interpreter: &mut dyn Interpreter) -> &mut RegexCache
+{
+    let resources = interpreter.resources_mut();
+
+    if resources.get_mut::<RegexCache>(REGEX_CACHE_HANDLE).is_none()
+    {
+        resources.insert(REGEX_CACHE_HANDLE, RegexCache { entries: Vec::new() });
+    }
+
+    resources.get_mut::<RegexCache>(REGEX_CACHE_HANDLE).expect("The regex cache was just added.")
+}
+
+
+/// Get the compiled form of a pattern, compiling and caching it if the pattern hasn't been seen
+/// recently.
+fn compile(interpreter: &mut dyn Interpreter, pattern: &String) -> error::Result<Regex>
+{
+    if let Some(regex) = regex_cache(interpreter).get(pattern)
+    {
+        return Ok(regex);
+    }
+
+    match Regex::new(pattern)
+    {
+        Ok(regex) =>
+            {
+                regex_cache(interpreter).insert(pattern.clone(), regex.clone());
+                Ok(regex)
+            },
+
+        Err(error) => script_error(interpreter, format!("Invalid regular expression {}: {}",
+                                                        Value::stringify(pattern),
+                                                        error))
+    }
+}
+
+
+/// Check if a value is a `regex` structure.
+fn is_regex(value: &Value) -> bool
+{
+    match value
+    {
+        Value::DataObject(data_ptr) => data_ptr.borrow().definition_ptr.borrow().name() == "regex",
+        _                           => false
+    }
+}
+
+
+/// Pop either a `regex` structure or a pattern string from the stack and get it's compiled form.
+fn pop_regex(interpreter: &mut dyn Interpreter) -> error::Result<Regex>
+{
+    let value = interpreter.pop()?;
+
+    let pattern = match &value
+        {
+            Value::String(pattern) => pattern.clone(),
+
+            Value::DataObject(data_ptr) if is_regex(&value) =>
+                {
+                    match data_ptr.borrow().fields.first()
+                    {
+                        Some(Value::String(pattern)) => pattern.clone(),
+                        _ => return script_error(interpreter,
+                                                 "The regex pattern field is not a string."
+                                                 .to_string())
+                    }
+                },
+
+            _ => return script_error(interpreter,
+                                     format!("Expected a regex or pattern string, found {}.",
+                                             value))
+        };
+
+    compile(interpreter, &pattern)
+}
+
+
+/// Convert an optional capture group into a string value, or none if the group didn't take part in
+/// the match.
+fn group_to_value(group: Option<regex::Match>) -> Value
+{
+    match group
+    {
+        Some(group) => group.as_str().to_string().to_value(),
+        None        => Value::None
+    }
+}
+
+
+
+/// Compile a regular expression pattern.  The compiled form is cached, so the `regex` structure
+/// can be freely copied and reused.
+///
+/// Signature: `pattern -- regex`
+fn word_regex_compile(interpreter: &mut dyn Interpreter,
+                      definition: &DataObjectDefinitionPtr) -> error::Result<()>
+{
+    let pattern = interpreter.pop_as_string()?;
+
+    compile(interpreter, &pattern)?;
+
+    let data_ptr = DataObject::new(definition);
+
+    data_ptr.borrow_mut().fields[0] = pattern.to_value();
+    interpreter.push(data_ptr.to_value());
+
+    Ok(())
+}
+
+/// Get the pattern a regex was compiled from.
+///
+/// Signature: `regex -- pattern`
+fn word_regex_pattern(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let value = interpreter.pop()?;
+
+    if !is_regex(&value)
+    {
+        return script_error(interpreter, format!("Expected a regex, found {}.", value));
+    }
+
+    let pattern = value.as_data_object(interpreter)?.borrow().fields[0].clone();
+
+    interpreter.push(pattern);
+
+    Ok(())
+}
+
+/// Check if the regular expression matches anywhere within the string.
+///
+/// Signature: `string regex -- bool`
+fn word_regex_is_match(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    interpreter.push(regex.is_match(&text).to_value());
+
+    Ok(())
+}
+
+/// Find the first match of the regular expression within the string.
+///
+/// Signature: `string regex -- match-or-none`
+fn word_regex_find(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    interpreter.push(group_to_value(regex.find(&text)));
+
+    Ok(())
+}
+
+/// Get the capture groups of the first match of the regular expression.  If the expression has
+/// named groups a hash table of the named groups is returned, otherwise an array of all the groups
+/// starting with the whole match.  Groups that didn't take part in the match are none, and none is
+/// returned if there's no match at all.
+///
+/// Signature: `string regex -- captures-or-none`
+fn word_regex_captures(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    let Some(captures) = regex.captures(&text)
+        else
+        {
+            interpreter.push(Value::None);
+            return Ok(());
+        };
+
+    let has_names = regex.capture_names().any(|name| name.is_some());
+
+    if has_names
+    {
+        let table = ValueHash::new();
+
+        for name in regex.capture_names().flatten()
+        {
+            table.borrow_mut().insert(name.to_string().to_value(),
+                                      group_to_value(captures.name(name)));
+        }
+
+        interpreter.push(table.to_value());
+    }
+    else
+    {
+        let groups = captures.iter().map(group_to_value).collect();
+
+        interpreter.push(ValueVec::from_vec(groups).to_value());
+    }
+
+    Ok(())
+}
+
+/// Replace the first match of the regular expression.  The replacement can refer to capture groups
+/// with `$1` or `${name}`.
+///
+/// Signature: `string replacement regex -- updated-string`
+fn word_regex_replace(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let regex = pop_regex(interpreter)?;
+    let replacement = interpreter.pop_as_string()?;
+    let text = interpreter.pop_as_string()?;
+
+    interpreter.push(regex.replace(&text, replacement.as_str()).to_string().to_value());
+
+    Ok(())
+}
+
+/// Replace every match of the regular expression.  The replacement can refer to capture groups
+/// with `$1` or `${name}`.
+///
+/// Signature: `string replacement regex -- updated-string`
+fn word_regex_replace_all(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let regex = pop_regex(interpreter)?;
+    let replacement = interpreter.pop_as_string()?;
+    let text = interpreter.pop_as_string()?;
+
+    interpreter.push(regex.replace_all(&text, replacement.as_str()).to_string().to_value());
+
+    Ok(())
+}
+
+/// Split a string on every match of the regular expression.
+///
+/// Signature: `string regex -- parts`
+fn word_regex_split(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+    let parts = regex.split(&text).map(|part| part.to_string().to_value()).collect();
+
+    interpreter.push(ValueVec::from_vec(parts).to_value());
+
+    Ok(())
+}
+
+
+
+/// Register the `regex` structure with the interpreter.  The structure's words aren't generated,
+/// the pattern is read with `regex.pattern@`.  If the field is changed anyway the new pattern is
+/// checked and compiled when the regex is next used.
+fn register_regex_struct(interpreter: &mut dyn Interpreter) -> DataObjectDefinitionPtr
+{
+    DataObjectDefinition::new(interpreter,
+                              "regex".to_string(),
+                              vec![ "pattern".to_string() ],
+                              vec![ String::new().to_value() ],
+                              false)
+}
+
+
+/// Register the regular expression words with the given interpreter.
+pub fn register_regex_words(interpreter: &mut dyn Interpreter)
+{
+    let definition = register_regex_struct(interpreter);
+
+    add_native_word!(interpreter, "regex.compile",
+        move |interpreter: &mut dyn Interpreter|
+        {
+            word_regex_compile(interpreter, &definition)
+        },
+        "Compile a regular expression pattern into a regex structure.",
+        "pattern -- regex");
+
+    add_native_word!(interpreter, "regex.pattern@", word_regex_pattern,
+        "Get the pattern a regex was compiled from.",
+        "regex -- pattern");
+
+    add_native_word!(interpreter, "regex.match?", word_regex_is_match,
+        "Check if a regex or pattern matches anywhere within a string.",
+        "string regex -- bool");
+
+    add_native_word!(interpreter, "regex.find", word_regex_find,
+        "Find the first matching text within a string, or none if there's no match.",
+        "string regex -- match_or_none");
+
+    add_native_word!(interpreter, "regex.captures", word_regex_captures,
+        "Get the capture groups of the first match as an array, or a hash table of named groups.",
+        "string regex -- captures_or_none");
+
+    add_native_word!(interpreter, "regex.replace", word_regex_replace,
+        "Replace the first match within a string, $1 or ${name} refer to capture groups.",
+        "string replacement regex -- updated_string");
+
+    add_native_word!(interpreter, "regex.replace-all", word_regex_replace_all,
+        "Replace every match within a string, $1 or ${name} refer to capture groups.",
+        "string replacement regex -- updated_string");
+
+    add_native_word!(interpreter, "regex.split", word_regex_split,
+        "Split a string on every match of a regex.",
+        "string regex -- parts");
+}
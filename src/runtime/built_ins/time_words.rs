@@ -0,0 +1,909 @@
+
+use std::{ thread::sleep,
+           time::{ Duration, Instant, SystemTime, UNIX_EPOCH } };
+use lazy_static::lazy_static;
+use crate::{ add_native_word,
+             location_here,
+             runtime::{ data_structures::{ data_object::{ DataObject,
+                                                          DataObjectDefinition,
+                                                          DataObjectDefinitionPtr },
+                                           value::{ ToValue,
+                                                    Value } },
+                        error::{ self,
+                                 script_error,
+                                 script_error_str },
+                        interpreter::Interpreter } };
+
+
+
+lazy_static!
+{
+    // The instant the interpreter started, used as the base for the monotonic clock.
+    static ref MONOTONIC_START: Instant = Instant::now();
+}
+
+
+/// Names of the days of the week, starting on Sunday.
+const WEEKDAY_NAMES: [ &str; 7 ] = [ "Sunday", "Monday", "Tuesday", "Wednesday", "Thursday",
+                                     "Friday", "Saturday" ];
+
+/// Names of the months of the year, starting on January.
+const MONTH_NAMES: [ &str; 12 ] = [ "January", "February", "March", "April", "May", "June", "July",
+                                    "August", "September", "October", "November", "December" ];
+
+/// The field names of the `time.parts` structure.  The order of the names must match the order of
+/// the fields in the TimeParts structure.
+const TIME_PARTS_FIELDS: [ &str; 10 ] = [ "year", "month", "day", "hour", "minute", "second",
+                                          "nanosecond", "weekday", "year_day", "utc_offset" ];
+
+/// The furthest year from the epoch, in either direction, that a `time.parts` structure can hold.
+/// Keeping the year within this range keeps the conversion back to seconds from overflowing.
+const MAX_YEAR: i64 = 1_000_000_000;
+
+
+
+/// A timestamp broken out into it's calendar components.  The fields represent the wall clock time
+/// at the given UTC offset.
+#[derive(Clone, Default)]
+pub struct TimeParts
+{
+    /// The full year, for example 2024.
+    year: i64,
+
+    /// The 1 based month of the year.
+    month: i64,
+
+    /// The 1 based day of the month.
+    day: i64,
+
+    /// The hour of the day, 0 - 23.
+    hour: i64,
+
+    /// The minute of the hour, 0 - 59.
+    minute: i64,
+
+    /// The second of the minute, 0 - 59.
+    second: i64,
+
+    /// The fractional second in nanoseconds.
+    nanosecond: i64,
+
+    /// The day of the week where 0 is Sunday.
+    weekday: i64,
+
+    /// The 1 based day of the year.
+    year_day: i64,
+
+    /// The offset from UTC in seconds, positive values are east of UTC.
+    utc_offset: i64
+}
+
+
+impl TimeParts
+{
+    /// Break out a timestamp into it's calendar parts as seen from the given UTC offset.
+    fn from_epoch(seconds: i64, nanosecond: i64, utc_offset: i64) -> TimeParts
+    {
+        let local_seconds = seconds + utc_offset;
+        let days = local_seconds.div_euclid(86_400);
+        let day_seconds = local_seconds.rem_euclid(86_400);
+
+        let ( year, month, day ) = civil_from_days(days);
+
+        TimeParts
+            {
+                year,
+                month,
+                day,
+                hour: day_seconds / 3_600,
+                minute: (day_seconds % 3_600) / 60,
+                second: day_seconds % 60,
+                nanosecond,
+                weekday: (days + 4).rem_euclid(7),
+                year_day: days - days_from_civil(year, 1, 1) + 1,
+                utc_offset
+            }
+    }
+
+    /// Convert the calendar parts back into a count of seconds since the Unix epoch.
+    fn to_epoch(&self) -> i64
+    {
+        let days = days_from_civil(self.year, self.month, self.day);
+
+        (days * 86_400) + (self.hour * 3_600) + (self.minute * 60) + self.second - self.utc_offset
+    }
+
+    /// Read the parts out of a `time.parts` structure.
+    pub fn from_value(interpreter: &mut dyn Interpreter, value: &Value) -> error::Result<TimeParts>
+    {
+        let data_ptr = value.as_data_object(interpreter)?.clone();
+        let data = data_ptr.borrow();
+
+        if    data.definition_ptr.borrow().name() != "time.parts"
+           || data.fields.len() != TIME_PARTS_FIELDS.len()
+        {
+            return script_error(interpreter,
+                                format!("Expected a time.parts structure, found {}.",
+                                        data.definition_ptr.borrow().name()));
+        }
+
+        let mut fields = [ 0i64; 10 ];
+
+        for ( index, field ) in data.fields.iter().enumerate()
+        {
+            if !field.is_numeric()
+            {
+                return script_error(interpreter,
+                                    format!("The time.parts field {} is not a number.",
+                                            TIME_PARTS_FIELDS[index]));
+            }
+
+            fields[index] = field.get_int_val();
+        }
+
+        let parts = TimeParts
+            {
+                year: fields[0],
+                month: fields[1],
+                day: fields[2],
+                hour: fields[3],
+                minute: fields[4],
+                second: fields[5],
+                nanosecond: fields[6],
+                weekday: fields[7],
+                year_day: fields[8],
+                utc_offset: fields[9]
+            };
+
+        if let Err(error) = parts.check_ranges()
+        {
+            return script_error(interpreter, format!("Invalid time.parts structure: {}", error));
+        }
+
+        Ok(parts)
+    }
+
+    /// Make sure that the date and time fields hold an actual moment in time, so that they can be
+    /// safely converted back into seconds.  The weekday and year day are derived from the date, so
+    /// they're checked when they're used.
+    fn check_ranges(&self) -> Result<(), String>
+    {
+        if self.year < -MAX_YEAR || self.year > MAX_YEAR
+        {
+            return Err(format!("Year {} is out of range.", self.year));
+        }
+
+        self.month_index()?;
+
+        if self.day < 1 || self.day > days_in_month(self.year, self.month)
+        {
+            return Err(format!("Day {} is out of range for the month.", self.day));
+        }
+
+        if    self.hour < 0 || self.hour > 23
+           || self.minute < 0 || self.minute > 59
+           || self.second < 0 || self.second > 60
+        {
+            return Err(format!("Time {:02}:{:02}:{:02} is out of range.",
+                               self.hour,
+                               self.minute,
+                               self.second));
+        }
+
+        if self.nanosecond < 0 || self.nanosecond > 999_999_999
+        {
+            return Err(format!("Nanosecond {} is out of range.", self.nanosecond));
+        }
+
+        if self.utc_offset <= -86_400 || self.utc_offset >= 86_400
+        {
+            return Err(format!("UTC offset {} is out of range.", self.utc_offset));
+        }
+
+        Ok(())
+    }
+
+    /// Create a new `time.parts` structure from these parts.
+    pub fn to_value(&self, definition: &DataObjectDefinitionPtr) -> Value
+    {
+        let data_ptr = DataObject::new(definition);
+
+        data_ptr.borrow_mut().fields = vec![ self.year.to_value(),
+                                             self.month.to_value(),
+                                             self.day.to_value(),
+                                             self.hour.to_value(),
+                                             self.minute.to_value(),
+                                             self.second.to_value(),
+                                             self.nanosecond.to_value(),
+                                             self.weekday.to_value(),
+                                             self.year_day.to_value(),
+                                             self.utc_offset.to_value() ];
+
+        data_ptr.to_value()
+    }
+
+    /// Make sure that the month is usable as an index for the month name tables.
+    fn month_index(&self) -> Result<usize, String>
+    {
+        if self.month < 1 || self.month > 12
+        {
+            return Err(format!("Month {} is out of range.", self.month));
+        }
+
+        Ok((self.month - 1) as usize)
+    }
+
+    /// Make sure that the weekday is usable as an index for the weekday name tables.
+    fn weekday_index(&self) -> Result<usize, String>
+    {
+        if self.weekday < 0 || self.weekday > 6
+        {
+            return Err(format!("Weekday {} is out of range.", self.weekday));
+        }
+
+        Ok(self.weekday as usize)
+    }
+}
+
+
+
+/// Compute the number of days since the Unix epoch for the given date in the proleptic Gregorian
+/// calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64
+{
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+
+/// Compute the year, month, and day for the given number of days since the Unix epoch.
+fn civil_from_days(days: i64) -> ( i64, i64, i64 )
+{
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524
+                       - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    ( year, month, day )
+}
+
+
+/// Is the given year a leap year?
+fn is_leap_year(year: i64) -> bool
+{
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+
+/// How many days are in the given month?
+fn days_in_month(year: i64, month: i64) -> i64
+{
+    match month
+    {
+        2                => if is_leap_year(year) { 29 } else { 28 },
+        4 | 6 | 9 | 11   => 30,
+        _                => 31
+    }
+}
+
+
+#[cfg(unix)]
+/// Get the local time zone's offset from UTC in seconds for the given timestamp.
+fn local_utc_offset(seconds: i64) -> Option<i64>
+{
+    let time = seconds as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+
+    let result = unsafe { libc::localtime_r(&time, &mut tm) };
+
+    if result.is_null()
+    {
+        None
+    }
+    else
+    {
+        Some(tm.tm_gmtoff as i64)
+    }
+}
+
+
+#[cfg(windows)]
+/// Get the local time zone's offset from UTC in seconds for the given timestamp.
+fn local_utc_offset(_seconds: i64) -> Option<i64>
+{
+    use winapi::um::timezoneapi::{ GetTimeZoneInformation, TIME_ZONE_INFORMATION };
+
+    let mut info: TIME_ZONE_INFORMATION = unsafe { std::mem::zeroed() };
+    let result = unsafe { GetTimeZoneInformation(&mut info) };
+
+    match result
+    {
+        // TIME_ZONE_ID_INVALID
+        0xffff_ffff => None,
+
+        // TIME_ZONE_ID_DAYLIGHT
+        2 => Some(-((info.Bias + info.DaylightBias) as i64) * 60),
+
+        _ => Some(-((info.Bias + info.StandardBias) as i64) * 60)
+    }
+}
+
+
+/// Get the current time as seconds and nanoseconds since the Unix epoch.
+fn epoch_now(interpreter: &mut dyn Interpreter) -> error::Result<( i64, i64 )>
+{
+    match SystemTime::now().duration_since(UNIX_EPOCH)
+    {
+        Ok(duration) => Ok(( duration.as_secs() as i64, duration.subsec_nanos() as i64 )),
+        Err(error)   => script_error(interpreter,
+                                     format!("System clock is set before the epoch: {}.", error))
+    }
+}
+
+
+/// Pop a timestamp from the stack.  Integers are taken as whole seconds since the epoch, while the
+/// fractional part of a floating point value is kept as nanoseconds.
+fn pop_timestamp(interpreter: &mut dyn Interpreter) -> error::Result<( i64, i64 )>
+{
+    let value = interpreter.pop()?;
+
+    if value.is_float()
+    {
+        let timestamp = value.get_float_val();
+        let seconds = timestamp.floor();
+        let nanoseconds = ((timestamp - seconds) * 1_000_000_000.0).round() as i64;
+
+        Ok(( seconds as i64, nanoseconds.min(999_999_999) ))
+    }
+    else if value.is_numeric()
+    {
+        Ok(( value.get_int_val(), 0 ))
+    }
+    else
+    {
+        script_error(interpreter, format!("Expected a timestamp, found {}.", value))
+    }
+}
+
+
+/// Format the UTC offset as +hh:mm or +hhmm.
+fn format_utc_offset(utc_offset: i64, use_colon: bool) -> String
+{
+    let sign = if utc_offset < 0 { '-' } else { '+' };
+    let minutes = utc_offset.abs() / 60;
+
+    if use_colon
+    {
+        format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+    else
+    {
+        format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+
+/// Format time parts with a strftime style format string.
+fn format_time(parts: &TimeParts, format: &str) -> Result<String, String>
+{
+    let mut result = String::new();
+    let mut characters = format.chars();
+
+    while let Some(next) = characters.next()
+    {
+        if next != '%'
+        {
+            result.push(next);
+            continue;
+        }
+
+        let specifier = match characters.next()
+            {
+                Some(specifier) => specifier,
+                None            => return Err("Incomplete format specifier at end of string."
+                                              .to_string())
+            };
+
+        match specifier
+        {
+            'Y' => result.push_str(&format!("{:04}", parts.year)),
+            'C' => result.push_str(&format!("{:02}", parts.year.div_euclid(100))),
+            'y' => result.push_str(&format!("{:02}", parts.year.rem_euclid(100))),
+            'm' => result.push_str(&format!("{:02}", parts.month)),
+            'd' => result.push_str(&format!("{:02}", parts.day)),
+            'e' => result.push_str(&format!("{:2}", parts.day)),
+            'j' => result.push_str(&format!("{:03}", parts.year_day)),
+            'H' => result.push_str(&format!("{:02}", parts.hour)),
+            'I' => result.push_str(&format!("{:02}", (parts.hour + 11) % 12 + 1)),
+            'M' => result.push_str(&format!("{:02}", parts.minute)),
+            'S' => result.push_str(&format!("{:02}", parts.second)),
+            'f' => result.push_str(&format!("{:09}", parts.nanosecond)),
+            'p' => result.push_str(if parts.hour < 12 { "AM" } else { "PM" }),
+            'a' => result.push_str(&WEEKDAY_NAMES[parts.weekday_index()?][..3]),
+            'A' => result.push_str(WEEKDAY_NAMES[parts.weekday_index()?]),
+            'b' => result.push_str(&MONTH_NAMES[parts.month_index()?][..3]),
+            'B' => result.push_str(MONTH_NAMES[parts.month_index()?]),
+            'u' => result.push_str(&format!("{}", (parts.weekday + 6) % 7 + 1)),
+            'w' => result.push_str(&format!("{}", parts.weekday)),
+            'z' => result.push_str(&format_utc_offset(parts.utc_offset, false)),
+            'Z' => result.push_str(&if parts.utc_offset == 0
+                                       {
+                                           "UTC".to_string()
+                                       }
+                                       else
+                                       {
+                                           format_utc_offset(parts.utc_offset, true)
+                                       }),
+            's' => result.push_str(&format!("{}", parts.to_epoch())),
+            'F' => result.push_str(&format_time(parts, "%Y-%m-%d")?),
+            'T' => result.push_str(&format_time(parts, "%H:%M:%S")?),
+            'D' => result.push_str(&format_time(parts, "%m/%d/%y")?),
+            'R' => result.push_str(&format_time(parts, "%H:%M")?),
+            'n' => result.push('\n'),
+            't' => result.push('\t'),
+            '%' => result.push('%'),
+            _   => return Err(format!("Unknown format specifier %{}.", specifier))
+        }
+    }
+
+    Ok(result)
+}
+
+
+/// Format time parts as an ISO-8601 timestamp.  The fractional seconds are only included if they
+/// are non-zero.
+pub fn format_iso_8601(parts: &TimeParts) -> String
+{
+    let mut result = format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                             parts.year,
+                             parts.month,
+                             parts.day,
+                             parts.hour,
+                             parts.minute,
+                             parts.second);
+
+    if parts.nanosecond != 0
+    {
+        let fraction = format!("{:09}", parts.nanosecond);
+        result.push('.');
+        result.push_str(fraction.trim_end_matches('0'));
+    }
+
+    if parts.utc_offset == 0
+    {
+        result.push('Z');
+    }
+    else
+    {
+        result.push_str(&format_utc_offset(parts.utc_offset, true));
+    }
+
+    result
+}
+
+
+/// Parse an ISO-8601 timestamp of the form `YYYY-MM-DD[THH:MM[:SS[.fff]]][Z|+hh[:mm]]`.  A space is
+/// also accepted as the date/time separator.  If no offset is given the time is taken to be UTC.
+pub fn parse_iso_8601(text: &str) -> Result<TimeParts, String>
+{
+    let bytes = text.trim().as_bytes();
+    let mut index = 0;
+
+    fn read_number(bytes: &[u8], index: &mut usize, digits: usize) -> Result<i64, String>
+    {
+        if *index + digits > bytes.len()
+        {
+            return Err(format!("Expected {} digits at position {}.", digits, *index + 1));
+        }
+
+        let mut value = 0;
+
+        for offset in 0..digits
+        {
+            let byte = bytes[*index + offset];
+
+            if !byte.is_ascii_digit()
+            {
+                return Err(format!("Expected a digit at position {}.", *index + offset + 1));
+            }
+
+            value = value * 10 + (byte - b'0') as i64;
+        }
+
+        *index += digits;
+        Ok(value)
+    }
+
+    fn expect(bytes: &[u8], index: &mut usize, expected: u8) -> Result<(), String>
+    {
+        if *index >= bytes.len() || bytes[*index] != expected
+        {
+            return Err(format!("Expected '{}' at position {}.", expected as char, *index + 1));
+        }
+
+        *index += 1;
+        Ok(())
+    }
+
+    let year = read_number(bytes, &mut index, 4)?;
+    expect(bytes, &mut index, b'-')?;
+    let month = read_number(bytes, &mut index, 2)?;
+    expect(bytes, &mut index, b'-')?;
+    let day = read_number(bytes, &mut index, 2)?;
+
+    let mut parts = TimeParts { year, month, day, ..TimeParts::default() };
+
+    if parts.month < 1 || parts.month > 12
+    {
+        return Err(format!("Month {} is out of range.", parts.month));
+    }
+
+    if parts.day < 1 || parts.day > days_in_month(parts.year, parts.month)
+    {
+        return Err(format!("Day {} is out of range for the month.", parts.day));
+    }
+
+    if index < bytes.len() && (bytes[index] == b'T' || bytes[index] == b't' || bytes[index] == b' ')
+    {
+        index += 1;
+
+        parts.hour = read_number(bytes, &mut index, 2)?;
+        expect(bytes, &mut index, b':')?;
+        parts.minute = read_number(bytes, &mut index, 2)?;
+
+        if index < bytes.len() && bytes[index] == b':'
+        {
+            index += 1;
+            parts.second = read_number(bytes, &mut index, 2)?;
+
+            if index < bytes.len() && (bytes[index] == b'.' || bytes[index] == b',')
+            {
+                index += 1;
+
+                let mut digits = 0;
+                let mut nanosecond = 0;
+
+                while index < bytes.len() && bytes[index].is_ascii_digit()
+                {
+                    if digits < 9
+                    {
+                        nanosecond = nanosecond * 10 + (bytes[index] - b'0') as i64;
+                        digits += 1;
+                    }
+
+                    index += 1;
+                }
+
+                if digits == 0
+                {
+                    return Err(format!("Expected fractional seconds at position {}.", index + 1));
+                }
+
+                for _ in digits..9
+                {
+                    nanosecond *= 10;
+                }
+
+                parts.nanosecond = nanosecond;
+            }
+        }
+
+        if parts.hour > 23 || parts.minute > 59 || parts.second > 60
+        {
+            return Err(format!("Time {:02}:{:02}:{:02} is out of range.",
+                               parts.hour,
+                               parts.minute,
+                               parts.second));
+        }
+
+        if index < bytes.len()
+        {
+            match bytes[index]
+            {
+                b'Z' | b'z' => index += 1,
+
+                b'+' | b'-' =>
+                    {
+                        let sign = if bytes[index] == b'-' { -1 } else { 1 };
+
+                        index += 1;
+
+                        let hours = read_number(bytes, &mut index, 2)?;
+                        let mut minutes = 0;
+
+                        if index < bytes.len()
+                        {
+                            if bytes[index] == b':'
+                            {
+                                index += 1;
+                            }
+
+                            minutes = read_number(bytes, &mut index, 2)?;
+                        }
+
+                        if hours > 23 || minutes > 59
+                        {
+                            return Err(format!("UTC offset {:02}:{:02} is out of range.",
+                                               hours,
+                                               minutes));
+                        }
+
+                        parts.utc_offset = sign * (hours * 3_600 + minutes * 60);
+                    },
+
+                _ => ()
+            }
+        }
+    }
+
+    if index != bytes.len()
+    {
+        return Err(format!("Unexpected characters at position {}.", index + 1));
+    }
+
+    // Now that we know the actual date, compute the derived fields.
+    let days = days_from_civil(parts.year, parts.month, parts.day);
+
+    parts.weekday = (days + 4).rem_euclid(7);
+    parts.year_day = days - days_from_civil(parts.year, 1, 1) + 1;
+
+    Ok(parts)
+}
+
+
+
+/// Get the current time as whole seconds since the Unix epoch.
+///
+/// Signature: ` -- seconds`
+fn word_time_now(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let ( seconds, _ ) = epoch_now(interpreter)?;
+
+    interpreter.push(seconds.to_value());
+    Ok(())
+}
+
+/// Get the current time as nanoseconds since the Unix epoch.
+///
+/// Signature: ` -- nanoseconds`
+fn word_time_now_ns(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let ( seconds, nanoseconds ) = epoch_now(interpreter)?;
+
+    interpreter.push((seconds * 1_000_000_000 + nanoseconds).to_value());
+    Ok(())
+}
+
+/// Get a monotonic clock reading in nanoseconds.  The value is only meaningful when compared with
+/// other readings, which makes it useful for measuring elapsed time.
+///
+/// Signature: ` -- nanoseconds`
+fn word_time_monotonic(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let elapsed = MONOTONIC_START.elapsed().as_nanos() as i64;
+
+    interpreter.push(elapsed.to_value());
+    Ok(())
+}
+
+/// Sleep the current thread for the given number of milliseconds.
+///
+/// Signature: `milliseconds -- `
+fn word_time_sleep(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let milliseconds = interpreter.pop_as_float()?;
+
+    match Duration::try_from_secs_f64(milliseconds / 1_000.0)
+    {
+        Ok(duration) => sleep(duration),
+        Err(_)       => script_error(interpreter, format!("Can not sleep for {} milliseconds.",
+                                                          milliseconds))?
+    }
+
+    Ok(())
+}
+
+/// Break a timestamp into it's calendar parts in UTC.
+///
+/// Signature: `seconds -- time-parts`
+fn word_time_parts_utc(interpreter: &mut dyn Interpreter,
+                       definition: &DataObjectDefinitionPtr) -> error::Result<()>
+{
+    let ( seconds, nanoseconds ) = pop_timestamp(interpreter)?;
+    let parts = TimeParts::from_epoch(seconds, nanoseconds, 0);
+
+    interpreter.push(parts.to_value(definition));
+    Ok(())
+}
+
+/// Break a timestamp into it's calendar parts in the local time zone.
+///
+/// Signature: `seconds -- time-parts`
+fn word_time_parts_local(interpreter: &mut dyn Interpreter,
+                         definition: &DataObjectDefinitionPtr) -> error::Result<()>
+{
+    let ( seconds, nanoseconds ) = pop_timestamp(interpreter)?;
+
+    let utc_offset = match local_utc_offset(seconds)
+        {
+            Some(utc_offset) => utc_offset,
+            None             => return script_error_str(interpreter,
+                                                 "Could not determine the local time zone offset.")
+        };
+
+    let parts = TimeParts::from_epoch(seconds, nanoseconds, utc_offset);
+
+    interpreter.push(parts.to_value(definition));
+    Ok(())
+}
+
+/// Convert calendar parts back into seconds since the Unix epoch.
+///
+/// Signature: `time-parts -- seconds`
+fn word_time_parts_epoch(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let value = interpreter.pop()?;
+    let parts = TimeParts::from_value(interpreter, &value)?;
+
+    interpreter.push(parts.to_epoch().to_value());
+    Ok(())
+}
+
+/// Format calendar parts with a strftime style format string.
+///
+/// Signature: `time-parts format -- string`
+fn word_time_format(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let format = interpreter.pop_as_string()?;
+    let value = interpreter.pop()?;
+    let parts = TimeParts::from_value(interpreter, &value)?;
+
+    match format_time(&parts, &format)
+    {
+        Ok(text)   => interpreter.push(text.to_value()),
+        Err(error) => script_error(interpreter, format!("Could not format time: {}", error))?
+    }
+
+    Ok(())
+}
+
+/// Format calendar parts as an ISO-8601 timestamp.
+///
+/// Signature: `time-parts -- string`
+fn word_time_to_iso(interpreter: &mut dyn Interpreter) -> error::Result<()>
+{
+    let value = interpreter.pop()?;
+    let parts = TimeParts::from_value(interpreter, &value)?;
+
+    interpreter.push(format_iso_8601(&parts).to_value());
+    Ok(())
+}
+
+/// Parse an ISO-8601 timestamp into calendar parts.
+///
+/// Signature: `string -- time-parts`
+fn word_time_from_iso(interpreter: &mut dyn Interpreter,
+                      definition: &DataObjectDefinitionPtr) -> error::Result<()>
+{
+    let text = interpreter.pop_as_string()?;
+
+    match parse_iso_8601(&text)
+    {
+        Ok(parts)  => interpreter.push(parts.to_value(definition)),
+        Err(error) => script_error(interpreter, format!("Could not parse timestamp {}: {}",
+                                                        Value::stringify(&text),
+                                                        error))?
+    }
+
+    Ok(())
+}
+
+
+
+/// Register the `time.parts` structure with the interpreter.
+fn register_time_parts_struct(interpreter: &mut dyn Interpreter) -> DataObjectDefinitionPtr
+{
+    let field_names = TIME_PARTS_FIELDS.iter().map(|name| name.to_string()).collect();
+    let defaults = TIME_PARTS_FIELDS.iter().map(|_| 0i64.to_value()).collect();
+
+    let definition = DataObjectDefinition::new(interpreter,
+                                               "time.parts".to_string(),
+                                               field_names,
+                                               defaults,
+                                               false);
+
+    DataObjectDefinition::create_data_definition_words(interpreter,
+                                                       Some(location_here!()),
+                                                       definition.clone(),
+                                                       false);
+
+    definition
+}
+
+
+/// Find the `time.parts` structure definition, for other modules that need to create timestamps.
+pub fn find_time_parts_definition(interpreter: &dyn Interpreter) -> Option<DataObjectDefinitionPtr>
+{
+    interpreter.structure_definitions()
+               .iter()
+               .filter(|definition| definition.borrow().name() == "time.parts")
+               .last()
+               .cloned()
+}
+
+
+/// Register the date and time words with the given interpreter.
+pub fn register_time_words(interpreter: &mut dyn Interpreter)
+{
+    let definition = register_time_parts_struct(interpreter);
+
+    add_native_word!(interpreter, "time.now", word_time_now,
+        "Get the current time as seconds since the Unix epoch.",
+        " -- seconds");
+
+    add_native_word!(interpreter, "time.now.ns", word_time_now_ns,
+        "Get the current time as nanoseconds since the Unix epoch.",
+        " -- nanoseconds");
+
+    add_native_word!(interpreter, "time.monotonic", word_time_monotonic,
+        "Get a monotonic clock reading in nanoseconds, for measuring elapsed time.",
+        " -- nanoseconds");
+
+    add_native_word!(interpreter, "time.sleep", word_time_sleep,
+        "Sleep for the given number of milliseconds.",
+        "milliseconds -- ");
+
+    let utc_definition = definition.clone();
+
+    add_native_word!(interpreter, "time.parts.utc",
+        move |interpreter: &mut dyn Interpreter|
+        {
+            word_time_parts_utc(interpreter, &utc_definition)
+        },
+        "Break a timestamp in seconds into a time.parts structure in UTC.",
+        "seconds -- time_parts");
+
+    let local_definition = definition.clone();
+
+    add_native_word!(interpreter, "time.parts.local",
+        move |interpreter: &mut dyn Interpreter|
+        {
+            word_time_parts_local(interpreter, &local_definition)
+        },
+        "Break a timestamp in seconds into a time.parts structure in the local time zone.",
+        "seconds -- time_parts");
+
+    add_native_word!(interpreter, "time.parts.epoch", word_time_parts_epoch,
+        "Convert a time.parts structure back into seconds since the Unix epoch.",
+        "time_parts -- seconds");
+
+    add_native_word!(interpreter, "time.format", word_time_format,
+        "Format a time.parts structure using a strftime style format string.",
+        "time_parts format -- string");
+
+    add_native_word!(interpreter, "time.to-iso", word_time_to_iso,
+        "Format a time.parts structure as an ISO-8601 timestamp.",
+        "time_parts -- string");
+
+    let iso_definition = definition.clone();
+
+    add_native_word!(interpreter, "time.from-iso",
+        move |interpreter: &mut dyn Interpreter|
+        {
+            word_time_from_iso(interpreter, &iso_definition)
+        },
+        "Parse an ISO-8601 timestamp into a time.parts structure.",
+        "string -- time_parts");
+}
@@ -1,15 +1,30 @@
 use std::{ cell::RefCell,
+           cmp::Ordering,
            fmt::{ self,
                   Display,
                   Formatter },
+           fs::File,
+           hash::{ Hash,
+                   Hasher },
            os::raw::c_void,
            rc::Rc };
+use memmap2::{ Mmap, MmapMut };
 use crate::runtime::data_structures::value::{ DeepClone,
                                               Value,
                                               ToValue };
 
 
 
+/// The order that the bytes of multi-byte integers and floats are stored in within a buffer.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub enum ByteOrder
+{
+    Little,
+    Big
+}
+
+
+
 /// Trait to represent byte buffers.  It uses a cursor to perform reads and writes.  If a read or
 /// write would exceed the bounds of the buffer the operation will panic.
 ///
@@ -26,8 +41,8 @@ pub trait Buffer
     /// Get a pointer to the buffer's raw bytes.
     fn byte_ptr(&self) -> *const c_void;
 
-    /// Get a mutable pointer to the buffer's raw bytes.
-    fn byte_ptr_mut(&mut self) -> *mut c_void;
+    /// Get a mutable pointer to the buffer's raw bytes, or none if the buffer can't be written to.
+    fn byte_ptr_mut(&mut self) -> Option<*mut c_void>;
 
     /// Resize the buffer to a new size.  If the new size is larger the buffer will be padded with
     /// zeros.  If the new size is smaller the buffer will be truncated.
@@ -36,13 +51,36 @@ pub trait Buffer
     /// Get the length of the buffer.
     fn len(&self) -> usize;
 
+    /// Can the buffer's size be changed?  Buffers that view memory owned by something else, like a
+    /// mapped file, have a fixed size.
+    fn is_resizable(&self) -> bool
+    {
+        true
+    }
+
+    /// Can the buffer be written to?
+    fn is_writable(&self) -> bool
+    {
+        true
+    }
+
+    /// Get a view of all the bytes in the buffer.
+    fn bytes(&self) -> &[u8]
+    {
+        unsafe
+        {
+            std::slice::from_raw_parts(self.byte_ptr() as *const u8, self.len())
+        }
+    }
+
 
     /// Get the current cursor position in the buffer.
     fn position(&self) -> usize;
 
 
-    /// Get a mutable pointer to the current cursor position in the buffer.
-    fn position_ptr_mut(&mut self) -> *mut c_void;
+    /// Get a mutable pointer to the byte at the current cursor position in the buffer, or none if
+    /// the buffer can't be written to.
+    fn position_ptr_mut(&mut self) -> Option<*mut c_void>;
 
 
     /// Set the cursor position in the buffer.  If the position is greater than the buffer size the
@@ -60,7 +98,10 @@ pub trait Buffer
     /// operation will panic.
     ///
     /// If the write would exceed the bounds of the buffer the operation will panic.
-    fn write_int(&mut self, byte_size: usize, value: i64);
+    fn write_int(&mut self, byte_size: usize, value: i64)
+    {
+        self.write_int_ordered(byte_size, value, ByteOrder::Little);
+    }
 
     /// Read an integer from the buffer.  The integer will be read in little endian format.
     ///
@@ -68,7 +109,10 @@ pub trait Buffer
     /// operation will panic.
     ///
     /// If the read would exceed the bounds of the buffer the operation will panic.
-    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64;
+    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64
+    {
+        self.read_int_ordered(byte_size, is_signed, ByteOrder::Little)
+    }
 
 
     /// Write a float to the buffer.  The float will be written in little endian format.
@@ -77,7 +121,10 @@ pub trait Buffer
     /// will panic.
     ///
     /// If the write would exceed the bounds of the buffer the operation will panic.
-    fn write_float(&mut self, byte_size: usize, value: f64);
+    fn write_float(&mut self, byte_size: usize, value: f64)
+    {
+        self.write_float_ordered(byte_size, value, ByteOrder::Little);
+    }
 
     /// Read a float from the buffer.  The float will be read in little endian format.
     ///
@@ -85,7 +132,24 @@ pub trait Buffer
     /// will panic.
     ///
     /// If the read would exceed the bounds of the buffer the operation will panic.
-    fn read_float(&mut self, byte_size: usize) -> f64;
+    fn read_float(&mut self, byte_size: usize) -> f64
+    {
+        self.read_float_ordered(byte_size, ByteOrder::Little)
+    }
+
+
+    /// Write an integer to the buffer in the given byte order.  Otherwise the same as write_int.
+    fn write_int_ordered(&mut self, byte_size: usize, value: i64, order: ByteOrder);
+
+    /// Read an integer from the buffer in the given byte order.  Otherwise the same as read_int.
+    fn read_int_ordered(&mut self, byte_size: usize, is_signed: bool, order: ByteOrder) -> i64;
+
+
+    /// Write a float to the buffer in the given byte order.  Otherwise the same as write_float.
+    fn write_float_ordered(&mut self, byte_size: usize, value: f64, order: ByteOrder);
+
+    /// Read a float from the buffer in the given byte order.  Otherwise the same as read_float.
+    fn read_float_ordered(&mut self, byte_size: usize, order: ByteOrder) -> f64;
 
 
     /// Write a string to the buffer.  If the string is larger than the given size, it will be
@@ -111,14 +175,7 @@ impl Display for dyn Buffer
         // 00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  | ........ ........ |
         // 00000010  00 00 00 00 00 00                                 | ......            |
 
-        let bytes =
-            unsafe
-            {
-                let ptr = self.byte_ptr();
-                let ptr_u8 = ptr as *const u8;
-
-                std::slice::from_raw_parts(ptr_u8, self.len())
-            };
+        let bytes = self.bytes();
 
         writeln!(f,
               "          00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  | 01234567 89abcdef |")?;
@@ -186,10 +243,208 @@ impl Display for dyn Buffer
 
 
 
+/// Buffers are equal if they hold the same bytes and their cursors are at the same position.
+impl PartialEq for dyn Buffer
+{
+    fn eq(&self, other: &dyn Buffer) -> bool
+    {
+        self.position() == other.position() && self.bytes() == other.bytes()
+    }
+}
+
+
+/// Order buffers by their bytes first, then by their cursor positions.
+impl PartialOrd for dyn Buffer
+{
+    fn partial_cmp(&self, other: &dyn Buffer) -> Option<Ordering>
+    {
+        match self.bytes().partial_cmp(other.bytes())
+        {
+            Some(Ordering::Equal) => self.position().partial_cmp(&other.position()),
+            ordering              => ordering
+        }
+    }
+}
+
+
+/// Hash a buffer based on it's bytes and cursor position.
+impl Hash for dyn Buffer
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.bytes().hash(state);
+        self.position().hash(state);
+    }
+}
+
+
+
 /// Generic pointer to a buffer object.
 pub type BufferPtr = Rc<RefCell<dyn Buffer>>;
 
 
+/// Deep copy any kind of buffer for the Value type.  The copy is always a new ByteBuffer.
+impl DeepClone for BufferPtr
+{
+    fn deep_clone(&self) -> Value
+    {
+        let source = self.borrow();
+        let mut new_buffer = ByteBuffer::from_bytes(source.bytes().to_vec());
+
+        new_buffer.current_position = source.position();
+
+        let new_buffer: BufferPtr = Rc::new(RefCell::new(new_buffer));
+        new_buffer.to_value()
+    }
+}
+
+
+
+/// Write an integer of the given size into a byte slice at a given position.  The caller is
+/// responsible for making sure the write is in bounds.
+fn write_int_at(bytes: &mut [u8], position: usize, byte_size: usize, value: i64, order: ByteOrder)
+{
+    if    byte_size != 1
+       && byte_size != 2
+       && byte_size != 4
+       && byte_size != 8
+    {
+        panic!("Invalid byte size for integer write {}.", byte_size);
+    }
+
+    let destination = &mut bytes[position..position + byte_size];
+
+    match order
+    {
+        ByteOrder::Little => destination.copy_from_slice(&value.to_le_bytes()[0..byte_size]),
+        ByteOrder::Big    => destination.copy_from_slice(&value.to_be_bytes()[8 - byte_size..8])
+    }
+}
+
+
+/// Read an integer of the given size from a byte slice at a given position.  Signed values are sign
+/// extended to the full 64 bits, except for single bytes which have always been read as unsigned.
+fn read_int_at(bytes: &[u8],
+               position: usize,
+               byte_size: usize,
+               is_signed: bool,
+               order: ByteOrder) -> i64
+{
+    if    byte_size != 1
+       && byte_size != 2
+       && byte_size != 4
+       && byte_size != 8
+    {
+        panic!("Invalid byte size for integer read {}.", byte_size);
+    }
+
+    let source = &bytes[position..position + byte_size];
+    let mut value_bytes = [0; 8];
+
+    // Gather the bytes in little endian order so that both byte orders are assembled the same way.
+    match order
+    {
+        ByteOrder::Little => value_bytes[0..byte_size].copy_from_slice(source),
+        ByteOrder::Big    =>
+            {
+                for ( index, byte ) in source.iter().rev().enumerate()
+                {
+                    value_bytes[index] = *byte;
+                }
+            }
+    }
+
+    let value = u64::from_le_bytes(value_bytes);
+
+    if is_signed && byte_size > 1 && byte_size < 8
+    {
+        let shift = 64 - (byte_size * 8);
+        ((value << shift) as i64) >> shift
+    }
+    else
+    {
+        value as i64
+    }
+}
+
+
+/// Write a float of the given size into a byte slice at a given position.
+fn write_float_at(bytes: &mut [u8],
+                  position: usize,
+                  byte_size: usize,
+                  value: f64,
+                  order: ByteOrder)
+{
+    let value_bytes = match ( byte_size, order )
+        {
+            ( 4, ByteOrder::Little ) => (value as f32).to_le_bytes().to_vec(),
+            ( 4, ByteOrder::Big    ) => (value as f32).to_be_bytes().to_vec(),
+            ( 8, ByteOrder::Little ) => value.to_le_bytes().to_vec(),
+            ( 8, ByteOrder::Big    ) => value.to_be_bytes().to_vec(),
+            _                        => panic!("Invalid byte size for float write {}.", byte_size)
+        };
+
+    bytes[position..position + byte_size].copy_from_slice(&value_bytes);
+}
+
+
+/// Read a float of the given size from a byte slice at a given position.
+fn read_float_at(bytes: &[u8], position: usize, byte_size: usize, order: ByteOrder) -> f64
+{
+    match byte_size
+    {
+        4 =>
+            {
+                let mut value_bytes = [0; 4];
+
+                value_bytes.copy_from_slice(&bytes[position..position + 4]);
+
+                match order
+                {
+                    ByteOrder::Little => f32::from_le_bytes(value_bytes) as f64,
+                    ByteOrder::Big    => f32::from_be_bytes(value_bytes) as f64
+                }
+            },
+
+        8 =>
+            {
+                let mut value_bytes = [0; 8];
+
+                value_bytes.copy_from_slice(&bytes[position..position + 8]);
+
+                match order
+                {
+                    ByteOrder::Little => f64::from_le_bytes(value_bytes),
+                    ByteOrder::Big    => f64::from_be_bytes(value_bytes)
+                }
+            },
+
+        _ => panic!("Invalid byte size for float read {}.", byte_size)
+    }
+}
+
+
+/// Write a zero padded string of a fixed size into a byte slice at a given position.
+fn write_string_at(bytes: &mut [u8], position: usize, max_size: usize, value: &str)
+{
+    let string_bytes = value.as_bytes();
+    let write_bytes = string_bytes.len().min(max_size);
+
+    bytes[position..position + write_bytes].copy_from_slice(&string_bytes[0..write_bytes]);
+    bytes[position + write_bytes..position + max_size].fill(0);
+}
+
+
+/// Read a zero terminated string of up to a fixed size from a byte slice at a given position.
+fn read_string_at(bytes: &[u8], position: usize, max_size: usize) -> String
+{
+    let bytes = &bytes[position..position + max_size];
+    let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(max_size);
+
+    String::from_utf8_lossy(&bytes[0..end]).to_string()
+}
+
+
 
 /// A concrete ByteBuffer data structure.  It uses a cursor to perform reads and writes.  If a read
 /// or write would exceed the bounds of the buffer the operation will panic.
@@ -223,9 +478,9 @@ impl Buffer for ByteBuffer
         self.buffer.as_ptr() as *const c_void
     }
 
-    fn byte_ptr_mut(&mut self) -> *mut c_void
+    fn byte_ptr_mut(&mut self) -> Option<*mut c_void>
     {
-        self.buffer.as_mut_ptr() as *mut c_void
+        Some(self.buffer.as_mut_ptr() as *mut c_void)
     }
 
     fn resize(&mut self, new_size: usize)
@@ -248,9 +503,10 @@ impl Buffer for ByteBuffer
         self.current_position
     }
 
-    fn position_ptr_mut(&mut self) -> *mut c_void
+    fn position_ptr_mut(&mut self) -> Option<*mut c_void>
     {
-        &mut self.current_position as *mut usize as *mut c_void
+        let position = self.current_position;
+        Some(self.byte_ptr_mut()?.wrapping_byte_add(position))
     }
 
     fn set_position(&mut self, position: usize)
@@ -270,183 +526,258 @@ impl Buffer for ByteBuffer
         self.set_position(self.current_position + increment);
     }
 
-    fn write_int(&mut self, byte_size: usize, value: i64)
+    fn write_int_ordered(&mut self, byte_size: usize, value: i64, order: ByteOrder)
     {
-        let bytes = match byte_size
-            {
-                1 => value.to_le_bytes()[0..1].to_vec(),
-                2 => value.to_le_bytes()[0..2].to_vec(),
-                4 => value.to_le_bytes()[0..4].to_vec(),
-                8 => value.to_le_bytes()[0..8].to_vec(),
-                _ => panic!("Invalid byte size for integer write {}.", byte_size)
-            };
+        let position = self.current_position;
 
+        self.increment_position(byte_size);
+        write_int_at(&mut self.buffer, position, byte_size, value, order);
+    }
+
+    fn read_int_ordered(&mut self, byte_size: usize, is_signed: bool, order: ByteOrder) -> i64
+    {
         let position = self.current_position;
 
         self.increment_position(byte_size);
-        self.buffer[position..position + byte_size].copy_from_slice(&bytes);
+        read_int_at(&self.buffer, position, byte_size, is_signed, order)
     }
 
-    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64
+    fn write_float_ordered(&mut self, byte_size: usize, value: f64, order: ByteOrder)
     {
         let position = self.current_position;
 
         self.increment_position(byte_size);
+        write_float_at(&mut self.buffer, position, byte_size, value, order);
+    }
 
-        match byte_size
-        {
-            1 =>
-                {
-                    let mut bytes = [0; 1];
+    fn read_float_ordered(&mut self, byte_size: usize, order: ByteOrder) -> f64
+    {
+        let position = self.current_position;
 
-                    bytes.copy_from_slice(&self.buffer[position..position + 1]);
-                    bytes[0] as i64
-                },
-            2 =>
-                {
-                    let mut bytes = [0; 2];
+        self.increment_position(byte_size);
+        read_float_at(&self.buffer, position, byte_size, order)
+    }
 
-                    bytes.copy_from_slice(&self.buffer[position..position + 2]);
+    fn write_string(&mut self, max_size: usize, value: &String)
+    {
+        let position = self.current_position;
 
-                    if is_signed
-                    {
-                        i16::from_le_bytes(bytes) as i64
-                    }
-                    else
-                    {
-                        u16::from_le_bytes(bytes) as i64
-                    }
-                },
+        self.increment_position(max_size);
+        write_string_at(&mut self.buffer, position, max_size, value);
+    }
 
-            4 =>
-                {
-                    let mut bytes = [0; 4];
+    fn read_string(&mut self, max_size: usize) -> String
+    {
+        let position = self.current_position;
 
-                    bytes.copy_from_slice(&self.buffer[position..position + 4]);
+        self.increment_position(max_size);
+        read_string_at(&self.buffer, position, max_size)
+    }
+}
 
-                    if is_signed
-                    {
-                        i32::from_le_bytes(bytes) as i64
-                    }
-                    else
-                    {
-                        u32::from_le_bytes(bytes) as i64
-                    }
-                },
 
-            8 =>
-                {
-                    let mut bytes = [0; 8];
+/// Display the byte buffer in a hex dump format.
+impl Display for ByteBuffer
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result
+    {
+        let buffer = self as &dyn Buffer;
+        write!(f, "{}", buffer)
+    }
+}
 
-                    bytes.copy_from_slice(&self.buffer[position..position + 8]);
 
-                    if is_signed
-                    {
-                        i64::from_le_bytes(bytes)
-                    }
-                    else
-                    {
-                        u64::from_le_bytes(bytes) as i64
-                    }
-                },
 
-            _ => panic!("Invalid byte size for integer read {}.", byte_size)
-        }
+impl ByteBuffer
+{
+    /// Create a new byte buffer of the given size.
+    pub fn new(new_len: usize) -> ByteBuffer
+    {
+        let mut buffer = Vec::new();
+
+        buffer.resize(new_len, 0);
+
+        ByteBuffer
+            {
+                buffer,
+                current_position: 0
+            }
     }
 
-    fn write_float(&mut self, byte_size: usize, value: f64)
+    /// Create a new byte buffer that takes ownership of existing bytes.
+    pub fn from_bytes(bytes: Vec<u8>) -> ByteBuffer
     {
-        let bytes = match byte_size
+        ByteBuffer
             {
-                4 => (value as f32).to_le_bytes()[0..4].to_vec(),
-                8 => value.to_le_bytes()[0..8].to_vec(),
-                _ => panic!("Invalid byte size for integer write {}.", byte_size)
-            };
+                buffer: bytes,
+                current_position: 0
+            }
+    }
 
-        let position = self.current_position;
+    /// Create a new byte buffer reference of the given size.
+    pub fn new_ptr(new_len: usize) -> ByteBufferPtr
+    {
+        Rc::new(RefCell::new(ByteBuffer::new(new_len)))
+    }
 
-        self.increment_position(byte_size);
-        self.buffer[position..position + byte_size].copy_from_slice(&bytes);
+    pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
     }
+}
 
-    fn read_float(&mut self, byte_size: usize) -> f64
-    {
-        let position = self.current_position;
 
-        self.increment_position(byte_size);
 
-        match byte_size
+/// The memory of a mapped file, either read-only or writable.
+enum MappedMemory
+{
+    ReadOnly(Mmap),
+    ReadWrite(MmapMut)
+}
+
+
+/// A concrete implementation of the Buffer trait that views the contents of a memory mapped file.
+/// Reads and writes go directly to the mapped memory so large files can be worked with without
+/// copying them.
+///
+/// The size of the buffer is fixed to the size of the file when it was mapped.  Writes to a
+/// writable mapping are shared with the underlying file.
+pub struct MappedBuffer
+{
+    /// The mapped memory of the file.
+    memory: MappedMemory,
+
+    /// The buffer's cursor position within the mapped memory.
+    current_position: usize
+}
+
+
+impl Buffer for MappedBuffer
+{
+    fn byte_ptr(&self) -> *const c_void
+    {
+        self.bytes().as_ptr() as *const c_void
+    }
+
+    fn byte_ptr_mut(&mut self) -> Option<*mut c_void>
+    {
+        // A read-only mapping is backed by memory that can't be written to at all, so it never hands
+        // out a mutable pointer.
+        match &mut self.memory
         {
-            4 =>
-                {
-                    let mut bytes = [0; 4];
+            MappedMemory::ReadOnly(_)       => None,
+            MappedMemory::ReadWrite(memory) => Some(memory.as_mut_ptr() as *mut c_void)
+        }
+    }
 
-                    bytes.copy_from_slice(&self.buffer[position..position + 4]);
-                    f32::from_le_bytes(bytes) as f64
-                },
+    fn resize(&mut self, _new_size: usize)
+    {
+        panic!("Attempted to resize a memory mapped buffer.");
+    }
 
-            8 =>
-                {
-                    let mut bytes = [0; 8];
+    fn len(&self) -> usize
+    {
+        self.bytes().len()
+    }
 
-                    bytes.copy_from_slice(&self.buffer[position..position + 8]);
-                    f64::from_le_bytes(bytes)
-                },
+    fn is_resizable(&self) -> bool
+    {
+        false
+    }
 
-            _ => panic!("Invalid byte size for integer read {}.", byte_size)
+    fn is_writable(&self) -> bool
+    {
+        matches!(self.memory, MappedMemory::ReadWrite(_))
+    }
+
+    fn bytes(&self) -> &[u8]
+    {
+        match &self.memory
+        {
+            MappedMemory::ReadOnly(memory)  => memory,
+            MappedMemory::ReadWrite(memory) => memory
         }
     }
 
-    fn write_string(&mut self, max_size: usize, value: &String)
+    fn position(&self) -> usize
     {
-        let bytes = value.as_bytes();
-        let write_bytes = bytes.len().min(max_size);
+        self.current_position
+    }
 
+    fn position_ptr_mut(&mut self) -> Option<*mut c_void>
+    {
         let position = self.current_position;
-        self.increment_position(max_size);
-
-        self.buffer[position..position + write_bytes].copy_from_slice(&bytes[0..write_bytes]);
+        Some(self.byte_ptr_mut()?.wrapping_byte_add(position))
+    }
 
-        if write_bytes < max_size
+    fn set_position(&mut self, position: usize)
+    {
+        if position > self.len()
         {
-            for i in position + write_bytes..position + max_size
-            {
-                self.buffer[i] = 0;
-            }
+            panic!("Attempted to set position to {} in a buffer of size {}.",
+                   position,
+                   self.len());
         }
+
+        self.current_position = position;
     }
 
-    fn read_string(&mut self, max_size: usize) -> String
+    fn increment_position(&mut self, increment: usize)
+    {
+        self.set_position(self.current_position + increment);
+    }
+
+    fn write_int_ordered(&mut self, byte_size: usize, value: i64, order: ByteOrder)
     {
         let position = self.current_position;
-        self.increment_position(max_size);
 
-        let bytes = &self.buffer[position..position + max_size];
-        let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(max_size);
+        self.increment_position(byte_size);
+        write_int_at(self.bytes_mut(), position, byte_size, value, order);
+    }
+
+    fn read_int_ordered(&mut self, byte_size: usize, is_signed: bool, order: ByteOrder) -> i64
+    {
+        let position = self.current_position;
 
-        String::from_utf8_lossy(&bytes[0..end]).to_string()
+        self.increment_position(byte_size);
+        read_int_at(self.bytes(), position, byte_size, is_signed, order)
     }
-}
 
+    fn write_float_ordered(&mut self, byte_size: usize, value: f64, order: ByteOrder)
+    {
+        let position = self.current_position;
 
-/// Deep copy the byte buffer for the Value type.
-impl DeepClone for ByteBufferPtr
-{
-    fn deep_clone(&self) -> Value
+        self.increment_position(byte_size);
+        write_float_at(self.bytes_mut(), position, byte_size, value, order);
+    }
+
+    fn read_float_ordered(&mut self, byte_size: usize, order: ByteOrder) -> f64
     {
-        let new_buffer = ByteBuffer::new_ptr(self.borrow().len());
+        let position = self.current_position;
 
-        new_buffer.borrow_mut().buffer
-                               .copy_from_slice(&self.borrow().buffer[0..self.borrow().len()]);
-        new_buffer.borrow_mut().current_position = self.borrow().current_position;
+        self.increment_position(byte_size);
+        read_float_at(self.bytes(), position, byte_size, order)
+    }
 
-        new_buffer.to_value()
+    fn write_string(&mut self, max_size: usize, value: &String)
+    {
+        let position = self.current_position;
+
+        self.increment_position(max_size);
+        write_string_at(self.bytes_mut(), position, max_size, value);
+    }
+
+    fn read_string(&mut self, max_size: usize) -> String
+    {
+        let position = self.current_position;
+
+        self.increment_position(max_size);
+        read_string_at(self.bytes(), position, max_size)
     }
 }
 
 
-/// Display the byte buffer in a hex dump format.
-impl Display for ByteBuffer
+/// Display the mapped buffer in a hex dump format.
+impl Display for MappedBuffer
 {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result
     {
@@ -456,31 +787,48 @@ impl Display for ByteBuffer
 }
 
 
-
-impl ByteBuffer
+impl MappedBuffer
 {
-    /// Create a new byte buffer of the given size.
-    pub fn new(new_len: usize) -> ByteBuffer
+    /// Map the contents of an open file into memory.  A writable mapping requires the file to have
+    /// been opened for both reading and writing.
+    pub fn new(file: &File, writable: bool) -> std::io::Result<MappedBuffer>
     {
-        let mut buffer = Vec::new();
-
-        buffer.resize(new_len, 0);
+        // The mapping is only valid as long as the file isn't truncated by another process while
+        // it's mapped, which is the same caveat every mmap user lives with.
+        let memory = unsafe
+            {
+                if writable
+                {
+                    MappedMemory::ReadWrite(MmapMut::map_mut(file)?)
+                }
+                else
+                {
+                    MappedMemory::ReadOnly(Mmap::map(file)?)
+                }
+            };
 
-        ByteBuffer
+        Ok(MappedBuffer
             {
-                buffer,
+                memory,
                 current_position: 0
-            }
+            })
     }
 
-    /// Create a new byte buffer reference of the given size.
-    pub fn new_ptr(new_len: usize) -> ByteBufferPtr
+    /// Map an open file into memory and get a generic buffer reference to it.
+    pub fn new_ptr(file: &File, writable: bool) -> std::io::Result<BufferPtr>
     {
-        Rc::new(RefCell::new(ByteBuffer::new(new_len)))
+        Ok(Rc::new(RefCell::new(MappedBuffer::new(file, writable)?)))
     }
 
-    pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
-        &mut self.buffer
+    /// Get mutable access to the mapped bytes.  Panics if the mapping is read-only.
+    fn bytes_mut(&mut self) -> &mut [u8]
+    {
+        match &mut self.memory
+        {
+            MappedMemory::ReadWrite(memory) => memory,
+            MappedMemory::ReadOnly(_)       =>
+                panic!("Attempted to write to a read-only mapped buffer.")
+        }
     }
 }
 
@@ -510,24 +858,12 @@ impl Buffer for SubBuffer
 {
     fn byte_ptr(&self) -> *const c_void
     {
-        let position = self.parent.borrow().position();
-
-        self.parent.borrow_mut().set_position(self.start);
-        let ptr = self.parent.borrow().byte_ptr();
-        self.parent.borrow_mut().set_position(position);
-
-        ptr
+        self.parent.borrow().byte_ptr().wrapping_byte_add(self.start)
     }
 
-    fn byte_ptr_mut(&mut self) -> *mut c_void
+    fn byte_ptr_mut(&mut self) -> Option<*mut c_void>
     {
-        let position = self.parent.borrow().position();
-
-        self.parent.borrow_mut().set_position(self.start);
-        let ptr = self.parent.borrow_mut().byte_ptr_mut();
-        self.parent.borrow_mut().set_position(position);
-
-        ptr
+        Some(self.parent.borrow_mut().byte_ptr_mut()?.wrapping_byte_add(self.start))
     }
 
     fn resize(&mut self, new_size: usize)
@@ -544,23 +880,30 @@ impl Buffer for SubBuffer
 
     fn len(&self) -> usize
     {
-        self.end - self.start
+        // If the parent has shrunk since the sub-buffer was created, only the part of the range
+        // that still exists is visible.
+        self.end.min(self.parent.borrow().len()).saturating_sub(self.start)
     }
 
-    fn position(&self) -> usize
+    fn is_resizable(&self) -> bool
     {
-        self.current_position
+        false
     }
 
-    fn position_ptr_mut(&mut self) -> *mut c_void
+    fn is_writable(&self) -> bool
     {
-        let position = self.parent.borrow().position();
+        self.parent.borrow().is_writable()
+    }
 
-        self.parent.borrow_mut().set_position(self.start + self.current_position);
-        let ptr = self.parent.borrow_mut().byte_ptr_mut();
-        self.parent.borrow_mut().set_position(position);
+    fn position(&self) -> usize
+    {
+        self.current_position
+    }
 
-        ptr
+    fn position_ptr_mut(&mut self) -> Option<*mut c_void>
+    {
+        let position = self.current_position;
+        Some(self.byte_ptr_mut()?.wrapping_byte_add(position))
     }
 
     fn set_position(&mut self, position: usize)
@@ -580,21 +923,21 @@ impl Buffer for SubBuffer
         self.set_position(self.current_position + increment);
     }
 
-    fn write_int(&mut self, byte_size: usize, value: i64)
+    fn write_int_ordered(&mut self, byte_size: usize, value: i64, order: ByteOrder)
     {
         {
             let mut parent = self.parent.borrow_mut();
             let position = parent.position();
 
             parent.set_position(self.start + self.current_position);
-            parent.write_int(byte_size, value);
+            parent.write_int_ordered(byte_size, value, order);
             parent.set_position(position);
         }
 
         self.increment_position(byte_size);
     }
 
-    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64
+    fn read_int_ordered(&mut self, byte_size: usize, is_signed: bool, order: ByteOrder) -> i64
     {
         let value =
             {
@@ -602,7 +945,7 @@ impl Buffer for SubBuffer
                 let position = parent.position();
 
                 parent.set_position(self.start + self.current_position);
-                let value = parent.read_int(byte_size, is_signed);
+                let value = parent.read_int_ordered(byte_size, is_signed, order);
                 parent.set_position(position);
 
                 value
@@ -613,21 +956,21 @@ impl Buffer for SubBuffer
         value
     }
 
-    fn write_float(&mut self, byte_size: usize, value: f64)
+    fn write_float_ordered(&mut self, byte_size: usize, value: f64, order: ByteOrder)
     {
         {
             let mut parent = self.parent.borrow_mut();
             let position = parent.position();
 
             parent.set_position(self.start + self.current_position);
-            parent.write_float(byte_size, value);
+            parent.write_float_ordered(byte_size, value, order);
             parent.set_position(position);
         }
 
         self.increment_position(byte_size);
     }
 
-    fn read_float(&mut self, byte_size: usize) -> f64
+    fn read_float_ordered(&mut self, byte_size: usize, order: ByteOrder) -> f64
     {
         let value =
             {
@@ -635,7 +978,7 @@ impl Buffer for SubBuffer
                 let position = parent.position();
 
                 parent.set_position(self.start + self.current_position);
-                let value = parent.read_float(byte_size);
+                let value = parent.read_float_ordered(byte_size, order);
                 parent.set_position(position);
 
                 value
@@ -694,29 +1037,29 @@ impl Display for SubBuffer
 
 impl SubBuffer
 {
-    ///// Create a new sub-buffer from a parent buffer with a specified range inside of that buffer.
-    //fn new(parent: BufferPtr, start: usize, end: usize) -> SubBuffer
-    //{
-    //    let parent_len = parent.borrow().len();
-    //
-    //    if    start > parent_len
-    //       || end > parent_len
-    //    {
-    //        panic!("Attempted to create a sub-buffer with a range outside of the parent buffer.");
-    //    }
-    //
-    //    SubBuffer
-    //        {
-    //            parent,
-    //            start,
-    //            end,
-    //            current_position: 0
-    //        }
-    //}
-    //
-    ///// Create a new sub-buffer ptr from a parent buffer within a specified range.
-    //fn new_ptr(parent: BufferPtr, start: usize, end: usize) -> BufferPtr
-    //{
-    //    Rc::new(RefCell::new(SubBuffer::new(parent, start, end)))
-    //}
+    /// Create a new sub-buffer from a parent buffer with a specified range inside of that buffer.
+    pub fn new(parent: BufferPtr, start: usize, end: usize) -> SubBuffer
+    {
+        let parent_len = parent.borrow().len();
+
+        if    start > end
+           || end > parent_len
+        {
+            panic!("Attempted to create a sub-buffer with a range outside of the parent buffer.");
+        }
+
+        SubBuffer
+            {
+                parent,
+                start,
+                end,
+                current_position: 0
+            }
+    }
+
+    /// Create a new sub-buffer ptr from a parent buffer within a specified range.
+    pub fn new_ptr(parent: BufferPtr, start: usize, end: usize) -> BufferPtr
+    {
+        Rc::new(RefCell::new(SubBuffer::new(parent, start, end)))
+    }
 }
@@ -6,7 +6,8 @@ use std::{ cmp::Ordering,
            hash::{ Hash, Hasher } };
 use crate::{ lang::source_buffer::SourceLocation,
              runtime::{ error::{ self, script_error },
-                        data_structures::{ contextual_list::ContextualList,
+                        data_structures::{ byte_buffer::ByteOrder,
+                                           contextual_list::ContextualList,
                                            dictionary::{ WordRuntime,
                                                          WordType,
                                                          WordVisibility },
@@ -21,17 +22,136 @@ use crate::{ lang::source_buffer::SourceLocation,
 
 
 
+/// The largest string or padding entry a binary layout can hold.
+const MAX_BINARY_FIELD_SIZE: usize = u32::MAX as usize;
+
+
+/// How a single entry of a structure's binary layout is stored in a byte buffer.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub enum BinaryField
+{
+    /// An integer of the given byte size, signedness, and byte order.
+    Int(usize, bool, ByteOrder),
+
+    /// A float of the given byte size and byte order.
+    Float(usize, ByteOrder),
+
+    /// A boolean stored as a single byte.
+    Bool,
+
+    /// A zero padded string of a fixed byte size.
+    String(usize),
+
+    /// Bytes of padding that don't belong to any field.
+    Padding(usize)
+}
+
+
+impl BinaryField
+{
+    /// Parse a field type from it's textual description.  The supported types are:
+    ///
+    ///    - i8, u8, i16, u16, i32, u32, i64, u64, f32, f64 with an optional le or be suffix for the
+    ///      byte order.  Little endian is used if no order is given.
+    ///    - bool
+    ///    - string:<size>
+    ///    - pad:<size>
+    ///
+    /// String and padding sizes can't be larger than MAX_BINARY_FIELD_SIZE bytes.
+    pub fn parse(text: &str) -> Option<BinaryField>
+    {
+        let parse_size = |size: &str|
+            {
+                size.parse().ok().filter(|size| *size <= MAX_BINARY_FIELD_SIZE)
+            };
+
+        if let Some(size) = text.strip_prefix("string:")
+        {
+            return parse_size(size).map(BinaryField::String);
+        }
+
+        if let Some(size) = text.strip_prefix("pad:")
+        {
+            return parse_size(size).map(BinaryField::Padding);
+        }
+
+        if text == "bool"
+        {
+            return Some(BinaryField::Bool);
+        }
+
+        let ( base, order ) =
+            if let Some(base) = text.strip_suffix("be")
+            {
+                ( base, ByteOrder::Big )
+            }
+            else if let Some(base) = text.strip_suffix("le")
+            {
+                ( base, ByteOrder::Little )
+            }
+            else
+            {
+                ( text, ByteOrder::Little )
+            };
+
+        match base
+        {
+            "i8"  => Some(BinaryField::Int(1, true, order)),
+            "u8"  => Some(BinaryField::Int(1, false, order)),
+            "i16" => Some(BinaryField::Int(2, true, order)),
+            "u16" => Some(BinaryField::Int(2, false, order)),
+            "i32" => Some(BinaryField::Int(4, true, order)),
+            "u32" => Some(BinaryField::Int(4, false, order)),
+            "i64" => Some(BinaryField::Int(8, true, order)),
+            "u64" => Some(BinaryField::Int(8, false, order)),
+            "f32" => Some(BinaryField::Float(4, order)),
+            "f64" => Some(BinaryField::Float(8, order)),
+            _     => None
+        }
+    }
+
+    /// How many bytes does this entry take up in a buffer?
+    pub fn size(&self) -> usize
+    {
+        match self
+        {
+            BinaryField::Int(size, _, _) => *size,
+            BinaryField::Float(size, _)  => *size,
+            BinaryField::Bool            => 1,
+            BinaryField::String(size)    => *size,
+            BinaryField::Padding(size)   => *size
+        }
+    }
+
+    /// How many bytes does a whole layout take up in a buffer?  None is returned if the size is too
+    /// large to represent.
+    pub fn layout_size(layout: &[BinaryField]) -> Option<usize>
+    {
+        layout.iter().try_fold(0usize, |size, field| size.checked_add(field.size()))
+    }
+
+    /// Does this entry hold the value of one of the structure's fields?
+    pub fn is_field(&self) -> bool
+    {
+        !matches!(self, BinaryField::Padding(_))
+    }
+}
+
+
+
 /// The definition of a structured data object within a Strange Forth script.  This is used to
 /// define the fields and hold the default value initializers for a structured data object.
 ///
-/// The structure is readonly once created and it's fields are accessed by helper methods.
+/// The structure is readonly once created and it's fields are accessed by helper methods.  The only
+/// exception is the structure's optional binary layout which can be attached after the fact.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Hash)]
 pub struct DataObjectDefinition
 {
     name: String,
     field_names: Vec<String>,
     defaults: Vec<Value>,
-    visibility: WordVisibility
+    visibility: WordVisibility,
+    layout: Option<Vec<BinaryField>>
 }
 
 
@@ -77,7 +197,8 @@ impl DataObjectDefinition
                 name,
                 field_names,
                 defaults,
-                visibility: if is_hidden { WordVisibility::Hidden } else { WordVisibility::Visible }
+                visibility: if is_hidden { WordVisibility::Hidden } else { WordVisibility::Visible },
+                layout: None
             };
 
         let definition_ptr = Rc::new(RefCell::new(definition));
@@ -117,6 +238,21 @@ impl DataObjectDefinition
     }
 
 
+    /// The binary layout used to pack the structure into a byte buffer, if one has been given.
+    pub fn layout(&self) -> &Option<Vec<BinaryField>>
+    {
+        &self.layout
+    }
+
+
+    /// Attach a binary layout to the structure.  The caller is responsible for making sure the
+    /// layout has one entry for every field, not counting padding.
+    pub fn set_layout(&mut self, layout: Vec<BinaryField>)
+    {
+        self.layout = Some(layout);
+    }
+
+
     /// Create the data access words for the given data object definition.
     ///
     /// For example, if the definition is for a structure named "Person" with fields "name", and
@@ -31,3 +31,6 @@ pub mod value_hash;
 
 /// Module for the ByteBuffer data structure.
 pub mod byte_buffer;
+
+/// A table of native resources referred to by scripts through integer handles.
+pub mod resource_table;
@@ -0,0 +1,118 @@
+
+use std::{ any::{ Any,
+                  TypeId },
+           collections::HashMap };
+use crate::runtime::data_structures::contextual_data::ContextualData;
+
+
+
+/// A table of native resources, such as child processes or running hashes, that scripts refer to
+/// by an integer handle.
+///
+/// Resources of different types live side by side in the table and are kept apart by their type,
+/// so the same handle can refer to different resources of different types.  Like the file table,
+/// resources are tracked per marked context so that resetting the interpreter drops everything the
+/// script created since the last mark.
+pub struct ResourceTable
+{
+    /// The next handle to hand out for resources that don't bring their own.
+    next_handle: i64,
+
+    /// The resources indexed by their type and handle.
+    resources: HashMap<( TypeId, i64 ), Box<dyn Any>>,
+
+    /// The resources that were added while each marked context was the current one.
+    scopes: Vec<Vec<( TypeId, i64 )>>
+}
+
+
+impl ContextualData for ResourceTable
+{
+    fn mark_context(&mut self)
+    {
+        self.scopes.push(Vec::new());
+    }
+
+    fn release_context(&mut self)
+    {
+        // Resources outlive the context that created them, anything still in the table now belongs
+        // to the enclosing context.
+        if let Some(scope) = self.scopes.pop()
+        {
+            if let Some(parent) = self.scopes.last_mut()
+            {
+                parent.extend(scope.into_iter().filter(|key| self.resources.contains_key(key)));
+            }
+        }
+    }
+}
+
+
+impl ResourceTable
+{
+    /// Create a new empty resource table.
+    pub fn new() -> ResourceTable
+    {
+        ResourceTable
+            {
+                next_handle: 1,
+                resources: HashMap::new(),
+                scopes: Vec::new()
+            }
+    }
+
+    /// Add a new resource to the table and get the handle generated for it.
+    pub fn add<T: Any>(&mut self, resource: T) -> i64
+    {
+        let handle = self.next_handle;
+
+        self.next_handle += 1;
+        self.insert(handle, resource);
+
+        handle
+    }
+
+    /// Add a resource to the table under a handle chosen by the caller, such as a process id.  Any
+    /// resource of the same type already using the handle is replaced.
+    pub fn insert<T: Any>(&mut self, handle: i64, resource: T)
+    {
+        let key = ( TypeId::of::<T>(), handle );
+
+        if self.resources.insert(key, Box::new(resource)).is_none()
+        {
+            if let Some(scope) = self.scopes.last_mut()
+            {
+                scope.push(key);
+            }
+        }
+    }
+
+    /// Get the resource of the given type for a handle, if it's in the table.
+    pub fn get_mut<T: Any>(&mut self, handle: i64) -> Option<&mut T>
+    {
+        self.resources
+            .get_mut(&( TypeId::of::<T>(), handle ))
+            .and_then(|resource| resource.downcast_mut::<T>())
+    }
+
+    /// Take the resource of the given type for a handle out of the table.
+    pub fn remove<T: Any>(&mut self, handle: i64) -> Option<T>
+    {
+        self.resources
+            .remove(&( TypeId::of::<T>(), handle ))
+            .and_then(|resource| resource.downcast::<T>().ok())
+            .map(|resource| *resource)
+    }
+
+    /// Drop every resource that was added since the current context was marked.
+    pub fn reset(&mut self)
+    {
+        if let Some(scope) = self.scopes.last_mut()
+        {
+            for key in scope.drain(..)
+            {
+                let _ = self.resources.remove(&key);
+            }
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::{ lang::{ tokenizing::{ NumberType,
                                    Token },
                      code::{ ByteCode,
                              pretty_print_code } },
-             runtime::{ data_structures::{ byte_buffer::ByteBufferPtr,
+             runtime::{ data_structures::{ byte_buffer::BufferPtr,
                                            data_object::DataObjectPtr,
                                            value_hash::ValueHashPtr,
                                            value_vec::{ ValueVec,
@@ -51,7 +51,7 @@ pub enum Value
     DataObject(DataObjectPtr),
 
     /// A buffer for holding binary data.
-    ByteBuffer(ByteBufferPtr),
+    ByteBuffer(BufferPtr),
 
     /// A Forth source code token.
     Token(Token),
@@ -341,7 +341,7 @@ value_conversion!(String,        String,     as_string);
 value_conversion!(ValueVecPtr,   Vec,        as_vec);
 value_conversion!(ValueHashPtr,  HashMap,    as_hash_map);
 value_conversion!(DataObjectPtr, DataObject, as_data_object);
-value_conversion!(ByteBufferPtr, ByteBuffer, as_byte_buffer);
+value_conversion!(BufferPtr, ByteBuffer, as_byte_buffer);
 value_conversion!(Token,         Token,      as_token);
 value_conversion!(ByteCode,      Code,       as_code);
 
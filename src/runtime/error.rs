@@ -24,7 +24,10 @@ pub struct ScriptError
     error: String,
 
     /// The script's call stack at the time of the error, if available.
-    call_stack: Option<CallStack>
+    call_stack: Option<CallStack>,
+
+    /// If the script requested the interpreter to exit, this is the exit code that was requested.
+    exit_code: Option<i64>
 }
 
 
@@ -36,9 +39,16 @@ impl Error for ScriptError
 /// When returned from main, convert the error result to an operating system exit code.
 impl Termination for ScriptError
 {
-    /// Because this type represents an error, the exit code is always FAILURE.
+    /// If the script requested an exit, report the requested code.  Otherwise this type represents
+    /// an error and the exit code is FAILURE.
     fn report(self) -> ExitCode
     {
+        if let Some(code) = self.exit_code
+        {
+            // Operating systems only report the low byte of the exit code.
+            return ExitCode::from((code & 0xff) as u8);
+        }
+
         eprintln!("Error: {}", self);
         ExitCode::FAILURE
     }
@@ -94,7 +104,21 @@ impl ScriptError
             {
                 location,
                 error,
-                call_stack
+                call_stack,
+                exit_code: None
+            }
+    }
+
+    /// Create a new ScriptError that requests the interpreter to exit with the given code.  This
+    /// error can not be caught by scripts.
+    pub fn new_exit_request(exit_code: i64) -> ScriptError
+    {
+        ScriptError
+            {
+                location: None,
+                error: format!("Exit requested with code {}.", exit_code),
+                call_stack: None,
+                exit_code: Some(exit_code)
             }
     }
 
@@ -123,6 +147,12 @@ impl ScriptError
     {
         &self.call_stack
     }
+
+    /// If this error is a request to exit the interpreter, the requested exit code.
+    pub fn exit_code(&self) -> Option<i64>
+    {
+        self.exit_code
+    }
 }
 
 
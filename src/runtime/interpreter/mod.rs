@@ -11,8 +11,9 @@ use crate::{ lang::{ code::{ ByteCode,
                      tokenizing::{ NumberType,
                                    Token,
                                    TokenList } },
-             runtime::{ built_ins::ffi_words::FfiInterface,
-                        data_structures::{ byte_buffer::ByteBufferPtr,
+             runtime::{ built_ins::{ ffi_words::FfiInterface,
+                                    io_words::FileTable },
+                        data_structures::{ byte_buffer::BufferPtr,
                                            contextual_data::ContextualData,
                                            contextual_list::ContextualList,
                                            data_object::{ DataDefinitionList,
@@ -23,6 +24,7 @@ use crate::{ lang::{ code::{ ByteCode,
                                                          WordRuntime,
                                                          WordType,
                                                          WordVisibility },
+                                           resource_table::ResourceTable,
                                            value::Value,
                                            value_hash::ValueHashPtr,
                                            value_vec::ValueVecPtr },
@@ -157,7 +159,7 @@ pub trait InterpreterStack
 
     /// Pop the top value and attempt to convert it to a byte buffer.  If the value can not be
     /// converted an error is returned.  We also fail if the stack is empty.
-    fn pop_as_byte_buffer(&mut self) -> error::Result<ByteBufferPtr>;
+    fn pop_as_byte_buffer(&mut self) -> error::Result<BufferPtr>;
 
     /// Pop the top value and attempt to convert it to a token.  If the value can not be converted
     /// an error is returned.  We also fail if the stack is empty.
@@ -499,6 +501,22 @@ pub trait Ffi
 }
 
 
+/// Trait for managing the files, sockets, and pipes owned by the interpreter.
+pub trait Files
+{
+    fn files(&self) -> &FileTable;
+    fn files_mut(&mut self) -> &mut FileTable;
+}
+
+
+/// Trait for managing the native resources, like child processes, owned by the interpreter.
+pub trait Resources
+{
+    fn resources(&self) -> &ResourceTable;
+    fn resources_mut(&mut self) -> &mut ResourceTable;
+}
+
+
 /// Core interpreter trait.
 ///
 /// This trait defines and brings together the traits that define the core functionality of the
@@ -512,7 +530,9 @@ pub trait Interpreter : ContextualData +
                         CodeManagement +
                         WordManagement +
                         ThreadManagement +
-                        Ffi
+                        Ffi +
+                        Files +
+                        Resources
 {
     /// Add a new path to the search path list.  This path will be checked to make sure that it
     /// exists.
@@ -545,6 +565,14 @@ pub trait Interpreter : ContextualData +
     fn structure_definitions(&self) -> &DataDefinitionList;
 
 
+    /// Register a word, by it's handler index, to be executed before the interpreter exits.
+    fn add_exit_hook(&mut self, index: usize);
+
+    /// Run the registered exit hooks in the reverse order they were added.  Each hook is only run
+    /// once.  All of the hooks are run even if one fails, in which case the last error is returned.
+    fn run_exit_hooks(&mut self) -> error::Result<()>;
+
+
     /// Reset the interpreter to a prior context state, while also clearing the data stack.  After
     /// reset a new context is created.
     fn reset(&mut self) -> error::Result<()>;
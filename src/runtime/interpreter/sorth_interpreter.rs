@@ -17,8 +17,9 @@ use crate::{ add_native_word,
                                    NumberType,
                                    Token,
                                    TokenList } },
-             runtime::{ built_ins::ffi_words::FfiInterface,
-                        data_structures::{ byte_buffer::ByteBufferPtr,
+             runtime::{ built_ins::{ ffi_words::FfiInterface,
+                                    io_words::FileTable },
+                        data_structures::{ byte_buffer::BufferPtr,
                                            contextual_data::ContextualData,
                                            contextual_list::ContextualList,
                                            data_object::{ DataDefinitionList,
@@ -29,6 +30,7 @@ use crate::{ add_native_word,
                                                          WordRuntime,
                                                          WordType,
                                                          WordVisibility },
+                                           resource_table::ResourceTable,
                                            value::{ DeepClone,
                                                     ToValue,
                                                     Value },
@@ -48,7 +50,9 @@ use crate::{ add_native_word,
                                        WordHandler,
                                        WordHandlerInfo,
                                        WordManagement,
-                                       Ffi } } };
+                                       Ffi,
+                                       Files,
+                                       Resources } } };
 
 
 
@@ -97,6 +101,16 @@ pub struct SorthInterpreter
     /// The FFI interface used by the interpreter.
     ffi: FfiInterface,
 
+    /// The files, sockets, and pipes opened by the interpreter.
+    files: FileTable,
+
+    /// The child processes, running hashes, and other native resources owned by the interpreter.
+    resources: ResourceTable,
+
+
+    /// The handler indices of the words to run before the interpreter exits.
+    exit_hooks: Vec<usize>,
+
 
     /// The stack of code construction contexts used to build up the code blocks for both words and
     /// script top level code.
@@ -227,13 +241,43 @@ impl Interpreter for SorthInterpreter
         &self.data_definitions
     }
 
+    fn add_exit_hook(&mut self, index: usize)
+    {
+        self.exit_hooks.push(index);
+    }
+
+    fn run_exit_hooks(&mut self) -> error::Result<()>
+    {
+        let mut result = Ok(());
+
+        while let Some(index) = self.exit_hooks.pop()
+        {
+            if let Err(error) = self.execute_word_index(&location_here!(), index)
+            {
+                result = Err(error);
+            }
+        }
+
+        result
+    }
+
     fn reset(&mut self) -> error::Result<()>
     {
+        // Close any files, sockets, and other resources the script opened since the context was
+        // marked.  This has to happen before the context is released, or they'd be handed to the
+        // enclosing context.
+        self.files.reset();
+        self.resources.reset();
+
         // Clear the current context and the stack.  This should be enough to reset the interpreter
         // to a managed default state.
         self.release_context();
         self.stack.clear();
 
+        // Drop any exit hooks that refer to words that were released with the context.
+        let word_count = self.word_handlers.len();
+        self.exit_hooks.retain(|index| *index < word_count);
+
         // Make sure to reset the FFI subsystem state as well.
         self.ffi.reset();
 
@@ -252,6 +296,8 @@ impl ContextualData for SorthInterpreter
         self.word_handlers.mark_context();
         self.data_definitions.mark_context();
         self.variables.mark_context();
+        self.files.mark_context();
+        self.resources.mark_context();
     }
 
     fn release_context(&mut self)
@@ -260,6 +306,8 @@ impl ContextualData for SorthInterpreter
         self.word_handlers.release_context();
         self.data_definitions.release_context();
         self.variables.release_context();
+        self.files.release_context();
+        self.resources.release_context();
     }
 }
 
@@ -387,7 +435,7 @@ impl InterpreterStack for SorthInterpreter
         Ok(value.as_data_object(self)?.clone())
     }
 
-    fn pop_as_byte_buffer(&mut self) -> error::Result<ByteBufferPtr>
+    fn pop_as_byte_buffer(&mut self) -> error::Result<BufferPtr>
     {
         let value = self.pop()?;
 
@@ -874,7 +922,9 @@ impl CodeManagement for SorthInterpreter
                         {
                             if contexts != 0
                             {
+                                self.release_context();
                                 contexts -= 1;
+
                                 Ok(())
                             }
                             else
@@ -949,7 +999,17 @@ impl CodeManagement for SorthInterpreter
             // If the instruction was not successful we need to clean up and report the error.
             if let Err(script_error) = result.clone()
             {
-                if let Some(catch_index) = catch_locations.pop()
+                // Requests to exit the interpreter are not caught, they always unwind the stack.
+                let catch_index = if script_error.exit_code().is_none()
+                    {
+                        catch_locations.pop()
+                    }
+                    else
+                    {
+                        None
+                    };
+
+                if let Some(catch_index) = catch_index
                 {
                     pc = catch_index - 1;
                     self.push(script_error.to_string().to_value());
@@ -1154,6 +1214,34 @@ impl Ffi for SorthInterpreter
 }
 
 
+impl Files for SorthInterpreter
+{
+    fn files(&self) -> &FileTable
+    {
+        &self.files
+    }
+
+    fn files_mut(&mut self) -> &mut FileTable
+    {
+        &mut self.files
+    }
+}
+
+
+impl Resources for SorthInterpreter
+{
+    fn resources(&self) -> &ResourceTable
+    {
+        &self.resources
+    }
+
+    fn resources_mut(&mut self) -> &mut ResourceTable
+    {
+        &mut self.resources
+    }
+}
+
+
 impl SorthInterpreter
 {
     pub fn new() -> SorthInterpreter
@@ -1177,6 +1265,10 @@ impl SorthInterpreter
                 variables: VariableList::new(),
 
                 ffi: FfiInterface::new(),
+                files: FileTable::new(),
+                resources: ResourceTable::new(),
+
+                exit_hooks: Vec::new(),
 
                 constructors: CodeConstructorList::new()
             }
@@ -1038,294 +1038,6 @@
 
 
 
-( Given a format string and a position after the beginning bracket { extract the substring found )
-( within those brackets, {}  If there no specifier there an empty string, "", is returned instead. )
-( in both cases an updated index is also returned that points to after the closing bracket, }. )
-: string.format.extract_specifier  hidden  ( index format_string -- new_index specifier_string )
-    variable! format_str
-    variable! char_index
-    format_str @ string.size@ variable! length
-
-    "" variable! specifier
-    variable next
-
-    char_index @   format_str @  string.[]@  "}" <>
-    if
-        begin
-            char_index @  length @  <
-        while
-            char_index @  format_str @  string.[]@  next !
-
-            next @  "}"  <>
-            if
-                specifier @  next @  +  specifier !
-            else
-                break
-            then
-
-            char_index ++!
-        repeat
-
-        next @  "}"  <>
-        if
-            "Missing closing } in format specifier." throw
-        then
-    then
-
-    char_index @
-    specifier @
-;
-
-
-( Get a character from the string at the given index.  If the index is outside of the string an )
-( empty string is returned instead. )
-: string.format.get_char  hidden  ( index string -- character )
-    variable! string
-    variable! index
-
-    string @ string.size@ variable! size
-
-    "" variable! char
-
-    index @  size @  <
-    if
-        index @  string string.[]@@  char !
-    then
-
-    char @
-;
-
-
-( Parse the specifier found in the format string and return it's values broken out.  If the string )
-( is empty or a component is missing then the default is returned in it's stead. )
-: string.format.parse_specifier  hidden  ( value specifier -- fill alignment width is_hex )
-    variable! specifier
-    variable! value
-
-    " " variable! fill
-    value @ value.is-number? if ">" else "<" then variable! alignment
-    ""  variable! width
-    false variable! is_hex
-
-    variable char
-    0 variable! index
-
-    specifier string.size@@ variable! size
-
-    specifier @  ""  <>
-    if
-        index @ specifier @ string.format.get_char char !
-
-        char @  "<"  =  char @  "^"  =  ||  char @  ">"  =  ||
-        if
-            char @  alignment !
-            index ++!
-        then
-
-        index @ specifier @ string.format.get_char char !
-
-        char @  "0"  <  char @  "9"  >  ||
-        char @  "0"  =
-        ||
-        if
-            char @ fill !
-            index ++!
-        then
-
-        index @ specifier @ string.format.get_char char !
-
-        begin
-            char @  "0"  >=  char @  "9"  <=  &&
-            index @  size @  <
-            &&
-        while
-            width @ char @ +  width !
-
-            index ++!
-            index @ specifier @ string.format.get_char char !
-        repeat
-
-        width @ string.size@ 0>
-        if
-            width @ string.to_number width !
-        else
-            0 width !
-        then
-
-        index @ specifier @ string.format.get_char char !
-
-        char @  "x"  =
-        char @  "X"  =
-        ||
-        is_hex !
-    else
-        0 width !
-    then
-
-    fill @
-    alignment @
-    width @
-    is_hex @
-;
-
-
-( Given a count and a character create a string of count width filled with that character. )
-: string.format.fill_str  hidden  ( count char -- fill_string )
-    variable! char
-    variable! count
-
-    0 variable! index
-
-    "" variable! new_str
-
-    begin
-        index @  count @  <
-    while
-        new_str @  char @  +  new_str !
-        index ++!
-    repeat
-
-    new_str @
-;
-
-
-( Given a value and a sub-specifier convert the value to a string and format it according to the )
-( specifier string. )
-: string.format_value  hidden  ( value specifier -- formatted_value )
-    variable! specifier
-    variable! value
-
-    variable fill
-    variable alignment
-    variable width
-    variable is_hex
-
-    0 variable! fill_width
-
-    variable str_value
-
-    specifier @  ""  <>
-    if
-        value @ specifier @ string.format.parse_specifier is_hex ! width ! alignment ! fill !
-
-        is_hex @
-        if
-            value @ value.is-number?
-            if
-                value @ hex str_value !
-            else
-                "Can't convert value to a hex string." throw
-            then
-        else
-            value @ to_string str_value !
-        then
-
-        str_value string.size@@  width  <
-        if
-            width @ str_value string.size@@ -  fill_width !
-
-            alignment @
-            case
-                "<" of
-                        str_value @  fill_width @ fill @ string.format.fill_str  +  str_value !
-                    endof
-
-                "^" of
-                        fill_width @ 2 / fill @ string.format.fill_str  str_value @  +  str_value !
-
-                        fill_width @ 2 %  0  <>
-                        if
-                            fill_width @ 2 / 1 + fill_width !
-                        else
-                            fill_width @ 2 / fill_width !
-                        then
-
-                        str_value @  fill_width @ fill @ string.format.fill_str  +  str_value !
-                    endof
-
-                ">" of
-                        fill_width @ fill @ string.format.fill_str  str_value @  +  str_value !
-                    endof
-            endcase
-        then
-    else
-        value @ to_string str_value !
-    then
-
-    str_value @
-;
-
-
-: string.format
-    description: "Format a string where occurrences of {} are replaced with stack values."
-    signature: "[variables] format_string -- formatted_string"
-
-    0 [].new variable! snippets
-    0 [].new variable! values
-    0 [].new variable! specifiers
-
-    variable! format_str
-
-    format_str string.size@@ variable! length
-    0 variable! char_index
-    "" variable! format_snippet
-
-    variable next
-
-    begin
-        char_index @  length @  <
-    while
-        char_index @  format_str @  string.[]@  next !
-
-        next @  "{"  =
-        if
-            char_index @ ++ format_str @ string.format.extract_specifier
-            specifiers [].push_back!!
-            char_index !
-
-            format_snippet @  snippets  [].push_back!!
-                                values  [].push_front!!
-
-            "" format_snippet !
-        else
-            format_snippet @  next @  +  format_snippet !
-        then
-
-        char_index ++!
-    repeat
-
-    0 variable! snippet_index
-    "" variable! output_string
-
-    begin
-        snippet_index @  snippets [].size@@  <
-    while
-        output_string @  snippets [ snippet_index @ ]@@  +  output_string !
-
-
-        snippet_index @  values [].size@@  <
-        if
-            output_string @
-            values [ snippet_index @ ]@@  specifiers [ snippet_index @ ]@@  string.format_value
-            +
-
-            output_string !
-        then
-
-        snippet_index ++!
-    repeat
-
-    format_snippet string.size@@  0>
-    if
-        output_string @ format_snippet @  +  output_string !
-    then
-
-    output_string @
-;
-
-
-
-
 ( Hash table words. )
 : {}!! description: "Insert a value into the hash table variable."
        signature: "value key hash_variable -- "
@@ -1678,6 +1390,173 @@
 
 
 
+( Helpers for reading/writing byte buffers with an explicit byte order. )
+: buffer.i16le!! description: "Write a 16-bit little endian integer to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 2 buffer.int.le!
+;
+
+
+: buffer.i32le!! description: "Write a 32-bit little endian integer to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 4 buffer.int.le!
+;
+
+
+: buffer.i64le!! description: "Write a 64-bit little endian integer to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 8 buffer.int.le!
+;
+
+
+
+: buffer.i16le@@ description: "Read a 16-bit signed little endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 2 true buffer.int.le@
+;
+
+
+: buffer.i32le@@ description: "Read a 32-bit signed little endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 4 true buffer.int.le@
+;
+
+
+: buffer.i64le@@ description: "Read a 64-bit signed little endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 8 true buffer.int.le@
+;
+
+
+
+: buffer.u16le@@ description: "Read a 16-bit unsigned little endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 2 false buffer.int.le@
+;
+
+
+: buffer.u32le@@ description: "Read a 32-bit unsigned little endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 4 false buffer.int.le@
+;
+
+
+: buffer.u64le@@ description: "Read a 64-bit unsigned little endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 8 false buffer.int.le@
+;
+
+
+
+: buffer.f32le!! description: "Write a 32-bit little endian float to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 4 buffer.float.le!
+;
+
+
+: buffer.f64le!! description: "Write a 64-bit little endian float to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 8 buffer.float.le!
+;
+
+
+
+: buffer.f32le@@ description: "Read a 32-bit little endian float from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 4 buffer.float.le@
+;
+
+
+: buffer.f64le@@ description: "Read a 64-bit little endian float from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 8 buffer.float.le@
+;
+
+
+
+: buffer.i16be!! description: "Write a 16-bit big endian integer to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 2 buffer.int.be!
+;
+
+
+: buffer.i32be!! description: "Write a 32-bit big endian integer to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 4 buffer.int.be!
+;
+
+
+: buffer.i64be!! description: "Write a 64-bit big endian integer to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 8 buffer.int.be!
+;
+
+
+
+: buffer.i16be@@ description: "Read a 16-bit signed big endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 2 true buffer.int.be@
+;
+
+
+: buffer.i32be@@ description: "Read a 32-bit signed big endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 4 true buffer.int.be@
+;
+
+
+: buffer.i64be@@ description: "Read a 64-bit signed big endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 8 true buffer.int.be@
+;
+
+
+
+: buffer.u16be@@ description: "Read a 16-bit unsigned big endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 2 false buffer.int.be@
+;
+
+
+: buffer.u32be@@ description: "Read a 32-bit unsigned big endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 4 false buffer.int.be@
+;
+
+
+: buffer.u64be@@ description: "Read a 64-bit unsigned big endian integer from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 8 false buffer.int.be@
+;
+
+
+
+: buffer.f32be!! description: "Write a 32-bit big endian float to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 4 buffer.float.be!
+;
+
+
+: buffer.f64be!! description: "Write a 64-bit big endian float to the buffer variable."
+                 signature: "value buffer_variable -- "
+    @ 8 buffer.float.be!
+;
+
+
+
+: buffer.f32be@@ description: "Read a 32-bit big endian float from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 4 buffer.float.be@
+;
+
+
+: buffer.f64be@@ description: "Read a 64-bit big endian float from the buffer variable."
+                 signature: "buffer_variable -- value"
+    @ 8 buffer.float.be@
+;
+
+
+
 : buffer.string!!
     description: "Write a string of a given size to the buffer variable.  Pad with 0s."
     signature: "string buffer_variable max_size -- "
@@ -1834,6 +1713,15 @@
 
 
 
+( Exit the interpreter, reporting the exit code to the operating system. )
+: quit description: "Run the exit hooks and exit the interpreter with the given code."
+       signature: "exit_code -- "
+    sorth.exit
+;
+
+
+
+
 ( Make sure that advanced terminal and user functionality is available.  If it is, enable the )
 ( 'fancy' repl capable of keeping history.  Otherwise enable the simpler repl. )
 [defined?] term.raw_mode
@@ -1904,12 +1792,6 @@
 
 
 
-( Include our json utility functions. )
-[include] std/json.f
-
-
-
-
 ( Include the ffi system. )
 [include] std/ffi.f
 
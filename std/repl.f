@@ -2,12 +2,12 @@
 ( Implementation of Sorth's repl.  The repl supports features like persistent command history, )
 ( single and multi-line editing. )
 
-user.home user.path_sep + ".sorth_init" + constant repl.config_path
+user.home ".sorth_init" path.join constant repl.config_path
 
 
 ( Count of the maximum number of items that can be in the history at any one time. )
 100 constant repl.history.default_max_size
-user.home user.path_sep + ".sorth_history.json" + constant repl.history.path
+user.home ".sorth_history.json" path.join constant repl.history.path
 
 
 ( Keep track of the repl's history.  We're using a circular buffer capped at max_size. )
@@ -1188,24 +1188,22 @@ repl.history.new variable! repl.history.state
 
 
 ( Ways to exit the repl. )
-false variable! repl.is_quitting?
-
-
-: quit description: "Exit the repl."
-       signature: " -- "
-    true repl.is_quitting? !
-;
-
-
 : q  description: "Exit the repl."
      signature: " -- "
-    quit
+    exit_success quit
 ;
 
 
 : exit description: "Exit the repl."
        signature: " -- "
-    quit
+    exit_success quit
+;
+
+
+( Restore the terminal and save the session's history when the interpreter exits. )
+: repl.on_exit  hidden  ( -- )
+    false term.raw_mode
+    repl.history.state repl.history.save
 ;
 
 
@@ -1219,7 +1217,7 @@ false variable! repl.is_quitting?
        Strange Forth REPL.
        Version: {}
 
-       Enter quit, q, or exit to quit the REPL.
+       Enter q or exit to quit the REPL, or <code> quit to exit with a code.
        Enter .w to show defined words.
        Enter show_word <word_name> to list detailed information about a word.
        Hit {} to enter multi-line editing mode.
@@ -1227,8 +1225,10 @@ false variable! repl.is_quitting?
     *"
     string.format .cr
 
-    ( Load the previous session's history if there is one. )
+    ( Load the previous session's history if there is one.  Then make sure it's saved again when )
+    ( the interpreter exits. )
     repl.history.state repl.history.load
+    ` repl.on_exit sorth.at-exit
 
     ( Load and process the user config file, if it exists. )
     repl.config_path file.exists?
@@ -1239,7 +1239,7 @@ false variable! repl.is_quitting?
     ( Loop forever.  If the user enters a quit command the execution of this script will end at )
     ( that point. )
     begin
-        repl.is_quitting? @ '
+        true
     while
         try
             ( Read and attempt to execute the user command. )
@@ -1256,7 +1256,4 @@ false variable! repl.is_quitting?
             cr .cr
         endcatch
     repeat
-
-    false term.raw_mode
-    repl.history.state repl.history.save
 ;
@@ -6,24 +6,15 @@
 
 
 ( Ways to exit the repl. )
-false variable! repl.is_quitting?
-
-
-: quit description: "Exit the repl."
-       signature: " -- "
-    true repl.is_quitting? !
-;
-
-
 : q  description: "Exit the repl."
      signature: " -- "
-    quit
+    exit_success quit
 ;
 
 
 : exit description: "Exit the repl."
        signature: " -- "
-    quit
+    exit_success quit
 ;
 
 
@@ -37,15 +28,17 @@ false variable! repl.is_quitting?
        Strange Forth REPL.
        Version: {}
 
-       Enter quit, q, or exit to quit the REPL.
+       Enter q or exit to quit the REPL, or <code> quit to exit with a code.
        Enter .w to show defined words.
        Enter show_word <word_name> to list detailed information about a word.
 
     *"
     string.format .cr
 
+    ( Loop forever.  If the user enters a quit command the execution of this script will end at )
+    ( that point. )
     begin
-        repl.is_quitting? @ '
+        true
     while
         try
             ( Always make sure we get the newest version of the prompt.  That way the user can )
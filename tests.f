@@ -60,3 +60,97 @@ cr
 ( "tests/09_test_ffi.f" include )
 
 ( cr )
+
+cr
+
+"--- Testing date and time. ---" .cr
+
+"tests/10_test_time.f" include
+
+cr
+
+"--- Testing processes. ---" .cr
+
+"tests/11_test_process.f" include
+
+cr
+
+"--- Testing exit codes. ---" .cr
+
+"tests/12_test_exit.f" include
+
+cr
+
+"--- Testing the filesystem. ---" .cr
+
+"tests/13_test_fs.f" include
+
+cr
+
+"--- Testing paths. ---" .cr
+
+"tests/14_test_path.f" include
+
+cr
+
+"--- Testing sockets. ---" .cr
+
+"tests/15_test_sockets.f" include
+
+cr
+
+"--- Testing resource handling. ---" .cr
+
+"tests/16_test_resources.f" include
+
+cr
+
+"--- Testing encodings. ---" .cr
+
+"tests/17_test_encoding.f" include
+
+cr
+
+"--- Testing hashes and checksums. ---" .cr
+
+"tests/18_test_hash.f" include
+
+cr
+
+"--- Testing JSON. ---" .cr
+
+"tests/19_test_json.f" include
+
+cr
+
+"--- Testing MessagePack. ---" .cr
+
+"tests/20_test_msgpack.f" include
+
+cr
+
+"--- Testing CSV. ---" .cr
+
+"tests/21_test_csv.f" include
+
+cr
+
+"--- Testing config files. ---" .cr
+
+"tests/22_test_config.f" include
+
+cr
+
+"--- Testing regular expressions. ---" .cr
+
+"tests/23_test_regex.f" include
+
+( The C library is only loaded by name on Linux, other systems skip the test. )
+user.os "Linux" =
+if
+    cr
+
+    "--- Testing the ffi with the C library. ---" .cr
+
+    "tests/24_test_ffi_libc.f" include
+then
@@ -16,12 +16,66 @@ bytes @ .cr
 "foobarbaz" 6 bytes buffer.string!!
 
 
-( Write the buffer to a file. )
-(
-"./test.bin" file.w/o file.create
-dup bytes @ swap file.!
-file.close
-)
+( Write the buffer to a file, then read it back and make sure nothing changed. )
+"./test.bin" file.r/w file.create variable! buffer_fd
+bytes @ buffer_fd @ file.buffer!
+
+0 file.seek.start buffer_fd @ file.seek drop
+100 buffer_fd @ file.@ variable! file_bytes
+
+( Buffers compare their positions too, so line the new buffer up with the original. )
+bytes @ buffer.position@ file_bytes @ buffer.position!
+
+"File size:  " . buffer_fd @ file.tell .cr
+
+file_bytes @ bytes @ <>
+if
+    "Buffer file round trip mismatch!" .cr
+    exit_failure quit
+then
+
+( Seek back from the end and read the last string on it's own, then cut the file down. )
+-6 file.seek.end buffer_fd @ file.seek drop
+6 buffer_fd @ file.@ 6 buffer.string@ variable! tail_text
+
+"File tail:  " . tail_text @ .cr
+
+16 buffer_fd @ file.truncate
+
+tail_text @ "foobar" <>
+buffer_fd @ file.size@ 16 <>
+||
+if
+    "File seek or truncate mismatch!" .cr
+    exit_failure quit
+then
+
+( Sizes are only a limit on how much to read, so a huge size reads what's left, and a negative
+  size is an error. )
+0 file.seek.start buffer_fd @ file.seek drop
+1000000000000000 buffer_fd @ file.@ buffer.size@ 16 <>
+if
+    "Reading with a huge size mismatch!" .cr
+    exit_failure quit
+then
+
+false variable! negative_read_failed
+
+try
+    -1 buffer_fd @ file.@ drop
+catch
+    drop
+    true negative_read_failed !
+endcatch
+
+negative_read_failed @ '
+if
+    "Reading a negative size should fail!" .cr
+    exit_failure quit
+then
+
+buffer_fd @ file.close
+"./test.bin" file.delete
 
 
 ( Show the buffer as it is now. )
@@ -65,3 +119,191 @@ cr
 
 "i32: " . bytes buffer.i32@@ .hcr
 "i32: " . bytes buffer.i32@@ .hcr
+
+cr
+
+
+( Map a file into memory, change it through a writable mapping, and read it back again. )
+"./mmap_test.bin" file.w/o file.create variable! mmap_fd
+bytes @ mmap_fd @ file.buffer!
+mmap_fd @ file.close
+
+"./mmap_test.bin" file.r/w file.mmap variable! mapped
+
+0x7f7f7f7f mapped buffer.i32!!
+
+"./mmap_test.bin" file.r/o file.mmap variable! read_only
+
+"Mapped: " . read_only buffer.i32@@ .hcr
+"Mapped: " . read_only buffer.i32@@ .hcr
+
+4 read_only buffer.position!!
+
+read_only @ buffer.size@ 8 <>
+read_only buffer.i32@@ 0x10101010 <>
+||
+if
+    "Mapped buffer mismatch!" .cr
+    exit_failure quit
+then
+
+try
+    1 read_only buffer.i32!!
+    "Wrote to a read-only mapping!" .cr
+    exit_failure quit
+catch
+    drop
+    "Read-only mapping rejected the write." .cr
+endcatch
+
+"./mmap_test.bin" file.delete
+
+cr
+
+
+( Slices share storage with their parent buffer but keep their own cursor. )
+16 buffer.new variable! packet
+
+0x0102 packet buffer.i16!!
+0x03040506 packet buffer.i32!!
+
+packet @ 2 6 buffer.slice variable! payload
+
+"Payload: " . payload buffer.i32@@ .hcr
+
+0 payload buffer.position!!
+0x0a0b0c0d payload buffer.i32!!
+
+2 packet buffer.position!!
+
+payload @ buffer.size@ 4 <>
+packet buffer.i32@@ 0x0a0b0c0d <>
+||
+if
+    "Buffer slice mismatch!" .cr
+    exit_failure quit
+then
+
+try
+    1 payload buffer.i32!!
+    "Wrote past the end of a slice!" .cr
+    exit_failure quit
+catch
+    drop
+    "Slice bounds were enforced." .cr
+endcatch
+
+cr
+
+
+( Read and write values with an explicit byte order. )
+16 buffer.new variable! ordered
+
+0x0102 ordered buffer.i16be!!
+0x0102 ordered buffer.i16le!!
+-2 ordered buffer.i32be!!
+1.5 ordered buffer.f64be!!
+
+ordered @ .cr
+
+0 ordered buffer.position!!
+
+ordered buffer.u8@@ 1 <>
+ordered buffer.u8@@ 2 <> ||
+ordered buffer.u16be@@ 0x0201 <> ||
+ordered buffer.i32be@@ -2 <> ||
+ordered buffer.f64be@@ 1.5 <> ||
+if
+    "Byte order mismatch!" .cr
+    exit_failure quit
+then
+
+4 ordered buffer.position!!
+"u32be: " . ordered buffer.u32be@@ .hcr
+
+( Single bytes have always been read as unsigned, even when a signed value is asked for. )
+0 ordered buffer.position!!
+-1 ordered buffer.i8!!
+0 ordered buffer.position!!
+
+ordered buffer.i8@@ 255 <>
+if
+    "Single byte read mismatch!" .cr
+    exit_failure quit
+then
+
+cr
+
+
+( Pack a structure into a buffer with a binary layout and unpack it again. )
+# record_header
+    magic -> 0x52454331 ,
+    version -> 2 ,
+    is_compressed -> true ,
+    name -> "header" ,
+    offset -> -12
+;
+
+"record_header" [ "u32be" , "u8" , "bool" , "pad:2" , "string:8" , "i16le" ] #.layout!
+
+"record_header" #.layout.size@ buffer.new variable! record_bytes
+
+record_header.new record_bytes @ buffer.pack
+record_bytes @ .cr
+
+0 record_bytes buffer.position!!
+
+record_header.new variable! record
+0 record record_header.version!!
+
+record @ record_bytes @ buffer.unpack
+record @ .cr
+
+record @ record_header.new #.= '
+if
+    "Unpacked structure mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Signed byte fields of a binary layout are sign extended when they're unpacked. )
+# signed_byte
+    value -> -1
+;
+
+"signed_byte" [ "i8" ] #.layout!
+
+1 buffer.new variable! signed_bytes
+signed_byte.new signed_bytes @ buffer.pack
+
+signed_byte.new variable! unpacked_byte
+0 unpacked_byte signed_byte.value!!
+0 signed_bytes buffer.position!!
+unpacked_byte @ signed_bytes @ buffer.unpack
+
+unpacked_byte signed_byte.value@@ -1 <>
+if
+    "Signed byte field mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Layouts that are too large to fit in memory are rejected. )
+# huge_record
+    value -> 0
+;
+
+false variable! huge_pad_failed
+
+try
+    "huge_record" [ "pad:18446744073709551615" , "u8" ] #.layout!
+catch
+    drop
+    true huge_pad_failed !
+endcatch
+
+huge_pad_failed @ '
+if
+    "A huge padding entry should fail!" .cr
+    exit_failure quit
+then
@@ -77,3 +77,227 @@ value @ 2 * value !
 value @  message @  "{9x} : {<19}"  string.format  .cr
 value @  message @  "{^9x} : {^19}" string.format  .cr
 value @  message @  "{<9x} : {>19}" string.format  .cr
+
+cr
+
+
+( Case conversion and trimming are Unicode aware. )
+"Straße Ünd" string.upper "STRASSE ÜND" <>
+"ÀBC" string.lower "àbc" <> ||
+"  \t padded \n" string.trim "padded" <> ||
+"  left  " string.trim-left "left  " <> ||
+"  right  " string.trim-right "  right" <> ||
+if
+    "String case or trim mismatch!" .cr
+    exit_failure quit
+then
+
+
+"pre" "prefix" string.starts-with? '
+"fix" "prefix" string.ends-with? ' ||
+"fix" "prefix" string.starts-with? ||
+"ab" "a-b" string.ends-with? ||
+if
+    "String prefix or suffix mismatch!" .cr
+    exit_failure quit
+then
+
+
+"o" "0" "foo boo" string.replace "f00 b00" <>
+", " [ "a" , 1 , 2.5 ] string.join "a, 1, 2.5" <> ||
+"-" 0 [].new string.join "" <> ||
+3 "ab" string.repeat "ababab" <> ||
+if
+    "String replace, join, or repeat mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Padding and reversing count characters rather than bytes. )
+5 "·" "ñ" string.pad-left "····ñ" <>
+4 "." "abc" string.pad-right "abc." <> ||
+2 "." "abc" string.pad-right "abc" <> ||
+"héllo" string.reverse "olléh" <> ||
+"one\r\ntwo\n\nfour\n" string.lines [ "one" , "two" , "" , "four" ] <> ||
+if
+    "String pad, reverse, or lines mismatch!" .cr
+    exit_failure quit
+then
+
+"Padded:        [" . 8 " " "right" string.pad-left . "]" .cr
+
+
+( Results too large to create are reported rather than crashing. )
+0 variable! string_size_errors
+
+try
+    9223372036854775807 "ab" string.repeat drop
+catch
+    drop
+    string_size_errors ++!
+endcatch
+
+try
+    9223372036854775807 "·" "x" string.pad-left drop
+catch
+    drop
+    string_size_errors ++!
+endcatch
+
+string_size_errors @ 2 <>
+9223372036854775807 "" string.repeat "" <> ||
+if
+    "String size limit mismatch!" .cr
+    exit_failure quit
+then
+
+cr
+
+
+( Format specifiers after a colon support precision, signs, zero padding, radix prefixes, and )
+( thousands separators. )
+3.14159 "{:.2}" string.format "3.14" <>
+42 "{:+}" string.format "+42" <> ||
+-7 "{:05}" string.format "-0007" <> ||
+255 255 255 255 "{:#x}|{:#X}|{:#b}|{:#o}" string.format "0xff|0xFF|0b11111111|0o377" <> ||
+1234567 "{:,}" string.format "1,234,567" <> ||
+-9876543.215 "{:,.1}" string.format "-9,876,543.2" <> ||
+"abc" "{:-^9}" string.format "---abc---" <> ||
+if
+    "Format specifier mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Arguments can be selected by position or by name, and {{ writes a literal brace. )
+"a" "b" "{1:}{0:}{1:}" string.format "bab" <>
+"x" { "name" -> "Ann" , "age" -> 7 } "{} {{{name}} {age:>3}" string.format "x {Ann}   7" <> ||
+{ "a10" -> "y" } "{a10:}" string.format "y" <> ||
+if
+    "Format argument mismatch!" .cr
+    exit_failure quit
+then
+
+
+( The original specifiers keep their meaning, and }} is still written as is. )
+1 2 "{} }} {}" string.format "1 }} 2" <>
+5 "{a10}" string.format "aaaaaaaaa5" <> ||
+"ab" "{>-5}" string.format "---ab" <> ||
+255 "{x}" string.format "ff" <> ||
+if
+    "Legacy format mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Debug output quotes strings. )
+"hi" [ "a" , 1 ] "{:?} {:?}" string.format variable! debug_text
+"Debug:         " . debug_text @ .cr
+
+debug_text @ "\"hi\" [ \"a\", 1 ]" <>
+if
+    "Format debug mismatch!" .cr
+    exit_failure quit
+then
+
+
+try
+    1 "{:.}" string.format drop
+    "Missing precision should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "a" "{18446744073709551615:}" string.format drop
+    "A huge format argument index should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "a" "{1000000000000:}" string.format drop
+    "Missing format arguments should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    1 "{:99999999999999999999999}" string.format drop
+    "A huge format width should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "hello" "{8x}" string.format drop
+    "Formatting a string as hex should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+"hello" hex "0" <>
+if
+    "The hex word should still convert strings to zero!" .cr
+    exit_failure quit
+then
+
+cr
+
+
+( Characters convert to and from their Unicode scalar values. )
+"é" char.code 233 <>
+128512 char.from-code char.code 128512 <> ||
+"ß" char.upper "SS" <> ||
+"Ä" char.lower "ä" <> ||
+"λ" char.alpha? ' ||
+"7" char.digit? ' ||
+"\t" char.whitespace? ' ||
+"x" char.digit? ||
+"½" char.digit? ||
+"²" char.digit? ||
+"Ⅻ" char.digit? ||
+if
+    "Character word mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Strings can be broken into characters or UTF-8 bytes. )
+"aé😀" string.chars variable! string_chars
+"aé😀" string.bytes variable! string_bytes
+
+"Chars:         " . string_chars @ .cr
+"Bytes:         " . string_bytes @ buffer.to-hex .cr
+
+string_chars @ [].size@ 3 <>
+2 string_chars @ []@ 2 "aé😀" string.[]@ <> ||
+"aé😀" string.size@ 3 <> ||
+"aé😀" string.byte-size@ 7 <> ||
+string_bytes @ buffer.to-hex "61c3a9f09f9880" <> ||
+if
+    "String chars or bytes mismatch!" .cr
+    exit_failure quit
+then
+
+
+try
+    "ab" char.code drop
+    "Multi-character char.code should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    55296 char.from-code drop
+    "Surrogate char.from-code should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
@@ -0,0 +1,95 @@
+
+( A fixed timestamp, 2023-11-14T22:13:20Z, so that the output is predictable. )
+1700000000 time.parts.utc variable! parts
+
+"UTC parts:     " . parts @ .cr
+
+parts @ time.to-iso variable! iso_text
+"ISO-8601:      " . iso_text @ .cr
+
+iso_text @ "2023-11-14T22:13:20Z" <>
+if
+    "ISO-8601 formatting mismatch!" throw
+then
+
+parts @ "%A %d %B %Y, %I:%M:%S %p (%j)" time.format variable! formatted
+"Formatted:     " . formatted @ .cr
+
+formatted @ "Tuesday 14 November 2023, 10:13:20 PM (318)" <>
+if
+    "Time formatting mismatch!" throw
+then
+
+cr
+
+( Make sure that parsing a timestamp with an offset gives us back the same moment in time. )
+"2023-11-15T03:43:20.25+05:30" time.from-iso variable! offset_parts
+"Parsed:        " . offset_parts @ time.to-iso .cr
+
+offset_parts @ time.parts.epoch 1700000000 <>
+if
+    "Parsed timestamp mismatch!" throw
+then
+
+cr
+
+( Check that the clocks are working. )
+time.monotonic variable! start
+10 time.sleep
+time.monotonic start @ - 10000000 <
+if
+    "Monotonic clock didn't advance!" throw
+then
+
+time.now 1700000000 <
+if
+    "Current time is in the past!" throw
+then
+
+( Sleeping for a time that isn't a valid duration is an error. )
+false variable! sleep_failed
+
+try
+    0.0 0.0 / time.sleep
+catch
+    drop
+    true sleep_failed !
+endcatch
+
+sleep_failed @ '
+if
+    "Sleeping for NaN milliseconds should fail!" throw
+then
+
+( Parts that don't describe an actual moment in time, or impossible offsets, are errors. )
+false variable! huge_year_failed
+
+try
+    1700000000 time.parts.utc variable! huge_year_parts
+    1000000000000000000 huge_year_parts @ time.parts.year!
+    huge_year_parts @ time.parts.epoch drop
+catch
+    drop
+    true huge_year_failed !
+endcatch
+
+huge_year_failed @ '
+if
+    "Converting a year of 1e18 should fail!" throw
+then
+
+false variable! bad_offset_failed
+
+try
+    "2023-11-15T03:43:20+99:99" time.from-iso drop
+catch
+    drop
+    true bad_offset_failed !
+endcatch
+
+bad_offset_failed @ '
+if
+    "Parsing an offset of +99:99 should fail!" throw
+then
+
+"Local offset:  " . time.now time.parts.local "%z" time.format .cr
@@ -0,0 +1,85 @@
+
+( Run a command to completion and capture it's output. )
+[ "echo" , "Hello from a child process." ] none none process.run
+variable! exit_code
+variable! error_text
+variable! output_text
+
+"Output:     " . output_text @ .
+"Exit code:  " . exit_code @ .cr
+
+exit_code @ 0 <>
+output_text @ "Hello from a child process.\n" <>
+||
+if
+    "Process output mismatch!" throw
+then
+
+
+( Make sure the environment, working directory, and exit codes are passed through. )
+[ "sh" , "-c" , "echo $SORTH_TEST_VALUE; pwd; exit 3" ]
+{ "SORTH_TEST_VALUE" -> "fhqwhgads" }
+"/"
+process.run
+variable! exit_code
+variable! error_text
+variable! output_text
+
+"Output:     " . output_text @ .
+"Exit code:  " . exit_code @ .cr
+
+exit_code @ 3 <>
+output_text @ "fhqwhgads\n/\n" <>
+||
+if
+    "Process environment mismatch!" throw
+then
+
+
+( Spawn a process and talk to it through it's pipes. )
+"cat" none none process.spawn
+variable! stderr_fd
+variable! stdout_fd
+variable! stdin_fd
+variable! pid
+
+"First line." stdin_fd @ file.line!
+"Second line." stdin_fd @ file.line!
+stdin_fd @ file.close
+
+"Echoed:     " . stdout_fd @ file.line@ .cr
+"Echoed:     " . stdout_fd @ file.line@ .cr
+
+"Exit code:  " . pid @ process.wait .cr
+
+stdout_fd @ file.close
+stderr_fd @ file.close
+
+
+( Mixing line, character, and string reads on the same fd shouldn't lose any read ahead data. )
+[ "printf" , "one\ntwo\nthree\n" ] none none process.spawn
+variable! mixed_stderr_fd
+variable! mixed_stdout_fd
+variable! mixed_stdin_fd
+variable! mixed_pid
+
+mixed_stdin_fd @ file.close
+
+mixed_stdout_fd @ file.line@ variable! mixed_line
+mixed_stdout_fd @ file.char@ variable! mixed_char
+mixed_stdout_fd @ file.string@ variable! mixed_rest
+
+"Mixed reads: " . mixed_line @ . " " . mixed_char @ . " " . mixed_rest @ .
+
+mixed_pid @ process.wait drop
+
+mixed_line @ "one" <>
+mixed_char @ "t" <>
+mixed_rest @ "wo\nthree\n" <>
+|| ||
+if
+    "Mixed read mismatch!" throw
+then
+
+mixed_stdout_fd @ file.close
+mixed_stderr_fd @ file.close
@@ -0,0 +1,21 @@
+
+( Make sure the exit codes match what the operating system expects. )
+"Success code:  " . exit_success .cr
+"Failure code:  " . exit_failure .cr
+
+exit_success 0 <>
+exit_failure 1 <>
+||
+if
+    "Exit code mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Register a hook that is run after the rest of the tests have finished. )
+: exit_test.hook
+    "Exit hook run." .cr
+;
+
+` exit_test.hook sorth.at-exit
+
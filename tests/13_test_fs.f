@@ -0,0 +1,131 @@
+
+( Build a small directory tree to work with.  Clean up after any earlier failed run first. )
+"fs_test_scratch" constant scratch
+
+scratch file.exists?
+if
+    scratch dir.remove-all
+then
+
+scratch "/src/nested" + dir.create
+
+scratch "/src/main.f" + file.w/o file.create variable! fd
+"Hello, filesystem!" fd @ file.!
+fd @ file.close
+
+scratch "/src/main.f" +  scratch "/src/nested/copy.f" +  file.copy
+scratch "/src/nested/copy.f" +  scratch "/src/nested/moved.txt" +  file.rename
+scratch "/notes.txt" + file.w/o file.create file.close
+
+
+( List the directories. )
+scratch dir.list variable! names
+"Listing:       " . names @ .cr
+
+names @ [ "notes.txt" , "src" ] <>
+if
+    "Directory listing mismatch!" .cr
+    exit_failure quit
+then
+
+scratch dir.walk variable! paths
+"Walked:        " . paths @ .cr
+
+paths @ [ scratch "/notes.txt" + ,
+          scratch "/src" + ,
+          scratch "/src/main.f" + ,
+          scratch "/src/nested" + ,
+          scratch "/src/nested/moved.txt" + ] <>
+if
+    "Directory walk mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Check the file metadata. )
+scratch "/src/nested/moved.txt" + file.stat variable! info
+"Stat:          " . info @ .cr
+
+info @ file.stat.size@ 18 <>
+info @ file.stat.is_file@ '
+info @ file.stat.is_dir@
+info @ file.stat.modified@ 0 <=
+|| || ||
+if
+    "File stat mismatch!" .cr
+    exit_failure quit
+then
+
+scratch file.stat file.stat.is_dir@ '
+if
+    "Directory stat mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Match paths with glob patterns. )
+scratch "/**/*.f" + file.glob variable! matches
+"Glob:          " . matches @ .cr
+
+matches @ [ scratch "/src/main.f" + ] <>
+if
+    "Glob mismatch!" .cr
+    exit_failure quit
+then
+
+"src/runtime/io_words.rs" "src/**/*_words.[rR][sS]" glob.match? '
+"src/main.rs" "src/*/*.rs" glob.match?
+"src/.hidden" "src/*" glob.match?
+|| ||
+if
+    "Glob pattern match mismatch!" .cr
+    exit_failure quit
+then
+
+( Stars only backtrack as far as they need to, so many stars don't make matching blow up. )
+"xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa" "x*a*a*a*a*a*a*a*a*ab" glob.match?
+"axbyc" "a*b*c" glob.match? ' ||
+"ab" "a*b*c" glob.match? ||
+"a[b]c" "a[[]b*" glob.match? ' ||
+if
+    "Glob star match mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Reads are buffered, so make sure the file's position still lines up with what's been read. )
+scratch "/lines.txt" + file.r/w file.create variable! lines_fd
+"first" lines_fd @ file.line!
+"second" lines_fd @ file.line!
+0 file.seek.start lines_fd @ file.seek drop
+
+lines_fd @ file.line@ variable! first_line
+lines_fd @ file.tell variable! first_line_end
+"Rest" lines_fd @ file.!
+
+0 file.seek.start lines_fd @ file.seek drop
+lines_fd @ file.string@ variable! all_lines
+lines_fd @ file.close
+
+"Lines:         " . first_line @ . " " . first_line_end @ . " " . all_lines @ .
+
+first_line @ "first" <>
+first_line_end @ 6 <>
+all_lines @ "first\nRestnd\n" <>
+|| ||
+if
+    "Buffered file position mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Clean up the directory tree. )
+scratch "/src/nested/moved.txt" + file.delete
+scratch "/src/nested" + dir.remove
+scratch dir.remove-all
+
+scratch file.exists?
+if
+    "Directory removal failed!" .cr
+    exit_failure quit
+then
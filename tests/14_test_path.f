@@ -0,0 +1,58 @@
+
+( Take a path apart and put it back together again. )
+"/usr/local" "lib/sorth/std.f" path.join variable! full_path
+"Joined:        " . full_path @ .cr
+
+full_path @ "/usr/local/lib/sorth/std.f" <>
+if
+    "Path join mismatch!" .cr
+    exit_failure quit
+then
+
+"Split:         " . full_path @ path.split .cr
+"Parent:        " . full_path @ path.parent .cr
+"File name:     " . full_path @ path.file-name .cr
+"Stem:          " . full_path @ path.stem .cr
+"Extension:     " . full_path @ path.extension .cr
+
+full_path @ path.split [ "/" , "usr" , "local" , "lib" , "sorth" , "std.f" ] <>
+full_path @ path.parent "/usr/local/lib/sorth" <>
+full_path @ path.file-name "std.f" <>
+full_path @ path.stem "std" <>
+full_path @ path.extension "f" <>
+"/" path.parent none <>
+"README" path.extension none <>
+|| || || || || ||
+if
+    "Path component mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Work out relative paths. )
+"/usr/local/lib/sorth/std.f" "/usr/local/bin" path.relative-to variable! relative
+"Relative:      " . relative @ .cr
+
+relative @ "../lib/sorth/std.f" <>
+"src/main.rs" "src" path.relative-to "main.rs" <>
+"src" "src" path.relative-to "." <>
+||  ||
+if
+    "Relative path mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Resolve paths against the filesystem.  The results of sorth.find-file should work with the path )
+( words too. )
+"tests.f" sorth.find-file variable! found
+"Found:         " . found @ .cr
+
+"tests.f" path.canonical found @ <>
+"tests.f" path.absolute found @ <>
+found @ path.parent "tests.f" path.join found @ <>
+|| ||
+if
+    "Path resolution mismatch!" .cr
+    exit_failure quit
+then
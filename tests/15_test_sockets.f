@@ -0,0 +1,219 @@
+
+( Listen on any free port on the loopback interface, and connect to ourselves. )
+"127.0.0.1" 0 socket.tcp.listen variable! listener
+listener @ socket.local-address@ variable! port drop
+
+"127.0.0.1" port @ socket.tcp.connect variable! client
+listener @ socket.accept variable! server
+
+"Hello over TCP." client @ file.line!
+server @ file.line@ variable! received
+
+"Received:      " . received @ .cr
+
+received @ "Hello over TCP." <>
+if
+    "TCP line mismatch!" .cr
+    exit_failure quit
+then
+
+( Shutting down the client's side should let the server read to the end of the stream. )
+"Goodbye." client @ file.!
+client @ socket.shutdown.write
+server @ file.string@ variable! remaining
+
+"Remaining:     " . remaining @ .cr
+
+remaining @ "Goodbye." <>
+if
+    "TCP shutdown mismatch!" .cr
+    exit_failure quit
+then
+
+server @ socket.peer-address@ drop "127.0.0.1" <>
+if
+    "TCP peer address mismatch!" .cr
+    exit_failure quit
+then
+
+server @ file.close
+client @ file.close
+listener @ file.close
+
+
+( Send a datagram between two UDP sockets. )
+"127.0.0.1" 0 socket.udp.bind variable! receiver
+"127.0.0.1" 0 socket.udp.bind variable! sender
+
+receiver @ socket.local-address@ variable! receiver_port variable! receiver_host
+
+"Hello over UDP." receiver_host @ receiver_port @ sender @ socket.udp.send-to
+1024 receiver @ socket.udp.recv-from variable! from_port variable! from_host variable! datagram
+
+"Datagram:      " . datagram @ .cr
+
+datagram @ "Hello over UDP." <>
+from_port @ sender @ socket.local-address@ swap drop <>
+||
+if
+    "UDP datagram mismatch!" .cr
+    exit_failure quit
+then
+
+
+( With nothing to receive a timeout should raise an error instead of blocking forever. )
+100 receiver @ socket.timeout!
+
+false variable! timed_out?
+
+try
+    1024 receiver @ socket.udp.recv-from
+catch
+    drop
+    true timed_out? !
+endcatch
+
+"Timed out:     " . timed_out? @ .cr
+
+timed_out? @ '
+if
+    "UDP timeout failed!" .cr
+    exit_failure quit
+then
+
+
+( Timeouts that aren't numbers and sizes that can't hold a datagram are errors. )
+false variable! bad_timeout_failed
+
+try
+    "x" receiver @ socket.timeout!
+catch
+    drop
+    true bad_timeout_failed !
+endcatch
+
+false variable! bad_size_failed
+
+try
+    -1 receiver @ socket.udp.recv-from
+catch
+    drop
+    true bad_size_failed !
+endcatch
+
+bad_timeout_failed @ ' bad_size_failed @ ' ||
+if
+    "Invalid UDP arguments should fail!" .cr
+    exit_failure quit
+then
+
+sender @ file.close
+receiver @ file.close
+
+
+( Serve a Unix domain socket and connect to it. )
+"sorth_test.sock" constant socket_path
+
+socket_path file.exists?
+if
+    socket_path file.delete
+then
+
+socket_path socket.unix.listen variable! unix_listener
+socket_path socket.connect variable! unix_client
+unix_listener @ socket.accept variable! unix_server
+
+"Hello over a Unix socket." unix_client @ file.line!
+unix_server @ file.line@ variable! unix_received
+
+"Unix socket:   " . unix_received @ .cr
+
+unix_received @ "Hello over a Unix socket." <>
+if
+    "Unix socket line mismatch!" .cr
+    exit_failure quit
+then
+
+unix_server @ file.close
+unix_client @ file.close
+unix_listener @ file.close
+
+socket_path file.exists?
+if
+    "Unix socket file was not removed!" .cr
+    exit_failure quit
+then
+
+
+( Poll a pair of connections, only one of which has data waiting. )
+"127.0.0.1" 0 socket.tcp.listen variable! poll_listener
+poll_listener @ socket.local-address@ variable! poll_port drop
+
+"127.0.0.1" poll_port @ socket.tcp.connect variable! quiet_client
+poll_listener @ socket.accept variable! quiet_server
+
+"127.0.0.1" poll_port @ socket.tcp.connect variable! busy_client
+poll_listener @ socket.accept variable! busy_server
+
+"Are you there?" busy_client @ file.line!
+
+{
+    quiet_server @ -> io.poll.read ,
+    busy_server @ -> io.poll.read
+}
+1000 io.poll variable! ready
+
+"Ready:         " . ready @ .cr
+
+ready @ {}.size@ 1 <>
+ready @ { busy_server @ }@ io.poll.read <>
+||
+if
+    "Poll ready set mismatch!" .cr
+    exit_failure quit
+then
+
+( Nothing should be ready to read from the quiet connection, so this times out. )
+{ quiet_server @ -> io.poll.read } 50 io.poll {}.size@ 0 <>
+if
+    "Poll timeout mismatch!" .cr
+    exit_failure quit
+then
+
+( Fds can also be given as an array, either on their own to poll for reading or paired with their
+  interest flags. )
+[ quiet_server @ , busy_server @ ] 1000 io.poll variable! array_ready
+[ [ quiet_server @ , io.poll.write ] ] 1000 io.poll variable! write_ready
+
+"Array ready:   " . array_ready @ . " " . write_ready @ .cr
+
+array_ready @ ready @ <>
+write_ready @ {}.size@ 1 <>
+write_ready @ { quiet_server @ }@ io.poll.write <>
+|| ||
+if
+    "Poll array mismatch!" .cr
+    exit_failure quit
+then
+
+( The timeout has to be none or a number. )
+false variable! bad_poll_timeout_failed
+
+try
+    [ quiet_server @ ] "x" io.poll drop
+catch
+    drop
+    true bad_poll_timeout_failed !
+endcatch
+
+bad_poll_timeout_failed @ '
+if
+    "Polling with a timeout that isn't a number should fail!" .cr
+    exit_failure quit
+then
+
+quiet_client @ file.close
+quiet_server @ file.close
+busy_client @ file.close
+busy_server @ file.close
+poll_listener @ file.close
@@ -0,0 +1,71 @@
+
+( Make a small file to work with. )
+"resource_test.txt" constant resource_path
+
+resource_path file.w/o file.create variable! resource_fd
+"Hello, resources!" resource_fd @ file.!
+resource_fd @ file.close
+
+
+( Check that file.with closes the file once the word has run. )
+none variable! with_fd
+
+: read_resource  ( fd -- )
+    dup with_fd !
+    dup file.size@ swap file.string@ "Read:          " . .cr
+;
+
+resource_path file.r/o ` read_resource file.with
+
+with_fd @ file.is_open?
+if
+    "File left open by file.with!" .cr
+    exit_failure quit
+then
+
+
+( The file should still be closed if the word throws. )
+: throw_resource  ( fd -- )
+    with_fd !
+    "Resource failure!" throw
+;
+
+try
+    resource_path file.r/o ` throw_resource file.with
+    "Expected an exception from file.with!" .cr
+    exit_failure quit
+catch
+    "Caught:        " . .cr
+endcatch
+
+with_fd @ file.is_open?
+if
+    "File left open after an exception!" .cr
+    exit_failure quit
+then
+
+
+( Scoped fds are closed when the word that scoped them returns. )
+none variable! scoped_fd
+
+: open_scoped
+    resource_path file.r/o file.open file.scoped scoped_fd !
+
+    scoped_fd @ file.is_open? '
+    if
+        "Scoped file closed too early!" .cr
+        exit_failure quit
+    then
+;
+
+open_scoped
+
+scoped_fd @ file.is_open?
+if
+    "Scoped file left open!" .cr
+    exit_failure quit
+then
+
+"Scoped fds closed on return." .cr
+
+resource_path file.delete
@@ -0,0 +1,77 @@
+
+( Check hex conversion both ways. )
+"Hello, world!" string.to-buffer variable! text_bytes
+
+text_bytes @ buffer.to-hex variable! text_hex
+"Hex:           " . text_hex @ .cr
+
+text_hex @ "48656c6c6f2c20776f726c6421" <>
+text_hex @ hex.to-buffer buffer.to-string "Hello, world!" <>
+||
+if
+    "Hex conversion mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Check Base64 with and without padding, as well as the URL safe alphabet. )
+"Man" base64.encode "TWFu" <>
+"Ma" base64.encode "TWE=" <> ||
+"M" base64.encode "TQ==" <> ||
+"" base64.encode "" <> ||
+"TWE=" base64.decode buffer.to-string "Ma" <> ||
+"TWE" base64.decode buffer.to-string "Ma" <> ||
+if
+    "Base64 mismatch!" .cr
+    exit_failure quit
+then
+
+"fbff" hex.to-buffer variable! url_bytes
+
+"Standard:      " . url_bytes @ base64.encode .cr
+"URL safe:      " . url_bytes @ base64.encode-url .cr
+
+url_bytes @ base64.encode-url base64.decode-url url_bytes @ <>
+if
+    "URL safe Base64 round trip failed!" .cr
+    exit_failure quit
+then
+
+
+( Invalid input should raise errors. )
+try
+    "T$==" base64.decode
+    "Invalid Base64 was accepted!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "abc" hex.to-buffer
+    "Odd length hex was accepted!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "+f+f" hex.to-buffer
+    "Signed hex digits were accepted!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "c328" hex.to-buffer buffer.to-string
+    "Invalid UTF-8 was accepted!" .cr
+    exit_failure quit
+catch
+    drop
+    "Invalid UTF-8 was rejected." .cr
+endcatch
+
+
+( Show a hex dump as a string. )
+text_bytes @ buffer.hexdump .
@@ -0,0 +1,67 @@
+
+( Check the one shot hashes against known digests. )
+"abc" hash.sha256 variable! abc_sha256
+"SHA-256:       " . abc_sha256 @ .cr
+
+abc_sha256 @ "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad" <>
+"abc" hash.sha1 "a9993e364706816aba3e25717850c26c9cd0d89d" <> ||
+"abc" hash.md5 "900150983cd24fb0d6963f7d28e17f72" <> ||
+"123456789" hash.crc32 "cbf43926" <> ||
+if
+    "Hash digest mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Strings and buffers holding the same bytes should hash the same. )
+"abc" string.to-buffer hash.sha256 abc_sha256 @ <>
+if
+    "Buffer hash mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Check that a streaming hash matches the one shot version. )
+"sha256" hash.new variable! stream_hash
+
+"a" stream_hash @ hash.update
+"b" string.to-buffer stream_hash @ hash.update
+"c" stream_hash @ hash.update
+
+stream_hash @ hash.finish variable! stream_digest
+"Streamed:      " . stream_digest @ buffer.to-hex .cr
+
+stream_digest @ buffer.to-hex abc_sha256 @ <>
+stream_digest @ buffer.size@ 32 <> ||
+if
+    "Streaming hash mismatch!" .cr
+    exit_failure quit
+then
+
+"crc32" hash.new variable! stream_crc
+"12345" stream_crc @ hash.update
+"6789" stream_crc @ hash.update
+
+stream_crc @ hash.finish-hex "cbf43926" <>
+if
+    "Streaming CRC32 mismatch!" .cr
+    exit_failure quit
+then
+
+
+( A finished handle is closed, and unknown algorithms are rejected. )
+try
+    "done" stream_hash @ hash.update
+    "Updating a finished hash should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "sha3" hash.new drop
+    "Unknown hash algorithm should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
@@ -0,0 +1,196 @@
+
+( Any JSON value is accepted at the top level. )
+"[ 1, 2.5, -3e2, true, null, \"text\" ]" json.parse variable! json_array
+
+json_array @ [].size@ 6 <>
+0 json_array @ []@ 1 <> ||
+1 json_array @ []@ 2.5 <> ||
+2 json_array @ []@ -300.0 <> ||
+3 json_array @ []@ true <> ||
+4 json_array @ []@ value.is-none? '  ||
+5 json_array @ []@ "text" <> ||
+"42" json.parse 42 <> ||
+"  \"alone\"  " json.parse "alone" <> ||
+"null" json.parse value.is-none? ' ||
+if
+    "JSON value parsing mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Check escape handling, including surrogate pairs. )
+"\"caf\\u00e9 \\ud83d\\ude00 \\\"q\\\" \\/ \\t\"" json.parse "café 😀 \"q\" / \t" <>
+if
+    "JSON escape mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Values should survive a round trip, and hash table keys come out sorted. )
+{
+    "name" -> "widget" ,
+    "tags" -> [ "a" , "b" ] ,
+    "count" -> 3 ,
+    "missing" -> none
+}
+variable! json_table
+
+json_table @ json.stringify variable! json_compact
+"Compact:       " . json_compact @ .cr
+
+json_compact @ "{\"count\":3,\"missing\":null,\"name\":\"widget\",\"tags\":[\"a\",\"b\"]}" <>
+json_compact @ json.parse json_table @ <> ||
+json_compact @ {}.from_json json_table @ <> ||
+json_table @ {}.to_json json_compact @ <> ||
+if
+    "JSON round trip mismatch!" .cr
+    exit_failure quit
+then
+
+json_table @ 2 json.stringify-pretty .cr
+
+"[ ]" json.parse 2 json.stringify-pretty "[]" <>
+1.0 json.stringify "1.0" <> ||
+"\n\01" json.stringify "\"\\n\\u0001\"" <> ||
+if
+    "JSON formatting mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Errors report where the problem is. )
+try
+    "{\n  \"key\": tru\n}" json.parse drop
+    "Bad JSON should fail to parse!" .cr
+    exit_failure quit
+catch
+    variable! json_error
+
+    "line 2, column 10" json_error @ string.find string.npos =
+    if
+        "JSON error location mismatch!" .cr
+        exit_failure quit
+    then
+
+    "Bad JSON was rejected at it's location." .cr
+endcatch
+
+try
+    "[ 1 ] 2" json.parse drop
+    "Trailing JSON text should fail to parse!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "[ 1, 2 ]" {}.from_json drop
+    "{}.from_json should require an object!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+
+( Values that contain themselves can't be converted. )
+[ 1 , 2 ] variable! json_cycle
+json_cycle @ 1 json_cycle @ []!
+
+try
+    json_cycle @ json.stringify drop
+    "Cyclic JSON should fail to convert!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+
+( Values nested deeper than the parser would accept can't be written either, and the indent has
+  to be a small, non-negative number. )
+[ 1 ] variable! json_deep
+0 variable! json_nesting
+
+begin
+    json_nesting @ 600 <
+while
+    [ json_deep @ ] json_deep !
+    json_nesting @ 1 + json_nesting !
+repeat
+
+try
+    json_deep @ json.stringify drop
+    "Deeply nested JSON should fail to convert!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    { "a" -> 1 } -1 json.stringify-pretty drop
+    "A negative JSON indent should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+
+( Typed JSON round trips structures, filling in defaults for missing fields. )
+# json_endpoint
+    host -> "localhost" ,
+    port -> 8080
+;
+
+# json_settings
+    name
+    endpoints -> [ ] ,
+    retries -> 3
+;
+
+json_settings.new variable! json_settings_value
+"service" json_settings_value json_settings.name!!
+[ json_endpoint.new ] json_settings_value json_settings.endpoints!!
+
+json_settings_value @ json.stringify-typed variable! json_typed
+"Typed:         " . json_typed @ .cr
+
+json_typed @ json.parse-typed variable! json_rebuilt
+
+json_rebuilt @ value.is-structure? '
+json_rebuilt @ json_settings_value @ <> ||
+if
+    "Typed JSON round trip mismatch!" .cr
+    exit_failure quit
+then
+
+"{ \"$type\": \"json_endpoint\", \"port\": 9000 }" json.parse-typed variable! json_partial
+
+json_partial json_endpoint.host@@ "localhost" <>
+json_partial json_endpoint.port@@ 9000 <> ||
+if
+    "Typed JSON defaults mismatch!" .cr
+    exit_failure quit
+then
+
+json_settings_value @ 4 json.stringify-typed-pretty json.parse-typed json_settings_value @ <>
+if
+    "Pretty typed JSON round trip mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Unknown fields and structures are rejected. )
+try
+    "{ \"$type\": \"json_endpoint\", \"path\": \"/\" }" json.parse-typed drop
+    "Unknown structure fields should fail to parse!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "{ \"$type\": \"json_no_such_structure\" }" json.parse-typed drop
+    "Unknown structures should fail to parse!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
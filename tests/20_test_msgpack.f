@@ -0,0 +1,114 @@
+
+( Check the encoding of simple values against the MessagePack spec. )
+1 value.to-msgpack buffer.to-hex "01" <>
+-1 value.to-msgpack buffer.to-hex "ff" <> ||
+300 value.to-msgpack buffer.to-hex "cd012c" <> ||
+-200 value.to-msgpack buffer.to-hex "d1ff38" <> ||
+1.5 value.to-msgpack buffer.to-hex "cb3ff8000000000000" <> ||
+"hi" value.to-msgpack buffer.to-hex "a26869" <> ||
+none value.to-msgpack buffer.to-hex "c0" <> ||
+true value.to-msgpack buffer.to-hex "c3" <> ||
+[ 1 , 2 ] value.to-msgpack buffer.to-hex "920102" <> ||
+"0102" hex.to-buffer value.to-msgpack buffer.to-hex "c4020102" <> ||
+if
+    "MessagePack encoding mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Values should survive a round trip. )
+{
+    "name" -> "cache" ,
+    "sizes" -> [ 1 , -70000 , 9000000000 ] ,
+    "ratio" -> 0.25 ,
+    "enabled" -> false ,
+    "missing" -> none ,
+    "blob" -> "deadbeef" hex.to-buffer
+}
+variable! msgpack_table
+
+msgpack_table @ value.to-msgpack variable! msgpack_bytes
+"Encoded size:  " . msgpack_bytes @ buffer.size@ .cr
+
+msgpack_bytes @ msgpack.to-value msgpack_table @ <>
+if
+    "MessagePack round trip mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Structures are written as extensions and rebuilt by name. )
+# msgpack_point x y ;
+
+msgpack_point.new variable! msgpack_origin
+10 msgpack_origin msgpack_point.x!!
+-20 msgpack_origin msgpack_point.y!!
+
+msgpack_origin @ value.to-msgpack msgpack.to-value variable! msgpack_rebuilt
+
+msgpack_rebuilt @ value.is-structure? '
+msgpack_rebuilt @ msgpack_origin @ <> ||
+if
+    "MessagePack structure round trip mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Shared values stay shared after a round trip. )
+[ 1 , 2 ] variable! msgpack_shared
+[ msgpack_shared @ , msgpack_shared @ ] value.to-msgpack msgpack.to-value variable! msgpack_pair
+
+99 0 msgpack_pair [ 0 ]@@ []!
+0 msgpack_pair [ 1 ]@@ []@ 99 <>
+if
+    "MessagePack shared values were not preserved!" .cr
+    exit_failure quit
+then
+
+
+( Cycles and invalid data are rejected. )
+[ 1 , 2 ] variable! msgpack_cycle
+msgpack_cycle @ 1 msgpack_cycle @ []!
+
+try
+    msgpack_cycle @ value.to-msgpack drop
+    "Cyclic MessagePack should fail to convert!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+( Values nested deeper than the reader would accept can't be written either. )
+[ 1 ] variable! msgpack_deep
+0 variable! msgpack_nesting
+
+begin
+    msgpack_nesting @ 600 <
+while
+    [ msgpack_deep @ ] msgpack_deep !
+    msgpack_nesting @ 1 + msgpack_nesting !
+repeat
+
+try
+    msgpack_deep @ value.to-msgpack drop
+    "Deeply nested MessagePack should fail to convert!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "c1" hex.to-buffer msgpack.to-value drop
+    "Invalid MessagePack should fail to read!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+try
+    "92" hex.to-buffer msgpack.to-value drop
+    "Truncated MessagePack should fail to read!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
@@ -0,0 +1,94 @@
+
+( Quoted fields can hold delimiters, quotes, and new lines. )
+"name,notes\r\nwidget,\"small, blue\"\n\ngadget,\"says \"\"hi\"\"\nthen leaves\"\n" "," csv.parse
+variable! csv_rows
+
+csv_rows @ [].size@ 3 <>
+0 1 csv_rows @ []@ []@ "widget" <> ||
+1 1 csv_rows @ []@ []@ "small, blue" <> ||
+1 2 csv_rows @ []@ []@ "says \"hi\"\nthen leaves" <> ||
+if
+    "CSV parse mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Writing quotes only the fields that need it, and parsing the result gives back the rows. )
+[ [ "id" , "label" ] , [ 1 , "a;b" ] , [ 2 , "plain" ] ] ";" csv.write variable! csv_text
+"Written:       " . csv_text @ .cr
+
+csv_text @ "id;label\n1;\"a;b\"\n2;plain\n" <>
+csv_text @ ";" csv.parse [ [ "id" , "label" ] , [ "1" , "a;b" ] , [ "2" , "plain" ] ] <> ||
+if
+    "CSV write mismatch!" .cr
+    exit_failure quit
+then
+
+
+( With a header row each record becomes a hash table. )
+"id,label\n7,seven\n8\n" "," csv.parse.header variable! csv_records
+
+csv_records @ [].size@ 2 <>
+0 csv_records @ []@ { "label" }@ "seven" <> ||
+1 csv_records @ []@ { "id" }@ "8" <> ||
+1 csv_records @ []@ { "label" }@ value.is-none? ' ||
+if
+    "CSV header parse mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Rows can be streamed to and from files one at a time. )
+"csv_test.csv" constant csv_path
+
+csv_path file.w/o file.create variable! csv_fd
+[ "city" , "population" ] csv_fd @ "," csv.row!
+[ "Multi\nLine" , 10 ] csv_fd @ "," csv.row!
+csv_fd @ file.close
+
+csv_path file.r/o file.open csv_fd !
+0 variable! csv_count
+
+begin
+    csv_fd @ "," csv.row@ dup value.is-none? '
+while
+    drop
+    csv_count ++!
+repeat
+drop
+
+csv_fd @ file.close
+csv_path file.r/o file.open csv_fd !
+csv_fd @ "," csv.parse variable! csv_file_rows
+csv_fd @ file.close
+csv_path file.delete
+
+csv_count @ 2 <>
+0 1 csv_file_rows @ []@ []@ "Multi\nLine" <> ||
+if
+    "CSV streaming mismatch!" .cr
+    exit_failure quit
+then
+
+
+( A row holding a single empty field isn't written as a blank line, so it survives a round trip. )
+[ [ "" ] , [ "x" ] ] "," csv.write variable! csv_empty_text
+
+"Empty field:  " . csv_empty_text @ .
+
+csv_empty_text @ "\"\"\nx\n" <>
+csv_empty_text @ "," csv.parse [ [ "" ] , [ "x" ] ] <>
+||
+if
+    "CSV empty field mismatch!" .cr
+    exit_failure quit
+then
+
+( Unterminated quotes are reported. )
+try
+    "a,\"open\n" "," csv.parse drop
+    "Unterminated CSV quote should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
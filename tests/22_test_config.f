@@ -0,0 +1,163 @@
+
+( Write a config file that covers the supported subset of TOML. )
+"config_test.toml" constant config_path
+
+config_path file.w/o file.create variable! config_fd
+
+"# Application settings.\n" config_fd @ file.!
+"title = \"Demo \\\"app\\\"\"  # Trailing comment.\n" config_fd @ file.!
+"path = 'C:\\temp'\n" config_fd @ file.!
+"count = 1_000\nmask = 0xff\nratio = 0.5\nenabled = true\n" config_fd @ file.!
+"started = 2023-11-14T22:13:20Z\n" config_fd @ file.!
+"ports = [ 8000,\n          8001, ]\n" config_fd @ file.!
+"point = { x = 1, y = 2 }\n" config_fd @ file.!
+"notes = \"\"\"\nline one\nline two\"\"\"\n" config_fd @ file.!
+"\n[server.limits]\nmax-connections = 10\n" config_fd @ file.!
+"\n[[users]]\nname = \"ann\"\n\n[[users]]\nname = \"bob\"\n" config_fd @ file.!
+
+config_fd @ file.close
+
+config_path config.load variable! config
+
+"Loaded:        " . config @ .cr
+
+config @ { "title" }@ "Demo \"app\"" <>
+config @ { "path" }@ "C:\\temp" <> ||
+config @ { "count" }@ 1000 <> ||
+config @ { "mask" }@ 255 <> ||
+config @ { "ratio" }@ 0.5 <> ||
+config @ { "enabled" }@ ' ||
+config @ { "started" }@ time.to-iso "2023-11-14T22:13:20Z" <> ||
+config @ { "ports" }@ [ 8000 , 8001 ] <> ||
+config @ { "point" }@ { "y" }@ 2 <> ||
+config @ { "notes" }@ "line one\nline two" <> ||
+config @ { "server" }@ { "limits" }@ { "max-connections" }@ 10 <> ||
+config @ { "users" }@ [].size@ 2 <> ||
+1 config @ { "users" }@ []@ { "name" }@ "bob" <> ||
+if
+    "Config load mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Saving and loading again gives back the same values. )
+config @ config_path config.save
+config_path config.load variable! reloaded
+
+"Reloaded:      " . reloaded @ .cr
+
+reloaded @ { "title" }@ config @ { "title" }@ <>
+reloaded @ { "started" }@ time.to-iso "2023-11-14T22:13:20Z" <> ||
+reloaded @ { "point" }@ config @ { "point" }@ <> ||
+reloaded @ { "server" }@ config @ { "server" }@ <> ||
+reloaded @ { "users" }@ config @ { "users" }@ <> ||
+reloaded @ { "notes" }@ config @ { "notes" }@ <> ||
+if
+    "Config round trip mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Errors are reported with the line they were found on. )
+config_path file.w/o file.create config_fd !
+"name = \"first\"\nname = \"second\"\n" config_fd @ file.!
+config_fd @ file.close
+
+try
+    config_path config.load drop
+    "Duplicate config key should fail!" .cr
+    exit_failure quit
+catch
+    variable! config_error
+endcatch
+
+"Error:         " . config_error @ .cr
+config_path file.delete
+
+"(2, 1)" config_error @ string.find string.npos =
+if
+    "Config error is missing the line number!" .cr
+    exit_failure quit
+then
+
+
+( Tables and arrays that contain themselves can't be saved. )
+{ "name" -> "loop" } variable! config_cycle
+config_cycle @ "self" config_cycle @ {}!
+
+[ 1 , 2 ] variable! config_array_cycle
+config_array_cycle @ 1 config_array_cycle @ []!
+
+{ "items" -> config_array_cycle @ } variable! config_array_holder
+
+0 variable! config_cycle_errors
+
+try
+    config_cycle @ config_path config.save
+catch
+    drop
+    config_cycle_errors ++!
+endcatch
+
+try
+    config_array_holder @ config_path config.save
+catch
+    drop
+    config_cycle_errors ++!
+endcatch
+
+config_path file.exists?
+if
+    config_path file.delete
+then
+
+config_cycle_errors @ 2 <>
+if
+    "Cyclic config should fail to save!" .cr
+    exit_failure quit
+then
+
+
+( Arrays and tables nested too deeply are reported instead of overflowing the stack. )
+config_path file.w/o file.create config_fd !
+"a = " 100000 "[" string.repeat + config_fd @ file.!
+config_fd @ file.close
+
+try
+    config_path config.load drop
+    "Deeply nested config should fail to load!" .cr
+    exit_failure quit
+catch
+    variable! config_depth_error
+endcatch
+
+config_path file.delete
+
+"(1, " config_depth_error @ string.find string.npos =
+if
+    "Config nesting error is missing the location!" .cr
+    exit_failure quit
+then
+
+[ 1 ] variable! config_deep
+0 variable! config_nesting
+
+begin
+    config_nesting @ 600 <
+while
+    [ config_deep @ ] config_deep !
+    config_nesting ++!
+repeat
+
+try
+    { "deep" -> config_deep @ } config_path config.save
+    "Deeply nested config should fail to save!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+config_path file.exists?
+if
+    config_path file.delete
+then
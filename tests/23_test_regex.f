@@ -0,0 +1,118 @@
+
+( Patterns can be compiled once and reused, or given directly as strings. )
+"^[a-z]+@[a-z]+\\.com$" regex.compile variable! email_regex
+
+"Regex:         " . email_regex @ .cr
+
+"ann@example.com" email_regex @ regex.match? '
+"not an email" email_regex @ regex.match? ||
+"order 66" "[0-9]+" regex.match? ' ||
+if
+    "Regex match mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Find gives the first matching text, or none. )
+"version 1.2.3 and 4.5.6" "[0-9]+\\.[0-9]+\\.[0-9]+" regex.find "1.2.3" <>
+"no digits" "[0-9]+" regex.find value.is-none? ' ||
+if
+    "Regex find mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Captures are an array of groups, or a hash table when the groups are named. )
+"key=value" "(\\w+)=(\\w+)" regex.captures variable! regex_groups
+"2024-05-06" "(?P<year>\\d{4})-(?P<month>\\d{2})-(?P<day>\\d{2})" regex.captures
+variable! regex_named
+
+"Groups:        " . regex_groups @ .cr
+"Named groups:  " . regex_named @ .cr
+
+regex_groups @ [ "key=value" , "key" , "value" ] <>
+regex_named @ { "month" }@ "05" <> ||
+"abc" "(x)" regex.captures value.is-none? ' ||
+if
+    "Regex captures mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Replacements can refer to the capture groups. )
+"a-b-c" "+" "-" regex.replace "a+b-c" <>
+"a-b-c" "+" "-" regex.replace-all "a+b+c" <> ||
+"john smith" "${last}, $1" "(\\w+) (?P<last>\\w+)" regex.replace "smith, john" <> ||
+if
+    "Regex replace mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Splitting on a pattern. )
+"one, two,three ,  four" "\\s*,\\s*" regex.split variable! regex_parts
+"Split:         " . regex_parts @ .cr
+
+regex_parts @ [ "one" , "two" , "three" , "four" ] <>
+if
+    "Regex split mismatch!" .cr
+    exit_failure quit
+then
+
+
+( Invalid patterns are reported as script errors. )
+try
+    "(unclosed" regex.compile drop
+    "Invalid regex should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+
+( The pattern can be read back, but there are no words to change it. )
+email_regex @ regex.pattern@ "^[a-z]+@[a-z]+\\.com$" <>
+defined? regex.pattern! ||
+if
+    "Regex pattern field mismatch!" .cr
+    exit_failure quit
+then
+
+( A pattern that's changed through the field anyway is checked again when the regex is used. )
+"a+" regex.compile variable! changed_regex
+"(unclosed" changed_regex @ 0 #!
+
+try
+    "aaa" changed_regex @ regex.match? drop
+    "A changed invalid pattern should fail!" .cr
+    exit_failure quit
+catch
+    drop
+endcatch
+
+
+( More patterns than the cache holds still work, older ones are just compiled again. )
+0 variable! regex_cache_misses
+
+0 variable! regex_cache_index
+
+begin
+    regex_cache_index @ 100 <
+while
+    "item " regex_cache_index @ to_string +
+    "^item " regex_cache_index @ to_string + "$" +
+    regex.match? '
+    if
+        regex_cache_misses ++!
+    then
+
+    regex_cache_index ++!
+repeat
+
+"ann@example.com" email_regex @ regex.match? '
+regex_cache_misses @ 0 <>
+||
+if
+    "Regex cache mismatch!" .cr
+    exit_failure quit
+then
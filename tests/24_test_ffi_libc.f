@@ -0,0 +1,24 @@
+( Call into the Linux C library, libc.so.6.  Each argument has to be handed to the function from
+  it's own slot in the argument buffer, so calls with several arguments make sure they don't get
+  mixed up. )
+ffi.load libc.so.6 as test-libc
+
+ffi.fn test-libc strlen ffi.string -> ffi.u64
+ffi.fn test-libc strncmp ffi.string ffi.string ffi.u64 -> ffi.i32
+
+
+"Hello, ffi." strlen variable! ffi_length
+"abcdef" "abcxyz" 3 strncmp variable! ffi_prefix_order
+"abcdef" "abcxyz" 4 strncmp variable! ffi_order
+
+"strlen:   " . ffi_length @ .cr
+"strncmp:  " . ffi_prefix_order @ . " " . ffi_order @ .cr
+
+ffi_length @ 11 <>
+ffi_prefix_order @ 0 <>
+ffi_order @ 0 >=
+|| ||
+if
+    "FFI call mismatch!" .cr
+    exit_failure quit
+then